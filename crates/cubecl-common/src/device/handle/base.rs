@@ -25,6 +25,9 @@ pub(crate) trait DeviceHandleSpec<S: DeviceService>: Sized {
     /// If a runner thread for this `device_id` does not exist, it will be spawned.
     fn new(device_id: DeviceId) -> Self;
 
+    /// Whether a service of this type is registered for the given device ID.
+    fn contains(device_id: DeviceId) -> bool;
+
     /// Retrieves the server utilities for this thread.
     fn utilities(&self) -> ServerUtilitiesHandle;
 
@@ -62,6 +62,18 @@ impl<S: DeviceService + 'static> DeviceHandleSpec<S> for ChannelDeviceHandle<S>
         }
     }
 
+    fn contains(device_id: DeviceId) -> bool {
+        let runner_id = RunnerId {
+            device: device_id,
+            stage: S::stage(),
+        };
+
+        CHANNELS
+            .lock()
+            .as_ref()
+            .is_some_and(|channels| channels.contains_key(&(runner_id, TypeId::of::<S>())))
+    }
+
     fn utilities(&self) -> ServerUtilitiesHandle {
         self.state.utilities()
     }
@@ -2,7 +2,9 @@ mod base;
 
 pub use base::*;
 
-use crate::device::{DeviceService, ServerUtilitiesHandle};
+use crate::device::{DeviceId, DeviceService, ServerUtilitiesHandle};
+use core::any::TypeId;
+use hashbrown::HashSet;
 
 #[cfg(feature = "std")]
 #[allow(dead_code)]
@@ -23,16 +25,35 @@ type Inner<S> = reentrant::ReentrantMutexDeviceHandle<S>;
 #[cfg(all(not(feature = "std"), not(multi_threading)))]
 type Inner<S> = mutex::MutexDeviceHandle<S>;
 
+// Exclusive services run their tasks on the calling thread, behind an uncontended lock, instead of
+// sending them to the runner thread.
+#[cfg(all(feature = "std", multi_threading))]
+type Exclusive<S> = reentrant::ReentrantMutexDeviceHandle<S>;
+#[cfg(not(all(feature = "std", multi_threading)))]
+type Exclusive<S> = Inner<S>;
+
+/// The services of each device that were [inserted as exclusive](DeviceHandle::insert_exclusive).
+static EXCLUSIVE_SERVICES: spin::Mutex<Option<HashSet<(DeviceId, TypeId)>>> =
+    spin::Mutex::new(None);
+
 /// TODO: Docs
 pub struct DeviceHandle<S: DeviceService> {
-    handle: Inner<S>,
+    handle: Handle<S>,
+}
+
+enum Handle<S: DeviceService> {
+    Shared(Inner<S>),
+    Exclusive(Exclusive<S>),
 }
 
 impl<S: DeviceService> Clone for DeviceHandle<S> {
     fn clone(&self) -> Self {
-        Self {
-            handle: self.handle.clone(),
-        }
+        let handle = match &self.handle {
+            Handle::Shared(handle) => Handle::Shared(handle.clone()),
+            Handle::Exclusive(handle) => Handle::Exclusive(handle.clone()),
+        };
+
+        Self { handle }
     }
 }
 
@@ -42,39 +63,115 @@ impl<S: DeviceService> DeviceHandle<S> {
         Inner::<S>::BLOCKING
     }
 
-    pub fn insert(device_id: super::DeviceId, service: S) -> Result<Self, ServiceCreationError> {
+    /// Whether the calls of this handle block the current thread, which is always the case for
+    /// [exclusive](Self::insert_exclusive) handles, even when [`is_blocking`](Self::is_blocking)
+    /// is false.
+    pub fn blocks(&self) -> bool {
+        match &self.handle {
+            Handle::Shared(_) => Inner::<S>::BLOCKING,
+            Handle::Exclusive(_) => Exclusive::<S>::BLOCKING,
+        }
+    }
+
+    pub fn insert(device_id: DeviceId, service: S) -> Result<Self, ServiceCreationError> {
+        if Self::is_exclusive(device_id) {
+            return Err(ServiceCreationError::new(
+                "Service already created as exclusive".into(),
+            ));
+        }
+
         Ok(Self {
-            handle: <Inner<S> as DeviceHandleSpec<S>>::insert(device_id, service)?,
+            handle: Handle::Shared(<Inner<S> as DeviceHandleSpec<S>>::insert(
+                device_id, service,
+            )?),
         })
     }
 
-    pub fn new(device_id: super::DeviceId) -> Self {
-        Self {
-            handle: <Inner<S> as DeviceHandleSpec<S>>::new(device_id),
+    /// Inserts a service that is only ever used from one thread at a time.
+    ///
+    /// The tasks of the service are executed directly on the calling thread instead of going
+    /// through the runner thread of the device, which removes a cross-thread round trip from
+    /// every call. The handles [created](Self::new) afterward for the same device and service
+    /// use the same mode.
+    ///
+    /// # Notes
+    ///
+    /// It must be called before any other handle to the service of the device is created, and
+    /// fails otherwise. The caller is responsible for not using the service from multiple threads
+    /// at once; the calls stay memory safe, but block each other.
+    pub fn insert_exclusive(device_id: DeviceId, service: S) -> Result<Self, ServiceCreationError> {
+        let mut exclusive = EXCLUSIVE_SERVICES.lock();
+
+        if <Inner<S> as DeviceHandleSpec<S>>::contains(device_id)
+            || <Exclusive<S> as DeviceHandleSpec<S>>::contains(device_id)
+        {
+            return Err(ServiceCreationError::new(
+                "Service already initialized.".into(),
+            ));
         }
+
+        let handle = <Exclusive<S> as DeviceHandleSpec<S>>::insert(device_id, service)?;
+        exclusive
+            .get_or_insert_with(HashSet::new)
+            .insert((device_id, TypeId::of::<S>()));
+
+        Ok(Self {
+            handle: Handle::Exclusive(handle),
+        })
+    }
+
+    pub fn new(device_id: DeviceId) -> Self {
+        let handle = match Self::is_exclusive(device_id) {
+            true => Handle::Exclusive(<Exclusive<S> as DeviceHandleSpec<S>>::new(device_id)),
+            false => Handle::Shared(<Inner<S> as DeviceHandleSpec<S>>::new(device_id)),
+        };
+
+        Self { handle }
+    }
+
+    fn is_exclusive(device_id: DeviceId) -> bool {
+        EXCLUSIVE_SERVICES
+            .lock()
+            .as_ref()
+            .is_some_and(|exclusive| exclusive.contains(&(device_id, TypeId::of::<S>())))
     }
 
     pub fn utilities(&self) -> ServerUtilitiesHandle {
-        self.handle.utilities()
+        match &self.handle {
+            Handle::Shared(handle) => handle.utilities(),
+            Handle::Exclusive(handle) => handle.utilities(),
+        }
     }
 
     pub fn submit_blocking<'a, R: Send, T: FnOnce(&mut S) -> R + Send + 'a>(
         &self,
         task: T,
     ) -> Result<R, CallError> {
-        self.handle.submit_blocking(task)
+        match &self.handle {
+            Handle::Shared(handle) => handle.submit_blocking(task),
+            Handle::Exclusive(handle) => handle.submit_blocking(task),
+        }
     }
 
     pub fn submit<T: FnOnce(&mut S) + Send + 'static>(&self, task: T) {
-        self.handle.submit(task)
+        match &self.handle {
+            Handle::Shared(handle) => handle.submit(task),
+            Handle::Exclusive(handle) => handle.submit(task),
+        }
     }
 
     pub fn flush_queue(&self) {
-        self.handle.flush_queue();
+        match &self.handle {
+            Handle::Shared(handle) => handle.flush_queue(),
+            Handle::Exclusive(handle) => handle.flush_queue(),
+        }
     }
 
     pub fn exclusive<R: Send, T: FnOnce() -> R + Send>(&self, task: T) -> Result<R, CallError> {
-        self.handle.exclusive(task)
+        match &self.handle {
+            Handle::Shared(handle) => handle.exclusive(task),
+            Handle::Exclusive(handle) => handle.exclusive(task),
+        }
     }
 }
 
@@ -100,3 +197,53 @@ mod tests_reentrant {
     include!("./tests.rs");
     include!("./tests_recursive.rs");
 }
+
+#[cfg(all(test, multi_threading))]
+mod tests_exclusive {
+    use super::*;
+    use crate::stub::Arc;
+    use std::thread::ThreadId;
+
+    struct ThreadService;
+
+    impl DeviceService for ThreadService {
+        fn init(_device_id: DeviceId) -> Self {
+            ThreadService
+        }
+
+        fn utilities(&self) -> ServerUtilitiesHandle {
+            Arc::new(())
+        }
+    }
+
+    fn task_thread(handle: &DeviceHandle<ThreadService>) -> ThreadId {
+        handle
+            .submit_blocking(|_service| std::thread::current().id())
+            .unwrap()
+    }
+
+    #[test]
+    fn exclusive_services_run_on_the_calling_thread() {
+        let device_id = DeviceId {
+            type_id: 20,
+            index_id: 0,
+        };
+        let handle = DeviceHandle::insert_exclusive(device_id, ThreadService).unwrap();
+        let loaded = DeviceHandle::<ThreadService>::new(device_id);
+
+        assert_eq!(task_thread(&handle), std::thread::current().id());
+        assert_eq!(task_thread(&loaded), std::thread::current().id());
+        assert!(DeviceHandle::<ThreadService>::insert(device_id, ThreadService).is_err());
+    }
+
+    #[test]
+    fn exclusive_services_are_rejected_once_shared() {
+        let device_id = DeviceId {
+            type_id: 20,
+            index_id: 1,
+        };
+        let _shared = DeviceHandle::<ThreadService>::new(device_id);
+
+        assert!(DeviceHandle::insert_exclusive(device_id, ThreadService).is_err());
+    }
+}
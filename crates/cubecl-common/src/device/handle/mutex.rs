@@ -70,6 +70,14 @@ impl<S: DeviceService + 'static> DeviceHandleSpec<S> for MutexDeviceHandle<S> {
         }
     }
 
+    fn contains(device_id: DeviceId) -> bool {
+        DEVICE_REGISTRY
+            .lock()
+            .as_ref()
+            .and_then(|registry| registry.get(&device_id))
+            .is_some_and(|services| services.contains_key(&TypeId::of::<S>()))
+    }
+
     fn utilities(&self) -> ServerUtilitiesHandle {
         self.state.utilities.clone()
     }
@@ -31,6 +31,23 @@ impl<S: DeviceService> DeviceHandleSpec<S> for ReentrantMutexDeviceHandle<S> {
         Self::locate(device_id)
     }
 
+    fn contains(device_id: DeviceId) -> bool {
+        let lock = {
+            let global = GLOBAL.lock();
+            match global
+                .state
+                .as_ref()
+                .and_then(|state| state.states.get(&device_id))
+            {
+                Some(lock) => lock.clone(),
+                None => return false,
+            }
+        };
+
+        let state = lock.lock.lock();
+        state.map.borrow().contains_key(&TypeId::of::<S>())
+    }
+
     fn utilities(&self) -> ServerUtilitiesHandle {
         let state = self.lock.lock.lock();
         state
@@ -13,6 +13,7 @@ use crate::prelude::AddressType;
 pub struct KernelIntegrator {
     expansion: KernelExpansion,
     buffer_bindings: Vec<KernelArg>,
+    uniform_bindings: Vec<KernelArg>,
     scalar_bindings: Vec<ScalarKernelArg>,
     tensor_maps: Vec<KernelArg>,
 }
@@ -21,7 +22,9 @@ pub struct KernelIntegrator {
 #[derive(Clone)]
 pub struct KernelExpansion {
     pub buffers: Vec<BufferInfo>,
+    pub uniforms: Vec<UniformInfo>,
     pub scalars: Vec<ScalarInfo>,
+    pub spec_constants: Vec<StorageType>,
     pub tensor_maps: Vec<BufferInfo>,
     pub scope: Scope,
 }
@@ -85,6 +88,15 @@ pub struct BufferInfo {
     pub has_extended_meta: bool,
 }
 
+/// Information related to a uniform buffer binding.
+#[derive(Clone, Debug)]
+pub struct UniformInfo {
+    pub id: Id,
+    pub item: Type,
+    /// The number of items in the buffer
+    pub length: usize,
+}
+
 /// Information related to a scalar input.
 #[derive(Clone, Debug)]
 pub struct ScalarInfo {
@@ -98,6 +110,7 @@ impl KernelIntegrator {
         Self {
             expansion: info,
             buffer_bindings: Default::default(),
+            uniform_bindings: Default::default(),
             scalar_bindings: Default::default(),
             tensor_maps: Default::default(),
         }
@@ -107,6 +120,7 @@ impl KernelIntegrator {
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn integrate(mut self, settings: KernelSettings) -> KernelDefinition {
         self.register_buffers();
+        self.register_uniforms();
         self.register_scalars();
         self.register_tensor_maps();
 
@@ -114,8 +128,10 @@ impl KernelIntegrator {
 
         KernelDefinition {
             buffers: self.buffer_bindings,
+            uniforms: self.uniform_bindings,
             tensor_maps: self.tensor_maps,
             scalars: self.scalar_bindings,
+            spec_constants: self.expansion.spec_constants,
             cube_dim: settings.cube_dim,
             body: self.expansion.scope,
             options: settings.options,
@@ -134,6 +150,18 @@ impl KernelIntegrator {
         }
     }
 
+    fn register_uniforms(&mut self) {
+        for uniform in self.expansion.uniforms.drain(..) {
+            self.uniform_bindings.push(KernelArg {
+                id: uniform.id,
+                ty: uniform.item,
+                visibility: Visibility::Read,
+                has_extended_meta: false,
+                size: Some(uniform.length),
+            });
+        }
+    }
+
     fn register_scalars(&mut self) {
         for scalar in self.expansion.scalars.drain(..) {
             self.scalar_bindings.push(ScalarKernelArg {
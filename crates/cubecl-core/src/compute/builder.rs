@@ -2,7 +2,7 @@ use alloc::{rc::Rc, vec::Vec};
 use core::sync::atomic::{AtomicI8, Ordering};
 
 use crate::{
-    BufferInfo, KernelExpansion, KernelIntegrator, KernelSettings, ScalarInfo,
+    BufferInfo, KernelExpansion, KernelIntegrator, KernelSettings, ScalarInfo, UniformInfo,
     ir::{Id, Type},
     prelude::KernelDefinition,
 };
@@ -20,7 +20,9 @@ pub struct KernelBuilder {
     /// Cube [scope](Scope).
     pub scope: Scope,
     buffers: Vec<BufferInfo>,
+    uniforms: Vec<UniformInfo>,
     scalars: BTreeMap<StorageType, usize>,
+    spec_constants: Vec<StorageType>,
     tensor_maps: Vec<BufferInfo>,
 }
 
@@ -35,6 +37,17 @@ impl KernelBuilder {
         expand
     }
 
+    /// Register a specialization constant and return the [element](ManagedVariable) to be used
+    /// for kernel expansion.
+    pub fn spec_constant(&mut self, storage: StorageType) -> ManagedVariable {
+        let id = self.spec_constants.len() as Id;
+        self.spec_constants.push(storage);
+        ManagedVariable::Plain(Variable::new(
+            VariableKind::SpecConstant(id),
+            Type::new(storage),
+        ))
+    }
+
     fn buffer_id(&self) -> Id {
         self.buffers.len() as Id + self.tensor_maps.len() as Id
     }
@@ -122,6 +135,17 @@ impl KernelBuilder {
         self.scope.input(id, item)
     }
 
+    /// Register a uniform array of `length` items and return the [element](ManagedVariable) to be
+    /// used for kernel expansion.
+    pub fn uniform_array(&mut self, item: Type, length: usize) -> ManagedVariable {
+        let id = self.uniforms.len() as Id;
+        self.uniforms.push(UniformInfo { id, item, length });
+        ManagedVariable::Plain(Variable::new(
+            VariableKind::UniformArray { id, length },
+            item,
+        ))
+    }
+
     pub fn runtime_properties(&mut self, properties: TargetProperties) {
         self.scope.runtime_properties = Rc::new(properties);
     }
@@ -140,7 +164,9 @@ impl KernelBuilder {
         KernelIntegrator::new(KernelExpansion {
             scope: self.scope,
             buffers: self.buffers,
+            uniforms: self.uniforms,
             scalars,
+            spec_constants: self.spec_constants,
             tensor_maps: self.tensor_maps,
         })
         .integrate(settings)
@@ -163,7 +189,9 @@ impl KernelBuilder {
         Self {
             scope: Scope::root(debug),
             buffers: Default::default(),
+            uniforms: Default::default(),
             scalars: Default::default(),
+            spec_constants: Default::default(),
             tensor_maps: Default::default(),
         }
     }
@@ -6,7 +6,7 @@ use crate::prelude::{ArrayArg, TensorArg, TensorMapArg, TensorMapKind};
 use crate::{InfoBuilder, KernelSettings, ScalarArgType};
 #[cfg(feature = "std")]
 use core::cell::RefCell;
-use cubecl_ir::{AddressType, Scope, StorageType, Type};
+use cubecl_ir::{AddressType, ConstantValue, Scope, StorageType, Type};
 use cubecl_runtime::server::{Binding, CubeCount, TensorMapBinding};
 use cubecl_runtime::{
     client::ComputeClient,
@@ -24,7 +24,9 @@ std::thread_local! {
 /// Prepare a kernel for [launch](KernelLauncher::launch).
 pub struct KernelLauncher<R: Runtime> {
     buffers: Vec<Binding>,
+    uniforms: Vec<Binding>,
     tensor_maps: Vec<TensorMapBinding>,
+    spec_constants: Vec<ConstantValue>,
     address_type: AddressType,
     pub settings: KernelSettings,
     #[cfg(not(feature = "std"))]
@@ -65,6 +67,11 @@ impl<R: Runtime> KernelLauncher<R> {
         self.with_info(|info| info.scalars.push_raw(bytes, dtype));
     }
 
+    /// Register the value of a specialization constant to be launched.
+    pub fn register_spec_constant(&mut self, value: ConstantValue) {
+        self.spec_constants.push(value);
+    }
+
     /// Launch the kernel.
     #[track_caller]
     pub fn launch<K: CubeKernel>(
@@ -117,7 +124,9 @@ impl<R: Runtime> KernelLauncher<R> {
         let info = self.with_info(|info| info.finish(address_type));
 
         bindings.buffers = self.buffers;
+        bindings.uniforms = self.uniforms;
         bindings.tensor_maps = self.tensor_maps;
+        bindings.spec_constants = self.spec_constants;
         bindings.info = info;
 
         bindings
@@ -186,6 +195,11 @@ impl<R: Runtime> KernelLauncher<R> {
         Some(array.handle)
     }
 
+    /// Push a new uniform buffer to the state.
+    pub fn register_uniform(&mut self, binding: Binding) {
+        self.uniforms.push(binding);
+    }
+
     /// Push a new tensor to the state.
     pub fn register_tensor_map<K: TensorMapKind>(&mut self, map: TensorMapArg<R, K>, ty: Type) {
         let binding = self
@@ -203,7 +217,9 @@ impl<R: Runtime> KernelLauncher<R> {
             address_type: settings.address_type,
             settings,
             buffers: Vec::new(),
+            uniforms: Vec::new(),
             tensor_maps: Vec::new(),
+            spec_constants: Vec::new(),
             _runtime: PhantomData,
             #[cfg(not(feature = "std"))]
             info: InfoBuilder::default(),
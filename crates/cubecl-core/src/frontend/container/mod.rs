@@ -7,6 +7,7 @@ mod sequence;
 mod shared_memory;
 mod slice;
 mod tensor;
+mod uniform;
 mod vector;
 
 pub(crate) use base::*;
@@ -19,4 +20,5 @@ pub use sequence::*;
 pub use shared_memory::*;
 pub use slice::*;
 pub use tensor::*;
+pub use uniform::*;
 pub use vector::*;
@@ -0,0 +1,89 @@
+use core::{marker::PhantomData, ops::Deref};
+
+use cubecl_runtime::{runtime::Runtime, server::Binding};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compute::{KernelBuilder, KernelLauncher},
+    prelude::{Array, CubeIndex, CubePrimitive, CubeType, LaunchArg, NativeExpand},
+    unexpanded,
+};
+
+/// A read-only array bound as a uniform buffer.
+///
+/// Uniform buffers are limited in size by
+/// [`max_uniform_buffer_size`](crate::ir::HardwareProperties::max_uniform_buffer_size), and their
+/// length is fixed when the kernel is compiled, but backends can serve them from faster constant
+/// memory than storage buffers. In the kernel, the array is read like an [`Array`].
+///
+/// On WGPU, uniform arrays must have a stride of 16 bytes, e.g. `Vector<f32, 4>` items.
+pub struct UniformArray<E> {
+    _val: PhantomData<E>,
+}
+
+impl<C: CubeType> CubeType for UniformArray<C> {
+    type ExpandType = NativeExpand<Array<C>>;
+}
+
+impl<C: CubeType> CubeType for &UniformArray<C> {
+    type ExpandType = NativeExpand<Array<C>>;
+}
+
+impl<E: CubePrimitive> CubeIndex for UniformArray<E> {
+    type Output = E;
+    type Idx = usize;
+}
+
+impl<T: CubePrimitive> Deref for UniformArray<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unexpanded!()
+    }
+}
+
+/// The buffer of a [`UniformArray`], with its length in items.
+pub struct UniformArrayArg<R: Runtime> {
+    handle: Binding,
+    length: usize,
+    runtime: PhantomData<R>,
+}
+
+impl<R: Runtime> UniformArrayArg<R> {
+    /// Create a new uniform array argument.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must hold at least `length` items.
+    pub unsafe fn from_raw_parts(handle: cubecl_runtime::server::Handle, length: usize) -> Self {
+        Self {
+            handle: handle.binding(),
+            length,
+            runtime: PhantomData,
+        }
+    }
+}
+
+/// Only the length of the array, which is part of the compiled kernel.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct UniformArrayCompilationArg {
+    length: usize,
+}
+
+impl<C: CubePrimitive> LaunchArg for UniformArray<C> {
+    type RuntimeArg<R: Runtime> = UniformArrayArg<R>;
+    type CompilationArg = UniformArrayCompilationArg;
+
+    fn register<R: Runtime>(
+        arg: Self::RuntimeArg<R>,
+        launcher: &mut KernelLauncher<R>,
+    ) -> Self::CompilationArg {
+        launcher.register_uniform(arg.handle);
+        UniformArrayCompilationArg { length: arg.length }
+    }
+
+    fn expand(arg: &Self::CompilationArg, builder: &mut KernelBuilder) -> NativeExpand<Array<C>> {
+        let ty = C::as_type(&builder.scope);
+        builder.uniform_array(ty, arg.length).into()
+    }
+}
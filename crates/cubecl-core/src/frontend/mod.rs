@@ -21,6 +21,7 @@ mod plane;
 mod polyfills;
 mod runtime_option;
 mod scalar;
+mod spec_constant;
 mod topology;
 mod trigonometry;
 mod validation;
@@ -39,6 +40,7 @@ pub use plane::*;
 pub use polyfills::*;
 pub use runtime_option::*;
 pub use scalar::*;
+pub use spec_constant::*;
 pub use synchronization::*;
 pub use topology::*;
 pub use trigonometry::*;
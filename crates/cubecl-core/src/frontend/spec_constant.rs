@@ -0,0 +1,107 @@
+use cubecl::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    self as cubecl, intrinsic,
+    ir::{ConstantValue, ElemType, ManagedVariable},
+};
+
+#[derive(Clone, Copy, Debug)]
+/// A scalar whose value is baked into the kernel when it is launched.
+///
+/// Unlike an [`InputScalar`], the value isn't read from memory: backends specialize the compiled
+/// kernel for it, as pipeline-overridable constants on WGPU or defines on CUDA and HIP. The value
+/// isn't part of the kernel id, so launching with a new value doesn't expand or compile the kernel
+/// again, only creates a new specialized pipeline.
+pub struct SpecConstant {
+    value: ConstantValue,
+    dtype: StorageType,
+}
+
+#[derive(Clone)]
+pub struct SpecConstantExpand {
+    pub expand: ManagedVariable,
+}
+
+impl CubeType for SpecConstant {
+    type ExpandType = SpecConstantExpand;
+}
+
+impl IntoMut for SpecConstantExpand {
+    fn into_mut(self, _scope: &mut Scope) -> Self {
+        self
+    }
+}
+
+impl CubeDebug for SpecConstantExpand {}
+
+impl SpecConstant {
+    /// Creates a [`SpecConstant`] from the given element and dtype.
+    ///
+    /// # Panics
+    ///
+    /// If the given numeric element can't be transformed into the passed [`ElemType`].
+    pub fn new<E: num_traits::ToPrimitive>(val: E, dtype: impl Into<StorageType>) -> Self {
+        let dtype: StorageType = dtype.into();
+        let value = match dtype.elem_type() {
+            ElemType::Float(_) => val.to_f64().map(ConstantValue::Float),
+            ElemType::Int(_) => val.to_i64().map(ConstantValue::Int),
+            ElemType::UInt(_) => val.to_u64().map(ConstantValue::UInt),
+            ElemType::Bool => val.to_u64().map(|val| ConstantValue::Bool(val != 0)),
+        };
+        let value = value.unwrap_or_else(|| panic!("Value can't be represented as {dtype}"));
+
+        SpecConstant { value, dtype }
+    }
+}
+
+#[cube]
+impl SpecConstant {
+    /// Reads the constant with the given element type.
+    ///
+    /// Performs casting if necessary.
+    pub fn get<C: Scalar>(&self) -> C {
+        intrinsic!(|scope| {
+            let dtype = C::as_type(scope);
+            if self.expand.ty == dtype {
+                return self.expand.into();
+            }
+            let new_var = scope.create_local(dtype);
+            cast::expand::<C, C>(scope, self.expand.into(), new_var.clone().into());
+            new_var.into()
+        })
+    }
+}
+
+impl LaunchArg for SpecConstant {
+    type RuntimeArg<R: Runtime> = SpecConstant;
+    type CompilationArg = SpecConstantCompilationArg;
+
+    fn register<R: Runtime>(
+        arg: Self::RuntimeArg<R>,
+        launcher: &mut KernelLauncher<R>,
+    ) -> Self::CompilationArg {
+        launcher.register_spec_constant(arg.value);
+        SpecConstantCompilationArg::new(arg.dtype)
+    }
+
+    fn expand(
+        arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> <Self as CubeType>::ExpandType {
+        let expand = builder.spec_constant(arg.ty);
+        SpecConstantExpand { expand }
+    }
+}
+
+/// Only the type of the constant, so kernels launched with different values share their id.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SpecConstantCompilationArg {
+    ty: StorageType,
+}
+
+impl SpecConstantCompilationArg {
+    pub fn new(ty: StorageType) -> Self {
+        Self { ty }
+    }
+}
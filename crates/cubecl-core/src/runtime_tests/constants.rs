@@ -29,6 +29,63 @@ pub fn test_constant_array<R: Runtime>(client: ComputeClient<R>) {
     assert_eq!(actual[0], 5.0);
 }
 
+#[cube(launch)]
+fn spec_constant_kernel(out: &mut Array<u32>, value: SpecConstant) {
+    if UNIT_POS == 0 {
+        out[0] = value.get::<u32>() * 2;
+    }
+}
+
+pub fn test_spec_constant<R: Runtime>(client: ComputeClient<R>) {
+    let ty = u32::as_type_native_unchecked().storage_type();
+
+    for value in [3u32, 7] {
+        let handle = client.create_from_slice(u32::as_bytes(&[0]));
+
+        spec_constant_kernel::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            unsafe { ArrayArg::from_raw_parts(handle.clone(), 1) },
+            SpecConstant::new(value, ty),
+        );
+
+        let actual = client.read_one_unchecked(handle);
+        let actual = u32::from_bytes(&actual);
+
+        assert_eq!(actual[0], value * 2);
+    }
+}
+
+#[cube(launch)]
+fn uniform_array_kernel(out: &mut Array<u32>, params: &UniformArray<Vector<u32, Const<4>>>) {
+    if UNIT_POS == 0 {
+        out[0] = params[1][2] + params.len() as u32;
+    }
+}
+
+pub fn test_uniform_array<R: Runtime>(client: ComputeClient<R>) {
+    if client.properties().hardware.max_uniform_buffer_size == 0 {
+        return;
+    }
+
+    let handle = client.create_from_slice(u32::as_bytes(&[0]));
+    let params = client.create_from_slice(u32::as_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]));
+
+    uniform_array_kernel::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { ArrayArg::from_raw_parts(handle.clone(), 1) },
+        unsafe { UniformArrayArg::from_raw_parts(params, 2) },
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = u32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 8);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_constants {
@@ -40,5 +97,17 @@ macro_rules! testgen_constants {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::constants::test_constant_array::<TestRuntime>(client);
         }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_spec_constant() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::constants::test_spec_constant::<TestRuntime>(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_uniform_array() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::constants::test_uniform_array::<TestRuntime>(client);
+        }
     };
 }
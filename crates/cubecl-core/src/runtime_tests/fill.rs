@@ -0,0 +1,39 @@
+use crate::Runtime;
+
+pub fn test_fill_zeros<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let handle = client.create_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    client.fill(handle.clone(), &[0]).unwrap();
+
+    let actual = client.read_one_unchecked(handle);
+    assert_eq!(&actual[..], &[0; 8]);
+}
+
+pub fn test_fill_pattern<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let handle = client.empty(12);
+
+    client.fill(handle.clone(), &[1, 2, 3]).unwrap();
+
+    let actual = client.read_one_unchecked(handle);
+    assert_eq!(&actual[..], &[1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_fill {
+    () => {
+        use super::*;
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_fill_zeros() {
+            cubecl_core::runtime_tests::fill::test_fill_zeros::<TestRuntime>();
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_fill_pattern() {
+            cubecl_core::runtime_tests::fill::test_fill_pattern::<TestRuntime>();
+        }
+    };
+}
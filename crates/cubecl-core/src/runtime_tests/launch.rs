@@ -1,10 +1,12 @@
 use std::println;
 
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 use crate::{self as cubecl, as_bytes};
 use cubecl::prelude::*;
-use cubecl_runtime::server::{ResourceLimitError, ServerError};
+use cubecl_common::stream_id::StreamId;
+use cubecl_runtime::server::{Handle, ResourceLimitError, ServerError};
 
 #[derive(CubeLaunch, CubeType)]
 pub struct ComptimeTag {
@@ -285,6 +287,113 @@ pub fn test_max_units_error<R: Runtime>(client: ComputeClient<R>) {
     }
 }
 
+pub fn test_buffer_size_error<R: Runtime>(client: ComputeClient<R>) {
+    // Bindings aren't validated on CPU
+    if client.properties().hardware.num_cpu_cores.is_some() {
+        return;
+    }
+
+    // 6 bytes can't hold a whole number of `f32`.
+    let handle = client.create_from_slice(&[0u8; 6]);
+
+    kernel_without_generics::launch(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { ArrayArg::from_raw_parts(handle, 1) },
+    );
+
+    let result = client.flush();
+
+    match result {
+        Err(ServerError::ServerUnhealthy { mut errors, .. }) => match errors.remove(0) {
+            ServerError::Launch(LaunchError::BindingSize {
+                index,
+                size,
+                elem_size,
+                ..
+            }) => {
+                assert_eq!(index, 0);
+                assert_eq!(size, 6);
+                assert_eq!(elem_size, size_of::<f32>());
+            }
+            other => panic!("Should be binding size error, is {other:?}"),
+        },
+        other => panic!("Should be unhealthy server error, is {other:?}"),
+    }
+}
+
+pub fn test_dynamic_count<R: Runtime>(client: ComputeClient<R>) {
+    for (count, expected) in [([1u32, 1, 1], 5.0), ([0, 1, 1], 0.0)] {
+        let handle = client.create_from_slice(f32::as_bytes(&[0.0, 1.0]));
+        let count = client.create_from_slice(u32::as_bytes(&count));
+
+        kernel_without_generics::launch(
+            &client,
+            CubeCount::Dynamic(count.binding()),
+            CubeDim::new_1d(1),
+            unsafe { ArrayArg::from_raw_parts(handle.clone(), 2) },
+        );
+
+        let actual = client.read_one_unchecked(handle);
+        let actual = f32::from_bytes(&actual);
+
+        assert_eq!(actual[0], expected);
+    }
+}
+
+pub fn test_launch_after_encoding_panic<R: Runtime>(client: ComputeClient<R>) {
+    // The memory of this handle is never initialized, so encoding a dispatch with it panics on the
+    // server after the kernel has started to be recorded.
+    let uninitialized = Handle::new(StreamId::current(), 12);
+    let handle = client.create_from_slice(f32::as_bytes(&[0.0, 1.0]));
+
+    kernel_without_generics::launch(
+        &client,
+        CubeCount::Dynamic(uninitialized.binding()),
+        CubeDim::new_1d(1),
+        unsafe { ArrayArg::from_raw_parts(handle.clone(), 2) },
+    );
+    // The panicked dispatch is reported as an error of the stream.
+    let _ = cubecl_common::future::block_on(client.sync());
+
+    kernel_without_generics::launch(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { ArrayArg::from_raw_parts(handle.clone(), 2) },
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 5.0);
+}
+
+pub fn test_max_in_flight<R: Runtime>(client: ComputeClient<R>) {
+    client.set_max_in_flight(1);
+
+    let handles = (0..8)
+        .map(|_| client.create_from_slice(f32::as_bytes(&[0.0, 1.0])))
+        .collect::<Vec<_>>();
+
+    for handle in handles.iter() {
+        kernel_without_generics::launch(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            unsafe { ArrayArg::from_raw_parts(handle.clone(), 2) },
+        );
+    }
+
+    for handle in handles {
+        let actual = client.read_one_unchecked(handle);
+        let actual = f32::from_bytes(&actual);
+
+        assert_eq!(actual[0], 5.0);
+    }
+}
+
 pub fn test_kernel_dynamic_addressing<R: Runtime>(
     client: ComputeClient<R>,
     address_type: AddressType,
@@ -368,6 +477,32 @@ macro_rules! testgen_launch_untyped {
             );
         }
 
+        #[test]
+        fn test_launch_dynamic_count() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_dynamic_count::<TestRuntime>(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_launch_after_encoding_panic() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_launch_after_encoding_panic::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_launch_max_in_flight() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_max_in_flight::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_buffer_size_error() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_buffer_size_error::<TestRuntime>(client);
+        }
+
         #[test]
         #[ignore = "Broken by channel refactor"]
         fn test_launch_shared_memory_error() {
@@ -16,12 +16,14 @@ pub mod debug;
 pub mod different_rank;
 pub mod enums;
 pub mod file;
+pub mod fill;
 pub mod index;
 pub mod launch;
 pub mod metadata;
 pub mod minifloat;
 pub mod numeric;
 pub mod plane;
+pub mod profile;
 pub mod properties;
 pub mod saturating;
 pub mod sequence;
@@ -140,9 +142,11 @@ macro_rules! testgen_untyped {
         cubecl_core::testgen_cmma!();
         cubecl_core::testgen_numeric!();
         cubecl_core::testgen_file!();
+        cubecl_core::testgen_fill!();
         cubecl_core::testgen_metadata!();
         cubecl_core::testgen_topology!();
         cubecl_core::testgen_properties!();
+        cubecl_core::testgen_profile!();
 
         cubecl_core::testgen_constants!();
         cubecl_core::testgen_sync_plane!();
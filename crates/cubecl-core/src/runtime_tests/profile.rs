@@ -0,0 +1,136 @@
+use crate::{self as cubecl};
+use cubecl::prelude::*;
+use cubecl_common::profile::{Instant, TimingMethod};
+
+#[cube(launch)]
+pub fn kernel_busy_loop(output: &mut Array<u32>, iterations: u32) {
+    let mut acc = UNIT_POS;
+    for i in 0..iterations {
+        acc = acc * 1664525 + i;
+    }
+    output[UNIT_POS as usize] = acc;
+}
+
+/// The device time is converted from timestamp ticks with the period of the device, so a wrong
+/// period scales it past the time measured on the host around the same work.
+pub fn test_device_time_fits_in_wall_time<R: Runtime>(client: ComputeClient<R>) {
+    if client.properties().timing_method != TimingMethod::Device {
+        return;
+    }
+
+    let output = client.empty(64 * size_of::<u32>());
+    let start = Instant::now();
+
+    let (_, duration) = client
+        .profile(
+            || {
+                kernel_busy_loop::launch(
+                    &client,
+                    CubeCount::Static(1, 1, 1),
+                    CubeDim::new_1d(64),
+                    unsafe { ArrayArg::from_raw_parts(output.clone(), 64) },
+                    1_000_000,
+                )
+            },
+            "busy_loop",
+        )
+        .unwrap();
+    let device_time = cubecl_common::future::block_on(duration.resolve()).duration();
+    let wall_time = start.elapsed();
+
+    assert!(
+        device_time <= wall_time,
+        "device time {device_time:?} exceeds wall time {wall_time:?}"
+    );
+}
+
+/// Polling the GPU time only reports work that completed since the last poll.
+pub fn test_poll_gpu_time_reports_completed_work<R: Runtime>(client: ComputeClient<R>) {
+    let output = client.empty(64 * size_of::<u32>());
+    cubecl_common::future::block_on(client.sync()).unwrap();
+    client.poll_gpu_time();
+
+    let start = Instant::now();
+    kernel_busy_loop::launch(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(64),
+        unsafe { ArrayArg::from_raw_parts(output, 64) },
+        1_000_000,
+    );
+    cubecl_common::future::block_on(client.sync()).unwrap();
+    let wall_time = start.elapsed();
+
+    // Backends that can't time their work without waiting never report it.
+    let Some(gpu_time) = client.poll_gpu_time() else {
+        return;
+    };
+    assert!(
+        gpu_time <= wall_time,
+        "gpu time {gpu_time:?} exceeds wall time {wall_time:?}"
+    );
+    assert_eq!(client.poll_gpu_time(), None);
+}
+
+/// Polling the GPU time reports completed work without a sync.
+pub fn test_poll_gpu_time_without_sync<R: Runtime>(client: ComputeClient<R>) {
+    let output = client.empty(64 * size_of::<u32>());
+    cubecl_common::future::block_on(client.sync()).unwrap();
+    client.poll_gpu_time();
+
+    kernel_busy_loop::launch(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(64),
+        unsafe { ArrayArg::from_raw_parts(output, 64) },
+        1_000,
+    );
+    client.flush().unwrap();
+
+    let start = Instant::now();
+    while start.elapsed() < core::time::Duration::from_secs(10) {
+        if client.poll_gpu_time().is_some() {
+            return;
+        }
+    }
+
+    // Backends that can't time their work without waiting never report it, even after a sync.
+    cubecl_common::future::block_on(client.sync()).unwrap();
+    assert_eq!(
+        client.poll_gpu_time(),
+        None,
+        "the gpu time was only reported after a sync"
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_profile {
+    () => {
+        use super::*;
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_device_time_fits_in_wall_time() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::profile::test_device_time_fits_in_wall_time::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_poll_gpu_time_reports_completed_work() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::profile::test_poll_gpu_time_reports_completed_work::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_poll_gpu_time_without_sync() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::profile::test_poll_gpu_time_without_sync::<TestRuntime>(
+                client,
+            );
+        }
+    };
+}
@@ -16,16 +16,8 @@ pub fn big_task<F: Float>(input: &Array<u32>, output: &mut Array<F>, num_loop: u
 }
 
 pub fn test_stream<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
-    let client_1 = unsafe {
-        let mut c = client.clone();
-        c.set_stream(StreamId { value: 10000 });
-        c
-    };
-    let client_2 = unsafe {
-        let mut c = client.clone();
-        c.set_stream(StreamId { value: 10001 });
-        c
-    };
+    let client_1 = unsafe { client.clone_onto(StreamId { value: 10000 }) };
+    let client_2 = unsafe { client.clone_onto(StreamId { value: 10001 }) };
 
     let len = 4096;
     let input: Vec<u32> = (0..len as u32).collect();
@@ -54,6 +46,36 @@ pub fn test_stream<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>)
     assert_eq!(actual[0], F::new(1318936000.0));
 }
 
+/// A kernel on one stream writes to memory created on another stream, which has to wait on the
+/// kernel before reading it.
+pub fn test_stream_shared_write<R: Runtime>(client: ComputeClient<R>) {
+    let client_1 = unsafe { client.clone_onto(StreamId { value: 10002 }) };
+    let client_2 = unsafe { client.clone_onto(StreamId { value: 10003 }) };
+
+    let len = 4096;
+    let input: Vec<u32> = (0..len as u32).collect();
+    let input = client_1.create_from_slice(u32::as_bytes(&input));
+
+    for _ in 0..10 {
+        let output = client_2.create_from_slice(f32::as_bytes(&alloc::vec![0.0; len]));
+        unsafe {
+            big_task::launch::<f32, R>(
+                &client_1,
+                CubeCount::Static(len as u32 / 32, 1, 1),
+                CubeDim::new_1d(32),
+                ArrayArg::from_raw_parts(input.clone(), len),
+                ArrayArg::from_raw_parts(output.clone(), len),
+                4096,
+            )
+        };
+
+        let actual = client_2.read_one_unchecked(output);
+        let actual = f32::from_bytes(&actual);
+
+        assert_eq!(actual[0], 2047.5);
+    }
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_stream {
@@ -66,5 +88,11 @@ macro_rules! testgen_stream {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::stream::test_stream::<TestRuntime, FloatType>(client);
         }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_stream_shared_write() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_shared_write::<TestRuntime>(client);
+        }
     };
 }
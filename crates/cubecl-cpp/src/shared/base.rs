@@ -190,6 +190,13 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
             });
         }
 
+        if !kernel.uniforms.is_empty() {
+            return Err(CompilationError::UnsupportedInstruction {
+                reason: "Uniform buffers are not supported by the cpp compiler".into(),
+                backtrace: BackTrace::capture(),
+            });
+        }
+
         self.addr_type = self.compile_type(addr_type.into());
         self.compilation_options = compilation_options.clone();
         self.strategy = strategy;
@@ -1697,6 +1704,10 @@ impl<D: Dialect> CppCompiler<D> {
                 id,
                 elem: self.compile_storage_type(item.storage_type()),
             },
+            gpu::VariableKind::SpecConstant(id) => Variable::SpecConstant {
+                id,
+                elem: self.compile_storage_type(item.storage_type()),
+            },
             gpu::VariableKind::TensorMapInput(id) => {
                 self.flags.inst_tma = true;
                 Variable::TensorMap(id)
@@ -1705,6 +1716,7 @@ impl<D: Dialect> CppCompiler<D> {
                 self.flags.inst_tma = true;
                 Variable::TensorMap(id)
             }
+            gpu::VariableKind::UniformArray { .. } => panic!("Uniform buffers not supported."),
             gpu::VariableKind::LocalMut { id } => Variable::LocalMut {
                 id,
                 item: self.compile_type(item),
@@ -69,6 +69,10 @@ pub enum Variable<D: Dialect> {
         id: Id,
         elem: Elem<D>,
     },
+    SpecConstant {
+        id: Id,
+        elem: Elem<D>,
+    },
     ConstantArray(Id, Item<D>, usize),
     Constant(ConstantValue, Item<D>),
     TensorMap(Id),
@@ -187,6 +191,7 @@ impl<D: Dialect> Component<D> for Variable<D> {
             Variable::Slice { item, .. } => *item,
             Variable::Constant(_, e) => *e,
             Variable::GlobalScalar { elem, .. } => Item::scalar(*elem, false),
+            Variable::SpecConstant { elem, .. } => Item::scalar(*elem, false),
             Variable::WmmaFragment { frag, .. } => Item::scalar(frag.elem, false),
             Variable::Tmp { item, .. } => *item,
             Variable::Pipeline { .. }
@@ -230,6 +235,16 @@ pub(crate) fn format_const<D: Dialect>(number: &ConstantValue, item: &Item<D>) -
     format!("{number}")
 }
 
+/// The compiler options defining the `SPEC_{id}` macros of the specialization constants of a
+/// kernel to the values it's launched with.
+pub fn spec_constant_defines(values: &[ConstantValue]) -> Vec<String> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(id, value)| format!("-DSPEC_{id}={value}"))
+        .collect()
+}
+
 impl<D: Dialect> Display for Variable<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -243,6 +258,8 @@ impl<D: Dialect> Display for Variable<D> {
                 write!(f, "slice_{id}")
             }
             Variable::GlobalScalar { id, elem } => write!(f, "info.scalars_{elem}[{id}]"),
+            // Defined when the kernel is compiled for the values it's launched with.
+            Variable::SpecConstant { id, elem } => write!(f, "{elem}(SPEC_{id})"),
             Variable::Constant(number, item) if item.vectorization <= 1 => {
                 let value = format_const(number, item);
                 write!(f, "{item}({value})")
@@ -512,6 +529,7 @@ impl<D: Dialect> Variable<D> {
             Variable::GlobalInputArray(_, _) => false,
             Variable::GlobalOutputArray(_, _) => false,
             Variable::GlobalScalar { .. } => true,
+            Variable::SpecConstant { .. } => true,
             Variable::LocalArray(_, _, _) => false,
             Variable::LocalConst { .. } => false,
             Variable::LocalMut { .. } => false,
@@ -14,7 +14,7 @@ pub use visitor::elem::register_supported_types;
 
 use cubecl_core::{
     Compiler,
-    ir::{self, StorageType},
+    ir::{self, ConstantValue, StorageType, Variable, VariableKind},
     post_processing::{
         checked_io::CheckedIoProcessor, predicate::PredicateProcessor,
         saturating::SaturatingArithmeticProcessor,
@@ -22,7 +22,7 @@ use cubecl_core::{
     prelude::KernelDefinition,
     server::ExecutionMode,
 };
-use cubecl_opt::OptimizerBuilder;
+use cubecl_opt::{OptimizerBuilder, visit_noop};
 use mlir_engine::MlirEngine;
 
 use crate::compiler::passes::{
@@ -31,7 +31,17 @@ use crate::compiler::passes::{
 };
 
 #[derive(Clone, Debug, Default)]
-pub struct MlirCompiler {}
+pub struct MlirCompiler {
+    /// The values of the specialization constants of the kernel, folded in when it's compiled.
+    spec_constants: Vec<ConstantValue>,
+}
+
+impl MlirCompiler {
+    /// Create a compiler specializing kernels for the values of their constants.
+    pub fn new(spec_constants: Vec<ConstantValue>) -> Self {
+        Self { spec_constants }
+    }
+}
 
 #[derive(Default, Debug)]
 pub struct MlirCompilerOptions {}
@@ -64,7 +74,7 @@ impl Compiler for MlirCompiler {
 
         #[cfg(feature = "mlir-dump")]
         dump_scope(&kernel.body, &kernel.options.kernel_name);
-        let opt = OptimizerBuilder::default()
+        let mut opt = OptimizerBuilder::default()
             .with_transformer(ErfTransform)
             .with_transformer(HypotTransform)
             .with_transformer(RhypotTransform)
@@ -76,6 +86,16 @@ impl Compiler for MlirCompiler {
             .with_processor(PredicateProcessor)
             .optimize(kernel.body.clone(), kernel.cube_dim);
 
+        let spec_constants = self.spec_constants.clone();
+        opt.visit_all(
+            move |_, var| {
+                if let VariableKind::SpecConstant(id) = var.kind {
+                    *var = Variable::constant(spec_constants[id as usize], var.ty);
+                }
+            },
+            visit_noop,
+        );
+
         let mut shared_memories = SharedMemories::default();
         shared_memories.visit(&opt);
 
@@ -6,6 +6,7 @@ use cubecl_core::{
 };
 use cubecl_runtime::{
     logging::ServerLogger,
+    memory_management::MemoryUsageSnapshot,
     storage::BytesResource,
     stream::{StreamFactory, scheduler::SchedulerStreamBackend},
 };
@@ -71,6 +72,7 @@ pub struct CpuStreamFactory {
     memory_properties: MemoryDeviceProperties,
     memory_config: MemoryConfiguration,
     logger: Arc<ServerLogger>,
+    memory_usage: Arc<MemoryUsageSnapshot>,
 }
 
 impl StreamFactory for CpuStreamFactory {
@@ -81,6 +83,7 @@ impl StreamFactory for CpuStreamFactory {
             self.memory_properties.clone(),
             self.memory_config.clone(),
             self.logger.clone(),
+            self.memory_usage.clone(),
         )
     }
 }
@@ -91,12 +94,14 @@ impl ScheduledCpuBackend {
         memory_properties: MemoryDeviceProperties,
         memory_config: MemoryConfiguration,
         logger: Arc<ServerLogger>,
+        memory_usage: Arc<MemoryUsageSnapshot>,
     ) -> Self {
         Self {
             factory: CpuStreamFactory {
                 memory_properties,
                 memory_config,
                 logger,
+                memory_usage,
             },
         }
     }
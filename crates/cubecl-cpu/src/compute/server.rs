@@ -10,11 +10,11 @@ use cubecl_common::{
     backtrace::BackTrace, bytes::Bytes, profile::ProfileDuration, stream_id::StreamId,
 };
 use cubecl_core::{
-    CompilationError, CubeCount, ExecutionMode, MemoryConfiguration, MemoryUsage,
+    ChunkInfo, CompilationError, CubeCount, ExecutionMode, MemoryConfiguration, MemoryUsage,
     future::DynFut,
-    ir::MemoryDeviceProperties,
+    ir::{ConstantValue, MemoryDeviceProperties},
     server::{
-        Binding, ComputeServer, CopyDescriptor, IoError, KernelArguments, ProfileError,
+        Binding, ComputeServer, CopyDescriptor, Handle, IoError, KernelArguments, ProfileError,
         ProfilingToken, ServerCommunication, ServerError, ServerUtilities,
     },
     zspace::{Shape, Strides, strides},
@@ -26,6 +26,7 @@ use cubecl_runtime::{
     id::KernelId,
     logging::ServerLogger,
     memory_management::{ManagedMemoryHandle, MemoryAllocationMode},
+    read_path::ReadPath,
     storage::{BytesStorage, ComputeStorage, ManagedResource},
     stream::scheduler::{SchedulerMultiStream, SchedulerMultiStreamOptions, SchedulerStrategy},
 };
@@ -46,8 +47,12 @@ impl CpuServer {
         memory_config: MemoryConfiguration,
         utilities: Arc<ServerUtilities<CpuServer>>,
     ) -> Self {
-        let backend =
-            ScheduledCpuBackend::new(memory_properties, memory_config, utilities.logger.clone());
+        let backend = ScheduledCpuBackend::new(
+            memory_properties,
+            memory_config,
+            utilities.logger.clone(),
+            utilities.memory_usage.clone(),
+        );
         let config = GlobalConfig::get();
         let max_streams = config.streaming.max_streams;
 
@@ -95,6 +100,7 @@ impl CpuServer {
         kernel: Box<dyn CubeTask<CpuCompiler>>,
         count: CubeCount,
         bindings: BindingsResource,
+        spec_constants: Vec<ConstantValue>,
         kind: ExecutionMode,
     ) -> Result<ScheduleTask, CompilationError> {
         let cube_count = match count {
@@ -121,7 +127,7 @@ impl CpuServer {
             }
         };
 
-        self.prepare_task_inner(kernel, cube_count, bindings, kind)
+        self.prepare_task_inner(kernel, cube_count, bindings, spec_constants, kind)
     }
 
     fn prepare_task_inner(
@@ -129,14 +135,16 @@ impl CpuServer {
         kernel: Box<dyn CubeTask<CpuCompiler>>,
         cube_count: [u32; 3],
         bindings: BindingsResource,
+        spec_constants: Vec<ConstantValue>,
         kind: ExecutionMode,
     ) -> Result<ScheduleTask, CompilationError> {
-        let kernel_id = kernel.id();
+        // The specialization constants are folded into the kernel, so each value is compiled.
+        let kernel_id = kernel.id().spec_constants(&spec_constants);
         let kernel = if let Some(kernel) = self.compilation_cache.get(&kernel_id) {
             kernel
         } else {
             let kernel = kernel.compile(
-                &mut Default::default(),
+                &mut CpuCompiler::new(spec_constants),
                 &MlirCompilerOptions::default(),
                 kind,
                 kernel.address_type(),
@@ -193,10 +201,50 @@ impl ComputeServer for CpuServer {
         self.utilities.clone()
     }
 
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, stream_id: StreamId) {
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
         let stream = self.scheduler.stream(&stream_id);
-        let reserved = stream.empty(size).unwrap();
+        let reserved = stream.empty(size)?;
         stream.bind(reserved, memory);
+        Ok(())
+    }
+
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let stream = self.scheduler.stream(&stream_id);
+        let reserved = stream.empty_aligned(size, alignment)?;
+        stream.bind(reserved, memory);
+        Ok(())
+    }
+
+    fn create_owned(&mut self, handle: Handle, data: Vec<u8>, stream_id: StreamId) {
+        let stream = self.scheduler.stream(&stream_id);
+
+        match stream.adopt(data) {
+            Ok(reserved) => stream.bind(reserved, handle.memory),
+            // Unaligned allocations are copied instead.
+            Err(data) => {
+                let size = data.len();
+                if let Err(err) =
+                    self.initialize_memory(handle.memory.clone(), handle.size(), stream_id)
+                {
+                    panic!("Failed to reserve {size} bytes: {err}");
+                }
+
+                let descriptor =
+                    CopyDescriptor::new(handle.binding(), [size].into(), [1].into(), 1);
+                self.write(vec![(descriptor, Bytes::from_bytes_vec(data))], stream_id);
+            }
+        }
     }
 
     fn read(
@@ -219,6 +267,7 @@ impl ComputeServer for CpuServer {
         }
 
         self.scheduler.execute_streams(streams);
+        let utilities = self.utilities.clone();
 
         Box::pin(async move {
             for result in results {
@@ -227,6 +276,7 @@ impl ComputeServer for CpuServer {
                     Err(err) => return Err(err.into()),
                 }
             }
+            utilities.read_path.record(ReadPath::Mapped);
 
             Ok(resources)
         })
@@ -268,11 +318,26 @@ impl ComputeServer for CpuServer {
         Ok(stream.memory_management.memory_usage())
     }
 
+    fn chunk_map(&mut self, stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError> {
+        let stream = self.scheduler.stream(&stream_id);
+        Ok(stream.memory_management.chunk_map())
+    }
+
     fn memory_cleanup(&mut self, stream_id: StreamId) {
         let stream = self.scheduler.stream(&stream_id);
         stream.memory_management.cleanup(true)
     }
 
+    fn release(&mut self, bindings: Vec<Binding>, stream_id: StreamId) {
+        self.scheduler.execute_streams(vec![stream_id]);
+
+        for binding in bindings {
+            let stream = self.scheduler.stream(&binding.stream);
+            // The handles of the memory are unusable either way.
+            let _ = stream.memory_management.release(binding.memory);
+        }
+    }
+
     unsafe fn launch(
         &mut self,
         kernel: Self::Kernel,
@@ -286,12 +351,19 @@ impl ComputeServer for CpuServer {
             .buffers
             .iter()
             .for_each(|b| self.streams_pool.push(b.stream));
+        let spec_constants = bindings.spec_constants.clone();
         let bindings = self.prepare_bindings(bindings);
-        let task = self.prepare_task(kernel, count, bindings, kind).unwrap();
+        let task = self
+            .prepare_task(kernel, count, bindings, spec_constants, kind)
+            .unwrap();
 
         self.scheduler.register(stream_id, task, &self.streams_pool);
     }
 
+    fn wait_streams(&mut self, dependencies: Vec<StreamId>, _stream_id: StreamId) {
+        self.scheduler.execute_streams(dependencies);
+    }
+
     fn flush(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
@@ -347,6 +419,10 @@ impl ComputeServer for CpuServer {
         let stream = self.scheduler.stream(&stream_id);
         stream.allocation_mode(mode);
     }
+
+    fn is_compiled(&mut self, kernel_id: &KernelId) -> bool {
+        self.compilation_cache.contains_key(kernel_id)
+    }
 }
 
 impl ServerCommunication for CpuServer {
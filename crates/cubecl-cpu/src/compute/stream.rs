@@ -15,6 +15,7 @@ use cubecl_runtime::{
     logging::ServerLogger,
     memory_management::{
         ManagedMemoryHandle, MemoryAllocationMode, MemoryManagement, MemoryManagementOptions,
+        MemoryUsageSnapshot,
     },
     storage::{BytesResource, BytesStorage},
     timestamp_profiler::TimestampProfiler,
@@ -39,13 +40,14 @@ impl CpuStream {
         memory_properties: MemoryDeviceProperties,
         memory_config: MemoryConfiguration,
         logger: Arc<ServerLogger>,
+        memory_usage: Arc<MemoryUsageSnapshot>,
     ) -> Self {
         let memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &memory_properties,
             memory_config,
             logger.clone(),
-            MemoryManagementOptions::new("Main CPU"),
+            MemoryManagementOptions::new("Main CPU").usage_snapshot(memory_usage),
         );
 
         Self {
@@ -116,6 +118,24 @@ impl CpuStream {
         self.memory_management.reserve(size)
     }
 
+    /// Reserves `size` bytes starting at an offset that is a multiple of `alignment` bytes.
+    pub fn empty_aligned(
+        &mut self,
+        size: u64,
+        alignment: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.memory_management.reserve_aligned(size, alignment)
+    }
+
+    /// Takes ownership of `data` as a new chunk of memory without copying it, giving it back when
+    /// the storage can't adopt it.
+    pub fn adopt(&mut self, data: Vec<u8>) -> Result<ManagedMemoryHandle, Vec<u8>> {
+        let size = data.len() as u64;
+        let storage = self.memory_management.storage().adopt(data)?;
+
+        Ok(self.memory_management.register(storage, size))
+    }
+
     /// Maps handles to their corresponding buffers.
     pub fn bind(&mut self, reserved: ManagedMemoryHandle, new: ManagedMemoryHandle) {
         self.memory_management.bind(reserved, new, 0).unwrap();
@@ -53,6 +53,7 @@ impl DeviceService for CpuServer {
             plane_size_max: 1,
             max_bindings: u32::MAX,
             max_shared_memory_size,
+            max_uniform_buffer_size: 0,
             max_cube_count,
             num_cpu_cores: Some(available_parallelism as u32),
             max_units_per_cube: u32::MAX,
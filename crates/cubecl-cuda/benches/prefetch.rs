@@ -0,0 +1,99 @@
+//! Measures the latency of the first kernel touching managed memory, with and without a prefetch
+//! hint.
+//!
+//! The buffers are allocated with `cuMemAllocManaged` and bound to handles with
+//! `CudaServer::register_device_ptr`, since `CubeCL` only allocates device memory. Before each
+//! sample, the pages are migrated back to the host, so the kernel either faults them in or finds
+//! them already prefetched to the device.
+
+use cubecl_common::future::block_on;
+use cubecl_common::stream_id::StreamId;
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_cuda::{CudaDevice, CudaRuntime};
+use cubecl_runtime::server::Handle;
+use std::time::{Duration, Instant};
+
+const MB: usize = 1024 * 1024;
+const SAMPLES: usize = 10;
+
+#[cube(launch)]
+fn touch(output: &mut Array<u32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = ABSOLUTE_POS as u32;
+    }
+}
+
+/// Allocates `size` bytes of managed memory and binds it to a new handle.
+///
+/// The memory is never freed, since the server may reuse its chunk for as long as it lives.
+fn managed(client: &ComputeClient<CudaRuntime>, device: &CudaDevice, size: usize) -> Handle {
+    // SAFETY: The primary context is the one used by the server, and it's made current before
+    // allocating. The memory is attached globally, so any stream of the server can access it.
+    let ptr = unsafe {
+        let device = cudarc::driver::result::device::get(device.index as i32).unwrap();
+        let ctx = cudarc::driver::result::primary_ctx::retain(device).unwrap();
+        cudarc::driver::result::ctx::set_current(ctx).unwrap();
+        cudarc::driver::result::malloc_managed(
+            size,
+            cudarc::driver::sys::CUmemAttach_flags::CU_MEM_ATTACH_GLOBAL,
+        )
+        .unwrap()
+    };
+
+    let stream_id = StreamId::current();
+    let handle = Handle::new(stream_id, size as u64);
+    let memory = handle.clone();
+    client
+        .downcast_server(move |server: &mut <CudaRuntime as Runtime>::Server| {
+            // SAFETY: `ptr` points to `size` bytes of managed memory of the context of the
+            // server, aligned to at least 256 bytes, and it's never freed.
+            unsafe { server.register_device_ptr(memory, ptr, size, stream_id) }
+        })
+        .unwrap();
+
+    handle
+}
+
+fn first_touch(client: &ComputeClient<CudaRuntime>, handle: &Handle, prefetch: bool) -> Duration {
+    let len = handle.size() as usize / core::mem::size_of::<u32>();
+    let cube_dim = CubeDim::new_1d(256);
+    let cube_count = CubeCount::Static((len as u32).div_ceil(cube_dim.x), 1, 1);
+
+    // Migrate the pages left on the device by the previous sample back to the host.
+    client.prefetch(handle.clone(), false);
+    block_on(client.sync()).unwrap();
+
+    let start = Instant::now();
+    if prefetch {
+        client.prefetch(handle.clone(), true);
+    }
+    touch::launch(client, cube_count, cube_dim, unsafe {
+        ArrayArg::from_raw_parts(handle.clone(), len)
+    });
+    block_on(client.sync()).unwrap();
+    start.elapsed()
+}
+
+fn main() {
+    let device = CudaDevice::default();
+    let client = CudaRuntime::client(&device);
+
+    // Warm up the kernel compilation.
+    first_touch(&client, &managed(&client, &device, MB), false);
+
+    for size in [MB, 16 * MB, 256 * MB] {
+        let handle = managed(&client, &device, size);
+
+        for prefetch in [false, true] {
+            let total: Duration = (0..SAMPLES)
+                .map(|_| first_touch(&client, &handle, prefetch))
+                .sum();
+            println!(
+                "size: {} MB, prefetch: {prefetch}, first touch: {:?}",
+                size / MB,
+                total / SAMPLES as u32
+            );
+        }
+    }
+}
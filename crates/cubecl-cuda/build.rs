@@ -3,6 +3,7 @@ use cudarc::driver::sys::CUDA_VERSION;
 fn main() {
     println!("cargo::rustc-check-cfg=cfg(cuda_12050)");
     println!("cargo::rustc-check-cfg=cfg(cuda_12080)");
+    println!("cargo::rustc-check-cfg=cfg(cuda_13020)");
 
     if CUDA_VERSION >= 12050 {
         println!("cargo:rustc-cfg=cuda_12050");
@@ -10,4 +11,7 @@ fn main() {
     if CUDA_VERSION >= 12080 {
         println!("cargo:rustc-cfg=cuda_12080");
     }
+    if CUDA_VERSION >= 13020 {
+        println!("cargo:rustc-cfg=cuda_13020");
+    }
 }
@@ -13,7 +13,7 @@ use cubecl_common::{
 #[cfg(debug_assertions)]
 use cubecl_core::zspace::striding::try_check_pitched_row_major_strides;
 use cubecl_core::{
-    MemoryUsage,
+    ChunkInfo, MemoryUsage,
     future::DynFut,
     server::{
         Binding, CopyDescriptor, ExecutionMode, Handle, IoError, LaunchError, ProfileError,
@@ -25,11 +25,12 @@ use cubecl_runtime::{
     compiler::CubeTask,
     id::KernelId,
     logging::ServerLogger,
-    memory_management::{ManagedMemoryHandle, MemoryAllocationMode, MemoryHandle},
+    memory_management::{AccessHint, ManagedMemoryHandle, MemoryAllocationMode, MemoryHandle},
     stream::ResolvedStreams,
 };
 use cudarc::driver::sys::{
-    CUDA_MEMCPY2D_st, CUmemorytype, CUstream_st, CUtensorMap, cuMemcpy2DAsync_v2,
+    CUDA_MEMCPY2D_st, CUmemorytype, CUpointer_attribute, CUstream_st, CUtensorMap,
+    cuMemcpy2DAsync_v2,
 };
 use std::{ffi::c_void, ops::DerefMut, sync::Arc};
 
@@ -72,6 +73,35 @@ impl<'a> Command<'a> {
         self.streams.cursor
     }
 
+    /// Prefetches the managed memory of the binding to the given device, or to the host when
+    /// `device` is `None`.
+    ///
+    /// Memory that isn't managed is left untouched.
+    pub fn prefetch(&mut self, binding: Binding, device: Option<i32>) -> Result<(), IoError> {
+        let resource = self.resource(binding)?;
+        let mut is_managed: u32 = 0;
+
+        // SAFETY: `resource.ptr` is a valid device pointer from the memory manager, and
+        // `is_managed` is a valid `unsigned int` for the `IS_MANAGED` attribute to be written to.
+        let result = unsafe {
+            cudarc::driver::sys::cuPointerGetAttribute(
+                &mut is_managed as *mut u32 as *mut c_void,
+                CUpointer_attribute::CU_POINTER_ATTRIBUTE_IS_MANAGED,
+                resource.ptr,
+            )
+        };
+        if result.result().is_err() || is_managed == 0 {
+            return Ok(());
+        }
+
+        prefetch_managed(
+            resource.ptr,
+            resource.size,
+            device,
+            self.streams.current().sys,
+        )
+    }
+
     /// Retrieves the gpu memory usage of the current stream.
     ///
     /// # Returns
@@ -81,6 +111,19 @@ impl<'a> Command<'a> {
         self.streams.current().memory_management_gpu.memory_usage()
     }
 
+    /// Retrieves the [layout](ChunkInfo) of the gpu memory chunks of the current stream.
+    pub fn chunk_map(&mut self) -> Vec<ChunkInfo> {
+        self.streams.current().memory_management_gpu.chunk_map()
+    }
+
+    /// Releases the memory of the binding, even if handles to it are still alive.
+    pub fn release(&mut self, binding: Binding) -> Result<(), IoError> {
+        self.streams
+            .get(&binding.stream)
+            .memory_management_gpu
+            .release(binding.memory)
+    }
+
     /// Explicitly cleanup gpu memory on the current stream.
     pub fn memory_cleanup(&mut self) {
         self.streams.current().memory_management_gpu.cleanup(true)
@@ -107,11 +150,67 @@ impl<'a> Command<'a> {
     /// * `Err(IoError)` - If the allocation fails.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn reserve(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
-        let handle = self.streams.current().memory_management_gpu.reserve(size)?;
+        self.reserve_with_hint(size, None)
+    }
+
+    /// Allocates a new GPU memory buffer of the specified size, placed according to the
+    /// [access hint](AccessHint).
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_with_hint(
+        &mut self,
+        size: u64,
+        hint: Option<AccessHint>,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        let handle = self
+            .streams
+            .current()
+            .memory_management_gpu
+            .reserve_with_hint(size, hint)?;
 
         Ok(handle)
     }
 
+    /// Allocates a new GPU memory buffer of the specified size, starting at an offset that is a
+    /// multiple of `alignment` bytes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_aligned(
+        &mut self,
+        size: u64,
+        alignment: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.streams
+            .current()
+            .memory_management_gpu
+            .reserve_aligned(size, alignment)
+    }
+
+    /// Registers `size` bytes of device memory allocated outside of the memory management as a
+    /// chunk of the current stream.
+    ///
+    /// # Safety
+    ///
+    /// See [`GpuStorage::from_device_ptr`](crate::compute::storage::gpu::GpuStorage::from_device_ptr).
+    pub unsafe fn register_device_ptr(&mut self, ptr: u64, size: usize) -> ManagedMemoryHandle {
+        let memory_management = &mut self.streams.current().memory_management_gpu;
+        // SAFETY: Upheld by the caller.
+        let storage = unsafe { memory_management.storage().from_device_ptr(ptr, size) };
+
+        memory_management.register(storage, size as u64)
+    }
+
+    /// Allocates `size` bytes of mapped host memory as a chunk of the current stream, for
+    /// allocations spilled out of the device memory.
+    ///
+    /// The chunk is only used by the returned handle, so device allocations never land in host
+    /// memory, and it's freed along with the handle.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_host(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
+        let memory_management = &mut self.streams.current().memory_management_gpu;
+        let storage = memory_management.storage().alloc_host(size)?;
+
+        Ok(memory_management.register_unshared(storage, size))
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn empty(&mut self, size: u64) -> Result<Handle, IoError> {
         let handle = Handle::new(self.streams.current, size);
@@ -395,6 +494,86 @@ impl<'a> Command<'a> {
         Ok(())
     }
 
+    /// Fills the GPU resource of the binding by repeating the `pattern`.
+    ///
+    /// Patterns of 1, 2 or 4 bytes use a device-side memset, other patterns are uploaded from
+    /// the host.
+    pub fn fill(&mut self, binding: Binding, pattern: &[u8]) -> Result<(), IoError> {
+        let size = binding.size_in_used() as usize;
+
+        if !matches!(pattern.len(), 1 | 2 | 4) {
+            let data = pattern
+                .iter()
+                .copied()
+                .cycle()
+                .take(size)
+                .collect::<Vec<_>>();
+            let descriptor = CopyDescriptor::new(binding, [size].into(), [1].into(), 1);
+            return self.write_to_gpu(descriptor, Bytes::from_bytes_vec(data));
+        }
+
+        let resource = self.resource(binding)?;
+        let stream = self.streams.current().sys;
+        let count = size / pattern.len();
+
+        // SAFETY: `resource.ptr` is a valid GPU allocation of `size` bytes, aligned to the
+        // memory alignment, and `stream` is an initialized CUDA stream.
+        let result = unsafe {
+            match pattern {
+                [value] => {
+                    cudarc::driver::sys::cuMemsetD8Async(resource.ptr, *value, count, stream)
+                }
+                [a, b] => cudarc::driver::sys::cuMemsetD16Async(
+                    resource.ptr,
+                    u16::from_ne_bytes([*a, *b]),
+                    count,
+                    stream,
+                ),
+                [a, b, c, d] => cudarc::driver::sys::cuMemsetD32Async(
+                    resource.ptr,
+                    u32::from_ne_bytes([*a, *b, *c, *d]),
+                    count,
+                    stream,
+                ),
+                _ => unreachable!(),
+            }
+        };
+
+        result.result().map_err(|e| IoError::Unknown {
+            description: format!("CUDA memset failed: {e}"),
+            backtrace: BackTrace::capture(),
+        })
+    }
+
+    /// Whether work submitted to the current stream hasn't completed yet.
+    pub fn has_pending_work(&mut self) -> bool {
+        let stream = self.streams.current().sys;
+
+        // SAFETY: `stream` is an initialized CUDA stream.
+        let result = unsafe { cudarc::driver::sys::cuStreamQuery(stream) };
+
+        // Errors are reported by the next sync, so it isn't skipped.
+        result != cudarc::driver::sys::CUresult::CUDA_SUCCESS
+    }
+
+    /// Copies the GPU resource of the `src` binding into the resource of the `dst` binding.
+    pub fn copy(&mut self, src: Binding, dst: Binding) -> Result<(), IoError> {
+        let src = self.resource(src)?;
+        let dst = self.resource(dst)?;
+        let stream = self.streams.current().sys;
+
+        // SAFETY: Both pointers are valid GPU allocations from the memory manager, `dst` being
+        // at least as large as `src`, and `stream` is an initialized CUDA stream.
+        let result = unsafe {
+            cudarc::driver::sys::cuMemcpyDtoDAsync_v2(dst.ptr, src.ptr, src.size as usize, stream)
+        };
+
+        result.result().map_err(|e| IoError::Unknown {
+            description: format!("CUDA copy failed: {e}"),
+            backtrace: BackTrace::capture(),
+        })
+    }
+
     /// Allocates a new GPU memory buffer and immediately copies contiguous host data into it.
     ///
     /// # Parameters
@@ -449,6 +628,8 @@ impl<'a> Command<'a> {
     /// * `kernel` - The cube task to compile if not cached.
     /// * `mode` - The execution mode for the current kernel.
     /// * `dispatch_count` - The number of thread blocks in the x, y, and z dimensions.
+    /// * `cooperative` - Whether all thread blocks must be co-resident to synchronize grid-wide.
+    /// * `dynamic_shared_mem` - Bytes of shared memory to allocate on top of the kernel's own.
     /// * `tensor_maps` - Tensor maps for structured memory access.
     /// * `resources` - GPU resources (e.g., buffers) used by the kernel.
     /// * `scalars` - Scalar arguments passed to the kernel.
@@ -464,6 +645,8 @@ impl<'a> Command<'a> {
         kernel: Box<dyn CubeTask<CudaCompiler>>,
         mode: ExecutionMode,
         dispatch_count: (u32, u32, u32),
+        cooperative: bool,
+        dynamic_shared_mem: usize,
         tensor_maps: &[CUtensorMap],
         resources: &[GpuResource],
         const_info: Option<*mut c_void>,
@@ -479,6 +662,8 @@ impl<'a> Command<'a> {
             stream,
             kernel_id,
             dispatch_count,
+            cooperative,
+            dynamic_shared_mem,
             tensor_maps,
             resources,
             const_info,
@@ -487,6 +672,7 @@ impl<'a> Command<'a> {
         if stream.drop_queue.should_flush() {
             stream.drop_queue.flush(|| Fence::new(stream.sys));
         }
+        stream.in_flight.register(stream.sys);
 
         if let Err(err) = result {
             match self.ctx.timestamps.is_empty() {
@@ -498,6 +684,50 @@ impl<'a> Command<'a> {
     }
 }
 
+#[cfg(cuda_12050)]
+fn prefetch_managed(
+    ptr: u64,
+    size: u64,
+    device: Option<i32>,
+    stream: *mut CUstream_st,
+) -> Result<(), IoError> {
+    use cudarc::driver::sys::{CUmemLocation, CUmemLocationType};
+
+    let (type_, id) = match device {
+        Some(id) => (CUmemLocationType::CU_MEM_LOCATION_TYPE_DEVICE, id),
+        None => (CUmemLocationType::CU_MEM_LOCATION_TYPE_HOST, 0),
+    };
+    #[cfg(not(cuda_13020))]
+    let location = CUmemLocation { type_, id };
+    #[cfg(cuda_13020)]
+    let location = CUmemLocation {
+        type_,
+        __bindgen_anon_1: cudarc::driver::sys::CUmemLocation_st__bindgen_ty_1 { id },
+    };
+
+    // SAFETY: `ptr` is a managed allocation of at least `size` bytes, and `stream` is an
+    // initialized CUDA stream.
+    unsafe {
+        cudarc::driver::result::mem_prefetch_async(ptr, size as usize, location, stream).map_err(
+            |e| IoError::Unknown {
+                description: format!("CUDA prefetch failed: {e}"),
+                backtrace: BackTrace::capture(),
+            },
+        )
+    }
+}
+
+#[cfg(not(cuda_12050))]
+fn prefetch_managed(
+    _ptr: u64,
+    _size: u64,
+    _device: Option<i32>,
+    _stream: *mut CUstream_st,
+) -> Result<(), IoError> {
+    // Prefetching to a memory location requires CUDA 12.5.
+    Ok(())
+}
+
 /// Internal write to GPU command.
 ///
 /// Writes data from a CPU buffer to a CUDA resource.
@@ -1,9 +1,13 @@
 use cubecl_common::backtrace::BackTrace;
 use cubecl_cpp::formatter::format_cpp;
-use cubecl_cpp::{cuda::arch::CudaArchitecture, shared::CompilationOptions};
+use cubecl_cpp::{
+    cuda::arch::CudaArchitecture,
+    shared::{CompilationOptions, spec_constant_defines},
+};
 use cubecl_runtime::{
     compiler::CompilationError,
-    validation::{validate_cube_dim, validate_units},
+    kernel::{KernelBinary, SourceTransform},
+    validation::{validate_buffer_sizes, validate_cube_dim, validate_units},
 };
 
 use super::storage::gpu::GpuResource;
@@ -36,11 +40,19 @@ use cubecl_common::cache::CacheOption;
 pub(crate) struct CudaContext {
     pub context: *mut CUctx_st,
     pub module_names: HashMap<KernelId, CompiledKernel>,
+    /// Element sizes of the buffers of kernels compiled in checked mode, to validate bindings.
+    buffer_elem_sizes: HashMap<KernelId, Vec<usize>>,
     ptx_cache: Option<CompilationCache<StableHash, PtxCacheEntry>>,
+    /// The PTX of the precompiled kernels [loaded](Self::load_compiled_kernels) in this context.
+    ///
+    /// The PTX of the kernels compiled in this context isn't kept once loaded.
+    ptx_entries: HashMap<StableHash, PtxCacheEntry>,
     pub timestamps: TimestampProfiler,
     pub arch: CudaArchitecture,
     pub compilation_options: CompilationOptions,
     pub properties: DeviceProperties,
+    /// The rewrite of the CUDA C source of the kernels, shared with the server utilities.
+    pub source_transform: Arc<SourceTransform>,
 }
 
 #[derive(Debug)]
@@ -54,6 +66,9 @@ pub struct CompiledKernel {
 pub struct PtxCacheEntry {
     entrypoint_name: String,
     shared_mem_bytes: usize,
+    /// Missing from entries written by older versions, whose bindings then aren't validated.
+    #[serde(default)]
+    buffer_elem_sizes: Vec<usize>,
     ptx: Vec<std::ffi::c_char>,
 }
 
@@ -67,6 +82,8 @@ impl CudaContext {
         Self {
             context,
             module_names: HashMap::new(),
+            buffer_elem_sizes: HashMap::new(),
+            ptx_entries: HashMap::new(),
             ptx_cache: {
                 let config = cubecl_runtime::config::GlobalConfig::get();
                 if let Some(cache) = &config.compilation.cache {
@@ -83,6 +100,7 @@ impl CudaContext {
             timestamps: TimestampProfiler::default(),
             compilation_options,
             properties,
+            source_transform: Arc::new(SourceTransform::default()),
         }
     }
 
@@ -100,26 +118,47 @@ impl CudaContext {
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
     ) -> Result<(), LaunchError> {
-        let hash = if let Some(cache) = &self.ptx_cache {
-            let hash = kernel_id.stable_hash();
-
-            if let Some(entry) = cache.get(&hash) {
-                log::trace!("Using PTX cache");
-
-                self.load_ptx(
-                    entry.ptx.clone(),
-                    kernel_id.clone(),
-                    entry.entrypoint_name.clone(),
-                    kernel_id.cube_dim,
-                    entry.shared_mem_bytes,
-                )?;
-                return Ok(());
-            }
-            Some(hash)
-        } else {
-            None
+        let entry = self.ptx_entry(kernel_id, kernel.as_ref(), mode, logger)?;
+
+        if matches!(mode, ExecutionMode::Checked) {
+            self.buffer_elem_sizes
+                .insert(kernel_id.clone(), entry.buffer_elem_sizes);
+        }
+
+        self.load_ptx(
+            entry.ptx,
+            kernel_id.clone(),
+            entry.entrypoint_name,
+            kernel_id.cube_dim,
+            entry.shared_mem_bytes,
+        )?;
+        Ok(())
+    }
+
+    /// The PTX of the kernel, from the precompiled kernels or the PTX cache when there, compiled
+    /// with NVRTC otherwise.
+    fn ptx_entry(
+        &mut self,
+        kernel_id: &KernelId,
+        kernel: &dyn CubeTask<CudaCompiler>,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<PtxCacheEntry, LaunchError> {
+        let hash = kernel_id.stable_hash();
+
+        let entry = match self.ptx_entries.get(&hash) {
+            Some(entry) => Some(entry.clone()),
+            None => self
+                .ptx_cache
+                .as_ref()
+                .and_then(|cache| cache.get(&hash).cloned()),
         };
 
+        if let Some(entry) = entry {
+            log::trace!("Using PTX cache");
+            return Ok(entry);
+        }
+
         log::trace!("Compiling kernel");
 
         validate_cube_dim(&self.properties, kernel_id)?;
@@ -141,8 +180,8 @@ impl CudaContext {
                 kernel_compiled.source = formatted;
             }
         }
+        let transformed = self.source_transform.apply(&mut kernel_compiled);
 
-        let cube_dim = kernel_compiled.cube_dim;
         let arch = if self.arch.version >= 90 {
             format!("--gpu-architecture=sm_{}a", self.arch)
         } else {
@@ -153,10 +192,12 @@ impl CudaContext {
         let include_option = format!("--include-path={}", include_path.to_str().unwrap());
         let cccl_include_path = cccl_include_path();
         let cccl_include_option = format!("--include-path={}", cccl_include_path.to_str().unwrap());
+        let spec_defines = spec_constant_defines(kernel_id.spec_constant_values());
         let mut options = vec![arch.as_str(), include_option.as_str(), "-lineinfo"];
         if cccl_include_path.exists() {
             options.push(&cccl_include_option);
         }
+        options.extend(spec_defines.iter().map(String::as_str));
 
         logger.log_compilation(&kernel_compiled);
 
@@ -211,28 +252,66 @@ impl CudaContext {
 
         let repr = kernel_compiled.repr.unwrap();
 
-        if let Some(cache) = &mut self.ptx_cache {
-            let result = cache.insert(
-                hash.unwrap(),
-                PtxCacheEntry {
-                    entrypoint_name: kernel_compiled.entrypoint_name.clone(),
-                    shared_mem_bytes: repr.shared_memory_size(),
-                    ptx: ptx.clone(),
-                },
-            );
+        let entry = PtxCacheEntry {
+            entrypoint_name: kernel_compiled.entrypoint_name,
+            shared_mem_bytes: repr.shared_memory_size(),
+            buffer_elem_sizes: kernel_compiled.buffer_elem_sizes,
+            ptx,
+        };
+
+        if let Some(cache) = &mut self.ptx_cache
+            && !transformed
+        {
+            let result = cache.insert(hash, entry.clone());
             if let Err(err) = result {
                 log::warn!("Unable to save the ptx {err:?}");
             }
         }
 
-        self.load_ptx(
-            ptx,
-            kernel_id.clone(),
-            kernel_compiled.entrypoint_name,
-            cube_dim,
-            repr.shared_memory_size(),
-        )?;
-        Ok(())
+        Ok(entry)
+    }
+
+    /// The PTX of the `kernels`, compiled for each of the `modes` unless precompiled or cached.
+    pub fn compile_binaries(
+        &mut self,
+        kernels: Vec<Box<dyn CubeTask<CudaCompiler>>>,
+        modes: &[ExecutionMode],
+        logger: Arc<ServerLogger>,
+    ) -> Result<Vec<KernelBinary>, LaunchError> {
+        let mut binaries = Vec::with_capacity(kernels.len() * modes.len());
+
+        for kernel in kernels {
+            for mode in modes {
+                let mut kernel_id = kernel.id();
+                kernel_id.mode(*mode);
+
+                let entry = self.ptx_entry(&kernel_id, kernel.as_ref(), *mode, logger.clone())?;
+                binaries.push(KernelBinary {
+                    id: kernel_id.stable_hash(),
+                    entrypoint_name: entry.entrypoint_name,
+                    shared_mem_bytes: entry.shared_mem_bytes,
+                    buffer_elem_sizes: entry.buffer_elem_sizes,
+                    binary: entry.ptx.into_iter().map(|c| c as u8).collect(),
+                });
+            }
+        }
+
+        Ok(binaries)
+    }
+
+    /// Registers precompiled PTX, used instead of compiling the matching kernels.
+    pub fn load_compiled_kernels(&mut self, kernels: Vec<KernelBinary>) {
+        for kernel in kernels {
+            self.ptx_entries.insert(
+                kernel.id,
+                PtxCacheEntry {
+                    entrypoint_name: kernel.entrypoint_name,
+                    shared_mem_bytes: kernel.shared_mem_bytes,
+                    buffer_elem_sizes: kernel.buffer_elem_sizes,
+                    ptx: kernel.binary.into_iter().map(|b| b as c_char).collect(),
+                },
+            );
+        }
     }
 
     fn load_ptx(
@@ -273,11 +352,14 @@ impl CudaContext {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_task(
         &mut self,
         stream: &mut Stream,
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
+        cooperative: bool,
+        dynamic_shared_mem: usize,
         tensor_maps: &[CUtensorMap],
         resources: &[GpuResource],
         const_info: Option<*mut c_void>,
@@ -289,8 +371,25 @@ impl CudaContext {
         bindings.extend(resources.iter().map(|memory| memory.binding));
         bindings.extend(const_info);
 
+        if let Some(elem_sizes) = self.buffer_elem_sizes.get(&kernel_id) {
+            let buffers = &resources[tensor_maps.len()..];
+            validate_buffer_sizes(elem_sizes, buffers.iter().map(|buffer| buffer.size))?;
+        }
+
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
+        let shared_mem_bytes = kernel.shared_mem_bytes + dynamic_shared_mem;
+        let max = self.properties.hardware.max_shared_memory_size;
+
+        if shared_mem_bytes > max {
+            return Err(ResourceLimitError::SharedMemory {
+                requested: shared_mem_bytes,
+                max,
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+
         // SAFETY: `kernel.func` is a valid function handle from a loaded module.
         // `stream.sys` is a valid CUDA stream. `bindings` contains valid device pointers
         // for all kernel arguments. The dispatch and cube dimensions are validated by
@@ -299,19 +398,30 @@ impl CudaContext {
             cudarc::driver::result::function::set_function_attribute(
                 kernel.func,
                 CUfunction_attribute::CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES,
-                kernel.shared_mem_bytes as i32,
+                shared_mem_bytes as i32,
             )
             .map_err(|err| LaunchError::Unknown {
                 reason: format!("{err:?}"),
                 backtrace: BackTrace::capture(),
             })?;
+
+            if cooperative {
+                return self.launch_cooperative(
+                    kernel,
+                    dispatch_count,
+                    shared_mem_bytes,
+                    stream,
+                    &mut bindings,
+                );
+            }
+
             cudarc::driver::result::launch_kernel(
                 kernel.func,
                 dispatch_count,
                 (cube_dim.x, cube_dim.y, cube_dim.z),
                 // Shared memory is collected into a single buffer, with each shared memory being
-                // an offset pointer
-                kernel.shared_mem_bytes as u32,
+                // an offset pointer, followed by the dynamic shared memory of the launch.
+                shared_mem_bytes as u32,
                 stream.sys,
                 &mut bindings,
             )
@@ -324,6 +434,104 @@ impl CudaContext {
         Ok(())
     }
 
+    /// Launches the kernel with all cubes co-resident, failing when the grid is larger than the
+    /// occupancy of the device allows.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as a regular launch of the kernel.
+    unsafe fn launch_cooperative(
+        &self,
+        kernel: &CompiledKernel,
+        dispatch_count: (u32, u32, u32),
+        shared_mem_bytes: usize,
+        stream: &mut Stream,
+        bindings: &mut [*mut c_void],
+    ) -> Result<(), LaunchError> {
+        let cube_dim = kernel.cube_dim;
+        let mut cubes_per_sm = 0;
+
+        // SAFETY: `kernel.func` is a valid function handle from a loaded module.
+        unsafe {
+            cudarc::driver::sys::cuOccupancyMaxActiveBlocksPerMultiprocessor(
+                &mut cubes_per_sm,
+                kernel.func,
+                cube_dim.num_elems() as i32,
+                shared_mem_bytes,
+            )
+        }
+        .result()
+        .map_err(|err| LaunchError::Unknown {
+            reason: format!("{err:?}"),
+            backtrace: BackTrace::capture(),
+        })?;
+
+        let num_sms = self
+            .properties
+            .hardware
+            .num_streaming_multiprocessors
+            .unwrap_or(1);
+        let max = cubes_per_sm as u64 * num_sms as u64;
+        let requested = dispatch_count.0 as u64 * dispatch_count.1 as u64 * dispatch_count.2 as u64;
+
+        if requested > max {
+            return Err(ResourceLimitError::CoResidentCubes {
+                requested,
+                max,
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+
+        // SAFETY: Same as a regular launch, the grid has been validated to be co-resident.
+        unsafe {
+            cudarc::driver::sys::cuLaunchCooperativeKernel(
+                kernel.func,
+                dispatch_count.0,
+                dispatch_count.1,
+                dispatch_count.2,
+                cube_dim.x,
+                cube_dim.y,
+                cube_dim.z,
+                shared_mem_bytes as u32,
+                stream.sys,
+                bindings.as_mut_ptr(),
+            )
+        }
+        .result()
+        .map_err(|err| LaunchError::Unknown {
+            reason: format!("{err:?}"),
+            backtrace: BackTrace::capture(),
+        })
+    }
+
+    /// The number of units per cube that maximizes the occupancy of the compiled kernel.
+    ///
+    /// The register and shared memory usage are the ones of the kernel compiled for the cube dim
+    /// of its id, which may differ once compiled for another cube dim.
+    pub fn max_potential_cube_size(&self, kernel_id: &KernelId) -> Option<u32> {
+        let kernel = self.module_names.get(kernel_id)?;
+        let mut min_cube_count = 0;
+        let mut cube_size = 0;
+
+        // SAFETY: `kernel.func` is a valid function handle from a loaded module, and no
+        // block-size-dependent shared memory callback is given.
+        unsafe {
+            cudarc::driver::sys::cuOccupancyMaxPotentialBlockSize(
+                &mut min_cube_count,
+                &mut cube_size,
+                kernel.func,
+                None,
+                kernel.shared_mem_bytes,
+                0,
+            )
+        }
+        .result()
+        .ok()?;
+
+        Some(cube_size as u32)
+    }
+
     fn validate_shared(&self, repr: &Option<CudaComputeKernel>) -> Result<(), LaunchError> {
         let requested = repr.as_ref().map(|repr| repr.shared_memory_size());
         let max = self.properties.hardware.max_shared_memory_size;
@@ -10,7 +10,10 @@ use crate::{
     },
 };
 use cubecl_common::{
-    backtrace::BackTrace, bytes::Bytes, profile::ProfileDuration, stream_id::StreamId,
+    backtrace::BackTrace,
+    bytes::{AllocationProperty, Bytes},
+    profile::ProfileDuration,
+    stream_id::StreamId,
 };
 use cubecl_core::{
     MemoryConfiguration,
@@ -19,9 +22,10 @@ use cubecl_core::{
     ir::{ElemType, FloatKind, IntKind, MemoryDeviceProperties, StorageType, UIntKind},
     prelude::*,
     server::{
-        Binding, CommunicationId, CopyDescriptor, Handle, KernelArguments, LaunchError,
-        ProfileError, ProfilingToken, ReduceOperation, ServerCommunication, ServerError,
-        ServerUtilities, StreamErrorMode, TensorMapBinding, TensorMapMeta,
+        BindGroup, BindGroupCache, Binding, CommunicationId, CopyDescriptor, Handle, IoError,
+        KernelArguments, LaunchError, ProfileError, ProfilingToken, ReduceOperation,
+        ServerCommunication, ServerError, ServerUtilities, StreamErrorMode, TensorMapBinding,
+        TensorMapMeta,
     },
     zspace::{Shape, Strides},
 };
@@ -29,17 +33,21 @@ use cubecl_runtime::{
     allocator::PitchedMemoryLayoutPolicy,
     compiler::CubeTask,
     config::GlobalConfig,
+    kernel::KernelBinary,
     logging::ServerLogger,
-    memory_management::{ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage},
+    memory_management::{
+        AccessHint, ChunkInfo, ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage,
+    },
+    read_path::ReadPath,
     server::ComputeServer,
     storage::{ComputeStorage, ManagedResource},
     stream::MultiStream,
 };
 use cudarc::{
     driver::sys::{
-        CUcontext, CUresult, CUstream_st, CUtensorMapDataType, CUtensorMapFloatOOBfill,
-        CUtensorMapInterleave, CUtensorMapL2promotion, CUtensorMapSwizzle, cuCtxEnablePeerAccess,
-        cuTensorMapEncodeIm2col, cuTensorMapEncodeTiled,
+        CUcontext, CUdeviceptr, CUresult, CUstream_st, CUtensorMapDataType,
+        CUtensorMapFloatOOBfill, CUtensorMapInterleave, CUtensorMapL2promotion, CUtensorMapSwizzle,
+        cuCtxEnablePeerAccess, cuTensorMapEncodeIm2col, cuTensorMapEncodeTiled,
     },
     nccl::sys::ncclComm,
 };
@@ -56,6 +64,16 @@ pub struct CudaServer {
     utilities: Arc<ServerUtilities<Self>>,
     comm_stream: *mut CUstream_st,
     communicators: HashMap<CommunicationId, *mut cudarc::nccl::sys::ncclComm>,
+    bind_groups: BindGroupCache<CudaBindGroup>,
+}
+
+/// The kernel arguments of a bind group, resolved when the group is created.
+#[derive(Debug)]
+struct CudaBindGroup {
+    /// The device pointers of the buffers, passed by address to the kernel.
+    pointers: Box<[CUdeviceptr]>,
+    /// The sizes of the buffers, to validate them against the kernel.
+    sizes: Vec<u64>,
 }
 
 // SAFETY: `CudaServer` is only accessed from one thread at a time via the `DeviceHandle`,
@@ -97,6 +115,8 @@ impl ComputeServer for CudaServer {
         descriptors: Vec<CopyDescriptor>,
         stream_id: StreamId,
     ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        let utilities = self.utilities.clone();
+
         match self.command(
             stream_id,
             descriptors.iter().map(|d| &d.handle),
@@ -105,12 +125,53 @@ impl ComputeServer for CudaServer {
                 flush: true,
             },
         ) {
-            Ok(mut command) => Box::pin(command.read_async(descriptors)),
+            Ok(mut command) => {
+                let read = command.read_async(descriptors);
+
+                Box::pin(async move {
+                    let data = read.await?;
+                    // Reads fall back to pageable memory when no pinned memory is available.
+                    let pinned = data
+                        .iter()
+                        .all(|bytes| matches!(bytes.property(), AllocationProperty::Pinned));
+                    utilities.read_path.record(match pinned {
+                        true => ReadPath::Staged,
+                        false => ReadPath::Chunked,
+                    });
+
+                    Ok(data)
+                })
+            }
             Err(err) => Box::pin(async move { Err(err) }),
         }
     }
 
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, stream_id: StreamId) {
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        self.reserve_and_bind(memory, size, None, stream_id)
+    }
+
+    fn initialize_memory_with_hint(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        hint: AccessHint,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        self.reserve_and_bind(memory, size, Some(hint), stream_id)
+    }
+
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
         let mut command = match self.command_no_inputs(
             stream_id,
             StreamErrorMode {
@@ -122,8 +183,9 @@ impl ComputeServer for CudaServer {
             Err(err) => unreachable!("{err:?}"),
         };
 
-        let reserved = command.reserve(size).unwrap();
+        let reserved = command.reserve_aligned(size, alignment)?;
         command.bind(reserved, memory);
+        Ok(())
     }
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor, Bytes)>, stream_id: StreamId) {
@@ -147,6 +209,42 @@ impl ComputeServer for CudaServer {
         }
     }
 
+    fn fill(&mut self, binding: Binding, pattern: Vec<u8>, stream_id: StreamId) {
+        let mut command = match self.command(
+            stream_id,
+            [&binding].into_iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(val) => val,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        if let Err(err) = command.fill(binding, &pattern) {
+            command.error(err.into());
+        }
+    }
+
+    fn copy_buffer(&mut self, src: Binding, dst: Binding, stream_id: StreamId) {
+        let mut command = match self.command(
+            stream_id,
+            [&src, &dst].into_iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(val) => val,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        if let Err(err) = command.copy(src, dst) {
+            command.error(err.into());
+        }
+    }
+
     unsafe fn launch(
         &mut self,
         kernel: Self::Kernel,
@@ -155,7 +253,88 @@ impl ComputeServer for CudaServer {
         mode: ExecutionMode,
         stream_id: StreamId,
     ) {
-        if let Err(err) = self.launch_checked(kernel, count, bindings, mode, stream_id) {
+        if let Err(err) = self.launch_checked(kernel, count, None, bindings, mode, false, stream_id)
+        {
+            let mut stream = match self.streams.resolve(stream_id, [].into_iter(), false) {
+                Ok(stream) => stream,
+                Err(err) => unreachable!("{err:?}"),
+            };
+            stream.current().errors.push(err);
+        }
+    }
+
+    fn create_bind_group(&mut self, group: BindGroup, stream_id: StreamId) {
+        let bindings = group.bindings().collect::<Vec<_>>();
+        let mut command = match self.command(
+            stream_id,
+            bindings.iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(command) => command,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        let mut pointers = Vec::with_capacity(bindings.len());
+        let mut sizes = Vec::with_capacity(bindings.len());
+
+        for binding in bindings {
+            match command.resource(binding) {
+                Ok(resource) => {
+                    pointers.push(resource.ptr);
+                    sizes.push(resource.size);
+                }
+                Err(err) => {
+                    // The group is resolved again on every launch instead.
+                    command.error(err.into());
+                    return;
+                }
+            }
+        }
+
+        core::mem::drop(command);
+        self.bind_groups.insert(
+            &group,
+            CudaBindGroup {
+                pointers: pointers.into_boxed_slice(),
+                sizes,
+            },
+        );
+    }
+
+    unsafe fn launch_with_group(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        group: BindGroup,
+        bindings: KernelArguments,
+        mode: ExecutionMode,
+        stream_id: StreamId,
+    ) {
+        let result = match self.bind_groups.get(group.id()).is_some() {
+            true => self.launch_checked(
+                kernel,
+                count,
+                Some(&group),
+                bindings,
+                mode,
+                false,
+                stream_id,
+            ),
+            false => self.launch_checked(
+                kernel,
+                count,
+                None,
+                bindings.with_bind_group(&group),
+                mode,
+                false,
+                stream_id,
+            ),
+        };
+
+        if let Err(err) = result {
             let mut stream = match self.streams.resolve(stream_id, [].into_iter(), false) {
                 Ok(stream) => stream,
                 Err(err) => unreachable!("{err:?}"),
@@ -164,6 +343,22 @@ impl ComputeServer for CudaServer {
         }
     }
 
+    unsafe fn launch_cooperative(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+        mode: ExecutionMode,
+        stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        self.launch_checked(kernel, count, None, bindings, mode, true, stream_id)
+    }
+
+    fn wait_streams(&mut self, dependencies: Vec<StreamId>, stream_id: StreamId) {
+        self.unsafe_set_current();
+        self.streams.wait_streams(stream_id, &dependencies);
+    }
+
     fn flush(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
         let mut command = self.command_no_inputs(
             stream_id,
@@ -195,6 +390,37 @@ impl ComputeServer for CudaServer {
         }
     }
 
+    fn fence(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        // The errors stay in the stream for the next synchronization to report.
+        let command = self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        );
+
+        match command {
+            Ok(mut command) => command.sync(),
+            Err(err) => Box::pin(async { Err(err) }),
+        }
+    }
+
+    fn has_pending_work(&mut self, stream_id: StreamId) -> bool {
+        let command = self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        );
+
+        match command {
+            Ok(mut command) => command.has_pending_work(),
+            Err(_) => true,
+        }
+    }
+
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
         cubecl_common::future::block_on(self.sync(stream_id))?;
         Ok(self.ctx.timestamps.start())
@@ -243,6 +469,95 @@ impl ComputeServer for CudaServer {
         Ok(command.memory_usage())
     }
 
+    fn chunk_map(&mut self, stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError> {
+        let mut command = self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: false,
+                flush: false,
+            },
+        )?;
+        Ok(command.chunk_map())
+    }
+
+    fn prefetch(&mut self, binding: Binding, to_device: bool, stream_id: StreamId) {
+        let device = to_device.then_some(self.device_id);
+        let mut command = match self.command(
+            stream_id,
+            [&binding].into_iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(command) => command,
+            Err(_) => return,
+        };
+
+        // It's only a hint, so failures are ignored.
+        let _ = command.prefetch(binding, device);
+    }
+
+    fn set_max_in_flight(&mut self, max_tasks: usize, stream_id: StreamId) {
+        let mut command = match self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(command) => command,
+            Err(_) => return,
+        };
+
+        command.streams.current().in_flight.set_max(max_tasks);
+    }
+
+    fn suggest_cube_size(&mut self, kernel: Self::Kernel, _stream_id: StreamId) -> Option<u32> {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(ExecutionMode::Checked);
+
+        self.ctx.unsafe_set_current().ok()?;
+        if !self.ctx.module_names.contains_key(&kernel_id) {
+            let logger = self.streams.logger.clone();
+            self.ctx
+                .compile_kernel(&kernel_id, kernel, ExecutionMode::Checked, logger)
+                .ok()?;
+        }
+
+        self.ctx.max_potential_cube_size(&kernel_id)
+    }
+
+    fn is_compiled(&mut self, kernel_id: &KernelId) -> bool {
+        self.ctx.module_names.contains_key(kernel_id)
+    }
+
+    fn compile_binaries(
+        &mut self,
+        kernels: Vec<Self::Kernel>,
+        modes: Vec<ExecutionMode>,
+    ) -> Result<Vec<KernelBinary>, LaunchError> {
+        self.ctx
+            .unsafe_set_current()
+            .map_err(|err| LaunchError::Unknown {
+                reason: format!("Unable to set the CUDA context: {err:?}"),
+                backtrace: BackTrace::capture(),
+            })?;
+        let logger = self.streams.logger.clone();
+
+        self.ctx.compile_binaries(kernels, &modes, logger)
+    }
+
+    fn load_compiled_kernels(&mut self, kernels: Vec<KernelBinary>) {
+        self.ctx.load_compiled_kernels(kernels);
+    }
+
+    fn device_memory(&mut self) -> Option<(u64, u64)> {
+        self.ctx.unsafe_set_current().ok()?;
+        let (free, total) = cudarc::driver::result::mem_get_info().ok()?;
+        Some((free as u64, total as u64))
+    }
+
     fn memory_cleanup(&mut self, stream_id: StreamId) {
         let mut command = match self.command_no_inputs(
             stream_id,
@@ -257,6 +572,25 @@ impl ComputeServer for CudaServer {
         command.memory_cleanup()
     }
 
+    fn release(&mut self, bindings: Vec<Binding>, stream_id: StreamId) {
+        let mut command = match self.command(
+            stream_id,
+            bindings.iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(command) => command,
+            Err(_) => return,
+        };
+
+        for binding in bindings {
+            // The handles of the memory are unusable either way.
+            let _ = command.release(binding);
+        }
+    }
+
     fn allocation_mode(&mut self, mode: MemoryAllocationMode, stream_id: StreamId) {
         let mut command = match self.command_no_inputs(
             stream_id,
@@ -403,9 +737,44 @@ impl ServerCommunication for CudaServer {
 }
 
 impl CudaServer {
+    /// Binds the memory of the `handle` to `size` bytes of device memory allocated outside of
+    /// `CubeCL`, such as the output of cuBLAS or cuDNN, without copying it.
+    ///
+    /// Reach the server as `<CudaRuntime as Runtime>::Server` with
+    /// [`ComputeClient::downcast_server`](cubecl_runtime::client::ComputeClient::downcast_server).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to at least `size` bytes of device memory of the context of the server,
+    /// aligned to the memory alignment of the device. The memory isn't freed by the server, and
+    /// must stay valid for as long as the server lives, since its chunk can be reused by other
+    /// allocations once the handle is dropped.
+    pub unsafe fn register_device_ptr(
+        &mut self,
+        handle: Handle,
+        ptr: u64,
+        size: usize,
+        stream_id: StreamId,
+    ) {
+        let mut command = match self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(val) => val,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        // SAFETY: Upheld by the caller.
+        let reserved = unsafe { command.register_device_ptr(ptr, size) };
+        command.bind(reserved, handle.memory);
+    }
+
     /// Create a new cuda server.
     pub(crate) fn new(
-        ctx: CudaContext,
+        mut ctx: CudaContext,
         mem_props: MemoryDeviceProperties,
         mem_config: MemoryConfiguration,
         mem_alignment: usize,
@@ -416,6 +785,7 @@ impl CudaServer {
         let max_streams = config.streaming.max_streams;
 
         ctx.unsafe_set_current().unwrap();
+        ctx.source_transform = utilities.source_transform.clone();
 
         let peer_activated = enable_one_way_peer_access(ctx.context).is_ok();
         if peer_activated {
@@ -440,12 +810,14 @@ impl CudaServer {
                     mem_config,
                     mem_alignment,
                     utilities.logger.clone(),
+                    utilities.memory_usage.clone(),
                 ),
                 max_streams,
             ),
             utilities: Arc::new(utilities),
             comm_stream,
             communicators: HashMap::default(),
+            bind_groups: BindGroupCache::default(),
         }
     }
 
@@ -691,6 +1063,39 @@ impl CudaServer {
         Ok(Command::new(&mut self.ctx, streams))
     }
 
+    fn reserve_and_bind(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        hint: Option<AccessHint>,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let utilities = self.utilities.clone();
+        let mut command = match self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(val) => val,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        let reserved = match command.reserve_with_hint(size, hint) {
+            Ok(reserved) => reserved,
+            // Only spill when the device memory is full, not for buffers larger than it allows.
+            Err(IoError::OutOfMemory { .. }) if utilities.spill.is_enabled() => {
+                let reserved = command.reserve_host(size)?;
+                utilities.spill.record(&memory);
+                reserved
+            }
+            Err(err) => return Err(err),
+        };
+        command.bind(reserved, memory);
+        Ok(())
+    }
+
     fn flush_errors(&mut self, stream_id: StreamId) -> Vec<ServerError> {
         let mut stream = match self.streams.resolve(stream_id, [].into_iter(), false) {
             Ok(stream) => stream,
@@ -711,15 +1116,22 @@ impl CudaServer {
         errors
     }
 
+    /// Launches the `kernel`, with the cached buffers of the `group` bound before the buffers of
+    /// the `bindings`.
+    #[allow(clippy::too_many_arguments)]
     fn launch_checked(
         &mut self,
         kernel: Box<dyn CubeTask<CudaCompiler>>,
         count: CubeCount,
+        group: Option<&BindGroup>,
         bindings: KernelArguments,
         mode: ExecutionMode,
+        cooperative: bool,
         stream_id: StreamId,
     ) -> Result<(), ServerError> {
-        let mut kernel_id = kernel.id();
+        // Each set of specialization constants is compiled to its own module.
+        let mut kernel_id = kernel.id().spec_constants(&bindings.spec_constants);
+        let dynamic_shared_mem = bindings.dynamic_shared_mem;
         let logger = self.streams.logger.clone();
         kernel_id.mode(mode);
         let grid_constants = self
@@ -727,9 +1139,25 @@ impl CudaServer {
             .compilation_options
             .supports_features
             .grid_constants;
+        let group_resources = group
+            .and_then(|group| self.bind_groups.get(group.id()))
+            .map(|cached| {
+                cached
+                    .pointers
+                    .iter()
+                    .zip(cached.sizes.iter())
+                    .map(|(ptr, size)| {
+                        GpuResource::new(*ptr, ptr as *const CUdeviceptr as *mut c_void, *size)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let group_bindings = group
+            .map(|group| group.bindings().collect::<Vec<_>>())
+            .unwrap_or_default();
         let mut command = self.command(
             stream_id,
-            bindings.buffers.iter(),
+            group_bindings.iter().chain(bindings.buffers.iter()),
             StreamErrorMode {
                 ignore: true,
                 flush: false,
@@ -757,6 +1185,11 @@ impl CudaServer {
             }
         };
 
+        // An empty dispatch, e.g. a predicated launch with a false condition, is a no-op.
+        if count.0 == 0 || count.1 == 0 || count.2 == 0 {
+            return Ok(());
+        }
+
         let (info_const, info_binding) = if grid_constants {
             let info = &bindings.info;
 
@@ -778,10 +1211,21 @@ impl CudaServer {
         let mut resources = bindings
             .tensor_maps
             .iter()
-            .map(|it| it.binding.clone())
-            .chain(bindings.buffers)
-            .map(|binding| command.resource(binding).expect("Resource to exist."))
+            .map(|it| {
+                command
+                    .resource(it.binding.clone())
+                    .expect("Resource to exist.")
+            })
             .collect::<Vec<_>>();
+        // The cached pointers outlive the launch, since groups are only discarded when a new one
+        // is created.
+        resources.extend(group_resources);
+        resources.extend(
+            bindings
+                .buffers
+                .into_iter()
+                .map(|binding| command.resource(binding).expect("Resource to exist.")),
+        );
 
         let mut tensor_maps = Vec::with_capacity(bindings.tensor_maps.len());
 
@@ -954,6 +1398,8 @@ impl CudaServer {
             kernel,
             mode,
             count,
+            cooperative,
+            dynamic_shared_mem,
             &tensor_maps,
             &resources,
             info_const,
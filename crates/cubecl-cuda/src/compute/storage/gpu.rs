@@ -9,6 +9,10 @@ use std::collections::HashMap;
 enum AllocationKind {
     Async,
     Sync,
+    /// Memory owned by another library, which is never freed by the storage.
+    External,
+    /// Pinned host memory mapped into the address space of the device.
+    Host(*mut std::ffi::c_void),
 }
 
 /// Buffer storage for NVIDIA GPUs.
@@ -34,6 +38,28 @@ pub struct GpuResource {
     pub size: u64,
 }
 
+/// The raw CUDA device memory of a [`GpuResource`].
+#[cfg(feature = "interop")]
+#[derive(Debug, Clone, Copy)]
+pub struct CudaRawResource {
+    /// The device pointer, with the offset of the resource already applied.
+    pub ptr: cudarc::driver::sys::CUdeviceptr,
+    /// The size of the resource in bytes.
+    pub size: u64,
+}
+
+#[cfg(feature = "interop")]
+impl cubecl_runtime::storage::InteropResource for GpuResource {
+    type Raw = CudaRawResource;
+
+    fn as_raw(&self) -> Self::Raw {
+        CudaRawResource {
+            ptr: self.ptr,
+            size: self.size,
+        }
+    }
+}
+
 impl GpuResource {
     /// Creates a new [`GpuResource`].
     pub fn new(ptr: u64, binding: *mut std::ffi::c_void, size: u64) -> Self {
@@ -57,6 +83,70 @@ impl GpuStorage {
         }
     }
 
+    /// Wraps `size` bytes of device memory allocated outside of the storage, such as the output
+    /// of cuBLAS or cuDNN, without copying it.
+    ///
+    /// The memory is registered as external, so it is never freed by the storage.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to at least `size` bytes of device memory of the context of the storage,
+    /// aligned to its [alignment](ComputeStorage::alignment). The memory must stay valid until
+    /// the storage deallocates the returned handle.
+    // Builds a handle from the pointer, the storage only keeps track of it.
+    #[allow(clippy::wrong_self_convention)]
+    pub unsafe fn from_device_ptr(&mut self, ptr: u64, size: usize) -> StorageHandle {
+        let id = StorageId::new();
+        self.memory.insert(id, (ptr, AllocationKind::External));
+
+        StorageHandle::new(
+            id,
+            StorageUtilization {
+                offset: 0,
+                size: size as u64,
+            },
+        )
+    }
+
+    /// Allocates `size` bytes of pinned host memory mapped into the address space of the
+    /// device, for allocations spilled out of the device memory.
+    ///
+    /// Kernels access the memory in place over the bus, so it is much slower than device memory.
+    pub fn alloc_host(&mut self, size: u64) -> Result<StorageHandle, IoError> {
+        let flags = cudarc::driver::sys::CU_MEMHOSTALLOC_DEVICEMAP
+            | cudarc::driver::sys::CU_MEMHOSTALLOC_PORTABLE;
+        // SAFETY: Calling CUDA driver FFI to allocate mapped host memory, which is only accessed
+        // through its device pointer and freed with `free_host` on deallocation.
+        let host_ptr = unsafe { cudarc::driver::result::malloc_host(size as usize, flags) }
+            .map_err(|err| IoError::Unknown {
+                description: format!("CUDA host allocation error: {err}"),
+                backtrace: BackTrace::capture(),
+            })?;
+
+        let mut ptr = 0;
+        // SAFETY: `host_ptr` was just allocated with the `DEVICEMAP` flag, and `ptr` is a valid
+        // `CUdeviceptr` for its device pointer to be written to.
+        let mapped = unsafe {
+            cudarc::driver::sys::cuMemHostGetDevicePointer_v2(&mut ptr, host_ptr, 0).result()
+        };
+        if let Err(err) = mapped {
+            // SAFETY: `host_ptr` was returned by `malloc_host` and isn't used anywhere else.
+            unsafe { cudarc::driver::result::free_host(host_ptr).ok() };
+            return Err(IoError::Unknown {
+                description: format!("CUDA host memory mapping error: {err}"),
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        let id = StorageId::new();
+        self.memory
+            .insert(id, (ptr, AllocationKind::Host(host_ptr)));
+        Ok(StorageHandle::new(
+            id,
+            StorageUtilization { offset: 0, size },
+        ))
+    }
+
     /// Deallocates buffers marked for deallocation.
     ///
     /// This method processes all pending deallocations by freeing the associated GPU memory.
@@ -64,8 +154,9 @@ impl GpuStorage {
         self.deallocations
             .drain(..)
             .filter_map(|id| self.memory.remove(&id))
-            // SAFETY: Each `ptr` was obtained from a prior `malloc_async` or `malloc_sync`
-            // call and has not been freed yet. The deallocation method matches the allocation kind.
+            // SAFETY: Each `ptr` was obtained from a prior `malloc_async`, `malloc_sync` or
+            // `malloc_host` call and has not been freed yet. The deallocation method matches the
+            // allocation kind.
             .for_each(|(ptr, kind)| unsafe {
                 match kind {
                     AllocationKind::Async => {
@@ -76,6 +167,12 @@ impl GpuStorage {
                             eprintln!("CUDA free error: {}", e);
                         }
                     }
+                    AllocationKind::External => {}
+                    AllocationKind::Host(host_ptr) => {
+                        if let Err(e) = cudarc::driver::result::free_host(host_ptr) {
+                            eprintln!("CUDA free error: {}", e);
+                        }
+                    }
                 }
             });
     }
@@ -13,11 +13,12 @@ use cubecl_core::{
 use cubecl_runtime::{
     logging::ServerLogger,
     memory_management::{
-        MemoryAllocationMode, MemoryManagement, MemoryManagementOptions, drop_queue,
+        MemoryAllocationMode, MemoryManagement, MemoryManagementOptions, MemoryUsageSnapshot,
+        drop_queue,
     },
     stream::EventStreamBackend,
 };
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 #[derive(Debug)]
 pub struct Stream {
@@ -26,6 +27,51 @@ pub struct Stream {
     pub memory_management_cpu: MemoryManagement<PinnedMemoryStorage>,
     pub errors: Vec<ServerError>,
     pub drop_queue: drop_queue::PendingDropQueue<Fence>,
+    pub in_flight: InFlightLimit,
+}
+
+/// Bounds the number of kernels in flight on a stream.
+///
+/// A fence is recorded every half of the limit, and the stream waits on the oldest fences
+/// whenever more kernels than the limit may be running.
+#[derive(Debug, Default)]
+pub struct InFlightLimit {
+    max_tasks: Option<usize>,
+    fences: VecDeque<(Fence, usize)>,
+    tasks_fenced: usize,
+    tasks_pending: usize,
+}
+
+impl InFlightLimit {
+    /// Set the maximum number of kernels in flight.
+    pub fn set_max(&mut self, max_tasks: usize) {
+        self.max_tasks = Some(max_tasks.max(1));
+    }
+
+    /// Register a kernel launched on the `stream`, waiting on the oldest kernels when too many
+    /// are in flight.
+    pub fn register(&mut self, stream: cudarc::driver::sys::CUstream) {
+        let Some(max_tasks) = self.max_tasks else {
+            return;
+        };
+
+        self.tasks_pending += 1;
+
+        if self.tasks_pending >= (max_tasks / 2).max(1) {
+            self.fences
+                .push_back((Fence::new(stream), self.tasks_pending));
+            self.tasks_fenced += self.tasks_pending;
+            self.tasks_pending = 0;
+        }
+
+        while self.tasks_fenced + self.tasks_pending > max_tasks
+            && let Some((fence, tasks)) = self.fences.pop_front()
+        {
+            // Errors are reported by the next synchronization of the stream.
+            let _ = fence.wait_sync();
+            self.tasks_fenced -= tasks;
+        }
+    }
 }
 
 impl drop_queue::Fence for Fence {
@@ -40,6 +86,7 @@ pub struct CudaStreamBackend {
     mem_config: MemoryConfiguration,
     mem_alignment: usize,
     logger: Arc<ServerLogger>,
+    memory_usage: Arc<MemoryUsageSnapshot>,
 }
 
 impl EventStreamBackend for CudaStreamBackend {
@@ -58,7 +105,8 @@ impl EventStreamBackend for CudaStreamBackend {
             &self.mem_props,
             self.mem_config.clone(),
             self.logger.clone(),
-            MemoryManagementOptions::new("Main GPU Memory"),
+            MemoryManagementOptions::new("Main GPU Memory")
+                .usage_snapshot(self.memory_usage.clone()),
         );
         // We use the same page size and memory pools configuration for CPU pinned memory, since we
         // expect the CPU to have at least the same amount of RAM as GPU memory.
@@ -79,6 +127,7 @@ impl EventStreamBackend for CudaStreamBackend {
             memory_management_cpu,
             errors: Vec::new(),
             drop_queue: Default::default(),
+            in_flight: Default::default(),
         }
     }
 
@@ -16,8 +16,8 @@ pub struct Fence {
 //
 // # Safety
 //
-// Since streams are never closed and we destroy the event after waiting, which consumes the
-// [Fence], it is safe.
+// Since streams are never closed and the event is only destroyed when the [Fence] is dropped,
+// it is safe.
 unsafe impl Send for Fence {}
 
 #[allow(unused)]
@@ -42,9 +42,8 @@ impl Fence {
     /// Wait for the [Fence] to be reached, ensuring that all previous tasks enqueued to the
     /// [stream](CUstream_st) are completed.
     pub fn wait_sync(self) -> Result<(), ServerError> {
-        // SAFETY: `self.event` is a valid event created in `Fence::new`. We synchronize
-        // (block) until the event completes, then destroy it. `self` is consumed so the
-        // event cannot be double-freed.
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, and is only destroyed
+        // when `self` is dropped after the wait.
         unsafe {
             cudarc::driver::result::event::synchronize(self.event).map_err(|err| {
                 ServerError::Generic {
@@ -52,12 +51,6 @@ impl Fence {
                     backtrace: BackTrace::capture(),
                 }
             })?;
-            cudarc::driver::result::event::destroy(self.event).map_err(|err| {
-                ServerError::Generic {
-                    reason: format!("{err:?}"),
-                    backtrace: BackTrace::capture(),
-                }
-            })?;
         }
 
         Ok(())
@@ -72,8 +65,8 @@ impl Fence {
     /// The [stream](CUevent_st) must be initialized.
     pub fn wait_async(self, stream: *mut CUstream_st) {
         // SAFETY: `self.event` is a valid event created in `Fence::new`. `stream` must be
-        // a valid CUDA stream. The event is destroyed after the wait, and `self` is consumed
-        // so the event cannot be used again.
+        // a valid CUDA stream. The wait is enqueued before the event is destroyed when `self`
+        // is dropped, which CUDA allows.
         unsafe {
             cudarc::driver::result::stream::wait_event(
                 stream,
@@ -81,7 +74,16 @@ impl Fence {
                 CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
             )
             .unwrap();
-            cudarc::driver::result::event::destroy(self.event).unwrap();
+        }
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, destroyed only here. A
+        // pending event is released once it completes.
+        unsafe {
+            cudarc::driver::result::event::destroy(self.event).ok();
         }
     }
 }
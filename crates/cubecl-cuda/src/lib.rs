@@ -9,6 +9,9 @@ mod runtime;
 pub use device::*;
 pub use runtime::*;
 
+#[cfg(feature = "interop")]
+pub use compute::storage::gpu::CudaRawResource;
+
 #[cfg(feature = "ptx-wmma")]
 pub(crate) type WmmaCompiler = cubecl_cpp::cuda::mma::PtxWmmaCompiler;
 
@@ -151,6 +151,7 @@ impl DeviceService for CudaServer {
                 plane_size_max: warp_size,
                 max_bindings: crate::device::CUDA_MAX_BINDINGS,
                 max_shared_memory_size: max_shared,
+                max_uniform_buffer_size: 0,
                 max_cube_count,
                 max_units_per_cube: max_threads,
                 max_cube_dim,
@@ -173,6 +174,14 @@ impl DeviceService for CudaServer {
             TimingMethod::System,
         );
         register_supported_types(&mut device_props);
+        // SAFETY: `device_ptr` is a valid CUDA device, the attribute is a read-only property.
+        device_props.features.cooperative_launch = unsafe {
+            cudarc::driver::result::device::get_attribute(
+                device_ptr,
+                cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_COOPERATIVE_LAUNCH,
+            )
+            .is_ok_and(|supported| supported != 0)
+        };
         device_props.register_type_usage(ElemType::Float(FloatKind::TF32), TypeUsage::Conversion);
         if arch_version >= 60 {
             device_props.register_atomic_type_usage(
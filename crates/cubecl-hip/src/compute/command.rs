@@ -7,7 +7,7 @@ use crate::{
 };
 use cubecl_common::{backtrace::BackTrace, bytes::Bytes, stream_id::StreamId};
 use cubecl_core::{
-    MemoryUsage,
+    ChunkInfo, MemoryUsage,
     bytes::AllocationProperty,
     future::DynFut,
     server::{
@@ -65,6 +65,19 @@ impl<'a> Command<'a> {
         self.streams.current().memory_management_gpu.memory_usage()
     }
 
+    /// Retrieves the [layout](ChunkInfo) of the gpu memory chunks of the current stream.
+    pub fn chunk_map(&mut self) -> Vec<ChunkInfo> {
+        self.streams.current().memory_management_gpu.chunk_map()
+    }
+
+    /// Releases the memory of the binding, even if handles to it are still alive.
+    pub fn release(&mut self, binding: Binding) -> Result<(), IoError> {
+        self.streams
+            .get(&binding.stream)
+            .memory_management_gpu
+            .release(binding.memory)
+    }
+
     /// Explicitly cleanup gpu memory on the current stream.
     pub fn memory_cleanup(&mut self) {
         self.streams.current().memory_management_gpu.cleanup(true)
@@ -96,6 +109,20 @@ impl<'a> Command<'a> {
         Ok(handle)
     }
 
+    /// Allocates a new GPU memory buffer of the specified size, starting at an offset that is a
+    /// multiple of `alignment` bytes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_aligned(
+        &mut self,
+        size: u64,
+        alignment: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.streams
+            .current()
+            .memory_management_gpu
+            .reserve_aligned(size, alignment)
+    }
+
     /// Get the stream cursor.
     pub fn cursor(&self) -> u64 {
         self.streams.cursor
@@ -10,12 +10,13 @@ use cubecl_core::{
     {ir::DeviceProperties, prelude::*},
 };
 use cubecl_cpp::formatter::format_cpp;
-use cubecl_cpp::shared::CompilationOptions;
+use cubecl_cpp::shared::{CompilationOptions, spec_constant_defines};
 use cubecl_hip_sys::{HIP_SUCCESS, get_hip_include_path, hiprtcResult_HIPRTC_SUCCESS};
 use cubecl_runtime::timestamp_profiler::TimestampProfiler;
 use cubecl_runtime::{
     compiler::CompilationError,
-    validation::{validate_cube_dim, validate_units},
+    kernel::SourceTransform,
+    validation::{validate_buffer_sizes, validate_cube_dim, validate_units},
 };
 use cubecl_runtime::{compiler::CubeTask, logging::ServerLogger};
 use serde::Deserialize;
@@ -28,10 +29,14 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub(crate) struct HipContext {
     pub module_names: HashMap<KernelId, HipCompiledKernel>,
+    /// Element sizes of the buffers of kernels compiled in checked mode, to validate bindings.
+    buffer_elem_sizes: HashMap<KernelId, Vec<usize>>,
     pub timestamps: TimestampProfiler,
     pub compilation_options: CompilationOptions,
     pub properties: DeviceProperties,
     pub compilation_cache: Option<CompilationCache<StableHash, CompilationCacheEntry>>,
+    /// The rewrite of the HIP C source of the kernels, shared with the server utilities.
+    pub source_transform: Arc<SourceTransform>,
 }
 
 #[derive(Debug)]
@@ -46,6 +51,9 @@ pub struct HipCompiledKernel {
 pub struct CompilationCacheEntry {
     entrypoint_name: String,
     shared_mem_bytes: usize,
+    /// Missing from entries written by older versions, whose bindings then aren't validated.
+    #[serde(default)]
+    buffer_elem_sizes: Vec<usize>,
     binary: Vec<i8>,
 }
 
@@ -53,6 +61,7 @@ impl HipContext {
     pub fn new(compilation_options: CompilationOptions, properties: DeviceProperties) -> Self {
         Self {
             module_names: HashMap::new(),
+            buffer_elem_sizes: HashMap::new(),
             timestamps: TimestampProfiler::default(),
             compilation_options,
             compilation_cache: {
@@ -68,6 +77,7 @@ impl HipContext {
                 }
             },
             properties,
+            source_transform: Arc::new(SourceTransform::default()),
         }
     }
 
@@ -83,6 +93,10 @@ impl HipContext {
             let hash = kernel_id.stable_hash();
             if let Some(entry) = cache.get(&hash) {
                 log::trace!("Using compilation cache");
+                if matches!(mode, ExecutionMode::Checked) {
+                    self.buffer_elem_sizes
+                        .insert(kernel_id.clone(), entry.buffer_elem_sizes.clone());
+                }
                 self.load_compiled_binary(
                     entry.binary.clone(),
                     kernel_id.clone(),
@@ -111,6 +125,11 @@ impl HipContext {
 
         self.validate_shared(&jitc_kernel.repr)?;
 
+        if matches!(mode, ExecutionMode::Checked) {
+            self.buffer_elem_sizes
+                .insert(kernel_id.clone(), jitc_kernel.buffer_elem_sizes.clone());
+        }
+
         if logger.compilation_activated() {
             jitc_kernel.debug_info = Some(DebugInformation::new("cpp", kernel_id.clone()));
 
@@ -118,6 +137,7 @@ impl HipContext {
                 jitc_kernel.source = formatted;
             }
         }
+        let transformed = self.source_transform.apply(&mut jitc_kernel);
         logger.log_compilation(&jitc_kernel);
 
         // Create HIP Program
@@ -155,11 +175,16 @@ impl HipContext {
         // needed for rocWMMA extension to compile
         let cpp_std_option_cstr = CString::new("--std=c++17").unwrap();
         let optimization_level = CString::new("-O3").unwrap();
+        let spec_defines_cstr = spec_constant_defines(kernel_id.spec_constant_values())
+            .into_iter()
+            .map(|define| CString::new(define).unwrap())
+            .collect::<Vec<_>>();
         let mut options = vec![
             cpp_std_option_cstr.as_ptr(),
             include_option_cstr.as_ptr(),
             optimization_level.as_ptr(),
         ];
+        options.extend(spec_defines_cstr.iter().map(|define| define.as_ptr()));
         // SAFETY: `program` is a valid RTC program handle created above. The `options` vector
         // contains valid null-terminated `CString` pointers that outlive this call. On failure,
         // we retrieve and report the compilation log before returning an error.
@@ -243,13 +268,16 @@ impl HipContext {
 
         let repr = jitc_kernel.repr.unwrap();
 
-        if let Some(cache) = self.compilation_cache.as_mut() {
+        if let Some(cache) = self.compilation_cache.as_mut()
+            && !transformed
+        {
             cache
                 .insert(
                     hash.unwrap(),
                     CompilationCacheEntry {
                         entrypoint_name: jitc_kernel.entrypoint_name.clone(),
                         shared_mem_bytes: repr.shared_memory_size(),
+                        buffer_elem_sizes: jitc_kernel.buffer_elem_sizes.clone(),
                         binary: code.clone(),
                     },
                 )
@@ -334,6 +362,10 @@ impl HipContext {
             .map(|memory| memory.binding)
             .collect::<Vec<_>>();
 
+        if let Some(elem_sizes) = self.buffer_elem_sizes.get(&kernel_id) {
+            validate_buffer_sizes(elem_sizes, resources.iter().map(|buffer| buffer.size))?;
+        }
+
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
 
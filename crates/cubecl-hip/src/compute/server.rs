@@ -3,7 +3,12 @@ use crate::{
     compute::{command::Command, context::HipContext, fence::Fence, stream::HipStreamBackend},
     runtime::HipCompiler,
 };
-use cubecl_common::{bytes::Bytes, future::DynFut, profile::ProfileDuration, stream_id::StreamId};
+use cubecl_common::{
+    bytes::{AllocationProperty, Bytes},
+    future::DynFut,
+    profile::ProfileDuration,
+    stream_id::StreamId,
+};
 use cubecl_core::{
     MemoryConfiguration,
     backtrace::BackTrace,
@@ -11,16 +16,18 @@ use cubecl_core::{
     ir::MemoryDeviceProperties,
     prelude::*,
     server::{
-        Binding, CopyDescriptor, KernelArguments, ProfileError, ProfilingToken,
+        Binding, CopyDescriptor, IoError, KernelArguments, ProfileError, ProfilingToken,
         ServerCommunication, ServerError, ServerUtilities, StreamErrorMode,
     },
 };
+use cubecl_hip_sys::HIP_SUCCESS;
 use cubecl_runtime::{
     allocator::PitchedMemoryLayoutPolicy,
     compiler::CubeTask,
     config::GlobalConfig,
     logging::ServerLogger,
-    memory_management::{ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage},
+    memory_management::{ChunkInfo, ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage},
+    read_path::ReadPath,
     server::ComputeServer,
     storage::{ComputeStorage, ManagedResource},
     stream::MultiStream,
@@ -69,7 +76,12 @@ impl ComputeServer for HipServer {
             .collect())
     }
 
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, stream_id: StreamId) {
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
         let mut command = match self.command_no_inputs(
             stream_id,
             StreamErrorMode {
@@ -81,8 +93,32 @@ impl ComputeServer for HipServer {
             Err(err) => unreachable!("{err:?}"),
         };
 
-        let reserved = command.reserve(size).unwrap();
+        let reserved = command.reserve(size)?;
         command.bind(reserved, memory);
+        Ok(())
+    }
+
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let mut command = match self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(val) => val,
+            Err(err) => unreachable!("{err:?}"),
+        };
+
+        let reserved = command.reserve_aligned(size, alignment)?;
+        command.bind(reserved, memory);
+        Ok(())
     }
 
     fn read(
@@ -90,6 +126,8 @@ impl ComputeServer for HipServer {
         descriptors: Vec<CopyDescriptor>,
         stream_id: StreamId,
     ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        let utilities = self.utilities.clone();
+
         match self.command(
             stream_id,
             descriptors.iter().map(|d| &d.handle),
@@ -98,7 +136,23 @@ impl ComputeServer for HipServer {
                 flush: true,
             },
         ) {
-            Ok(mut command) => Box::pin(command.read_async(descriptors)),
+            Ok(mut command) => {
+                let read = command.read_async(descriptors);
+
+                Box::pin(async move {
+                    let data = read.await?;
+                    // Reads fall back to pageable memory when no pinned memory is available.
+                    let pinned = data
+                        .iter()
+                        .all(|bytes| matches!(bytes.property(), AllocationProperty::Pinned));
+                    utilities.read_path.record(match pinned {
+                        true => ReadPath::Staged,
+                        false => ReadPath::Chunked,
+                    });
+
+                    Ok(data)
+                })
+            }
             Err(err) => Box::pin(async move { Err(err) }),
         }
     }
@@ -141,6 +195,10 @@ impl ComputeServer for HipServer {
         }
     }
 
+    fn wait_streams(&mut self, dependencies: Vec<StreamId>, stream_id: StreamId) {
+        self.streams.wait_streams(stream_id, &dependencies);
+    }
+
     fn flush(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
         let mut command = self.command_no_inputs(
             stream_id,
@@ -172,6 +230,22 @@ impl ComputeServer for HipServer {
         }
     }
 
+    fn fence(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        // The errors stay in the stream for the next synchronization to report.
+        let command = self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        );
+
+        match command {
+            Ok(mut command) => command.sync(),
+            Err(err) => Box::pin(async { Err(err) }),
+        }
+    }
+
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
         cubecl_common::future::block_on(self.sync(stream_id))?;
         Ok(self.ctx.timestamps.start())
@@ -220,6 +294,33 @@ impl ComputeServer for HipServer {
         Ok(command.memory_usage())
     }
 
+    fn chunk_map(&mut self, stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError> {
+        let mut command = self.command_no_inputs(
+            stream_id,
+            StreamErrorMode {
+                ignore: false,
+                flush: false,
+            },
+        )?;
+        Ok(command.chunk_map())
+    }
+
+    fn device_memory(&mut self) -> Option<(u64, u64)> {
+        let mut free: usize = 0;
+        let mut total: usize = 0;
+        // SAFETY: Calling HIP FFI to query device memory info. `free` and `total` are valid
+        // stack variables that HIP writes through on success.
+        let status = unsafe { cubecl_hip_sys::hipMemGetInfo(&mut free, &mut total) };
+        if status != HIP_SUCCESS {
+            return None;
+        }
+        Some((free as u64, total as u64))
+    }
+
+    fn is_compiled(&mut self, kernel_id: &KernelId) -> bool {
+        self.ctx.module_names.contains_key(kernel_id)
+    }
+
     fn memory_cleanup(&mut self, stream_id: StreamId) {
         let mut command = match self.command_no_inputs(
             stream_id,
@@ -235,6 +336,26 @@ impl ComputeServer for HipServer {
         command.memory_cleanup()
     }
 
+    fn release(&mut self, bindings: Vec<Binding>, stream_id: StreamId) {
+        let mut command = match self.command(
+            stream_id,
+            bindings.iter(),
+            StreamErrorMode {
+                ignore: true,
+                flush: false,
+            },
+        ) {
+            Ok(command) => command,
+            // Server is in error.
+            Err(_) => return,
+        };
+
+        for binding in bindings {
+            // The handles of the memory are unusable either way.
+            let _ = command.release(binding);
+        }
+    }
+
     fn allocation_mode(&mut self, mode: MemoryAllocationMode, stream_id: StreamId) {
         let mut command = match self.command_no_inputs(
             stream_id,
@@ -257,7 +378,7 @@ impl ServerCommunication for HipServer {
 impl HipServer {
     /// Create a new hip server.
     pub(crate) fn new(
-        ctx: HipContext,
+        mut ctx: HipContext,
         mem_props: MemoryDeviceProperties,
         mem_config: MemoryConfiguration,
         mem_alignment: usize,
@@ -266,6 +387,7 @@ impl HipServer {
     ) -> Self {
         let config = GlobalConfig::get();
         let max_streams = config.streaming.max_streams;
+        ctx.source_transform = utilities.source_transform.clone();
 
         Self {
             ctx,
@@ -277,6 +399,7 @@ impl HipServer {
                     mem_alignment,
                     is_integrated,
                     utilities.logger.clone(),
+                    utilities.memory_usage.clone(),
                 ),
                 max_streams,
             ),
@@ -341,7 +464,8 @@ impl HipServer {
         mode: ExecutionMode,
         stream_id: StreamId,
     ) -> Result<(), ServerError> {
-        let mut kernel_id = kernel.id();
+        // Each set of specialization constants is compiled to its own module.
+        let mut kernel_id = kernel.id().spec_constants(&bindings.spec_constants);
         let logger = self.streams.logger.clone();
         kernel_id.mode(mode);
         let mut command = self.command(
@@ -375,10 +499,16 @@ impl HipServer {
             }
         };
 
+        // An empty dispatch, e.g. a predicated launch with a false condition, is a no-op.
+        if count.0 == 0 || count.1 == 0 || count.2 == 0 {
+            return Ok(());
+        }
+
         let KernelArguments {
             buffers,
             info,
             tensor_maps,
+            ..
         } = bindings;
 
         debug_assert!(tensor_maps.is_empty(), "Can't use tensor maps on HIP");
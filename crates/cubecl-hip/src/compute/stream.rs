@@ -7,7 +7,7 @@ use cubecl_hip_sys::HIP_SUCCESS;
 use cubecl_runtime::{
     logging::ServerLogger,
     memory_management::{
-        MemoryAllocationMode, MemoryManagement, MemoryManagementOptions,
+        MemoryAllocationMode, MemoryManagement, MemoryManagementOptions, MemoryUsageSnapshot,
         drop_queue::{self, FlushingPolicy, PendingDropQueue},
     },
     stream::EventStreamBackend,
@@ -42,6 +42,7 @@ pub struct HipStreamBackend {
     mem_alignment: usize,
     is_integrated: bool,
     logger: Arc<ServerLogger>,
+    memory_usage: Arc<MemoryUsageSnapshot>,
 }
 
 impl EventStreamBackend for HipStreamBackend {
@@ -68,7 +69,8 @@ impl EventStreamBackend for HipStreamBackend {
             &self.mem_props,
             self.mem_config.clone(),
             self.logger.clone(),
-            MemoryManagementOptions::new("Main GPU Memory"),
+            MemoryManagementOptions::new("Main GPU Memory")
+                .usage_snapshot(self.memory_usage.clone()),
         );
         // We use the same page size and memory pools configuration for CPU pinned memory, since we
         // expect the CPU to have at least the same amount of RAM as GPU memory.
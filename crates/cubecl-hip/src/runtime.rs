@@ -141,6 +141,7 @@ impl DeviceService for HipServer {
             plane_size_max: prop_warp_size as u32,
             max_bindings: crate::device::AMD_MAX_BINDINGS,
             max_shared_memory_size: prop_max_shared_memory_size,
+            max_uniform_buffer_size: 0,
             max_cube_count,
             max_units_per_cube: prop_max_threads,
             max_cube_dim,
@@ -12,6 +12,8 @@ pub struct Features {
     pub plane: EnumSet<Plane>,
     /// Clustered launches and intra-cluster operations like cluster shared memory
     pub cube_cluster: bool,
+    /// Cooperative launches, where all cubes are co-resident and can synchronize grid-wide
+    pub cooperative_launch: bool,
     /// Enables changing the type of containers during kernel execution.
     pub memory_reinterpret: bool,
     /// Enables explicit alignment. If false, alignment still compiles, but isn't actually applied.
@@ -33,6 +33,9 @@ pub struct HardwareProperties {
     pub max_bindings: u32,
     /// Maximum amount of shared memory, in bytes
     pub max_shared_memory_size: usize,
+    /// Maximum size of a single uniform buffer binding, in bytes. Zero if the device doesn't
+    /// support binding uniform buffers.
+    pub max_uniform_buffer_size: u64,
     /// Maximum `CubeCount` in x, y and z dimensions
     pub max_cube_count: (u32, u32, u32),
     /// Maximum number of total units in a cube
@@ -47,6 +47,15 @@ pub enum VariableKind {
     GlobalInputArray(Id),
     GlobalOutputArray(Id),
     GlobalScalar(Id),
+    /// A scalar whose value is only known when the kernel is launched, letting the backend
+    /// specialize an already compiled kernel for it.
+    SpecConstant(Id),
+    /// A read-only array bound as a uniform buffer, with a length fixed when the kernel is
+    /// compiled.
+    UniformArray {
+        id: Id,
+        length: usize,
+    },
     TensorMapInput(Id),
     TensorMapOutput(Id),
     LocalArray {
@@ -146,6 +155,8 @@ impl Variable {
             VariableKind::LocalArray { .. } => false,
             VariableKind::GlobalInputArray { .. } => false,
             VariableKind::GlobalScalar { .. } => true,
+            VariableKind::SpecConstant(_) => true,
+            VariableKind::UniformArray { .. } => true,
             VariableKind::Versioned { .. } => true,
             VariableKind::LocalConst { .. } => true,
             VariableKind::Constant(_) => true,
@@ -164,6 +175,7 @@ impl Variable {
             VariableKind::GlobalInputArray { .. }
                 | VariableKind::GlobalOutputArray { .. }
                 | VariableKind::ConstantArray { .. }
+                | VariableKind::UniformArray { .. }
                 | VariableKind::SharedArray { .. }
                 | VariableKind::LocalArray { .. }
                 | VariableKind::Matrix { .. }
@@ -500,6 +512,7 @@ impl Variable {
             | VariableKind::Versioned { id, .. }
             | VariableKind::LocalConst { id, .. }
             | VariableKind::ConstantArray { id, .. }
+            | VariableKind::UniformArray { id, .. }
             | VariableKind::SharedArray { id, .. }
             | VariableKind::Shared { id, .. }
             | VariableKind::LocalArray { id, .. }
@@ -522,6 +535,7 @@ impl Display for Variable {
             VariableKind::GlobalInputArray(id) => write!(f, "input({id})"),
             VariableKind::GlobalOutputArray(id) => write!(f, "output({id})"),
             VariableKind::GlobalScalar(id) => write!(f, "scalar<{}>({id})", self.ty),
+            VariableKind::SpecConstant(id) => write!(f, "spec<{}>({id})", self.ty),
             VariableKind::TensorMapInput(id) => write!(f, "tensor_map({id})"),
             VariableKind::TensorMapOutput(id) => write!(f, "tensor_map({id})"),
             VariableKind::Constant(constant) => write!(f, "{}({constant})", self.ty),
@@ -531,6 +545,7 @@ impl Display for Variable {
             }
             VariableKind::LocalConst { id } => write!(f, "binding({id})"),
             VariableKind::ConstantArray { id, .. } => write!(f, "const_array({id})"),
+            VariableKind::UniformArray { id, .. } => write!(f, "uniform({id})"),
             VariableKind::SharedArray { id, .. } => write!(f, "shared_array({id})"),
             VariableKind::Shared { id } => write!(f, "shared({id})"),
             VariableKind::LocalArray { id, .. } => write!(f, "array({id})"),
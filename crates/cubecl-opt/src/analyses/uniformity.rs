@@ -207,11 +207,13 @@ impl Uniformity {
     pub fn is_var_uniform(&self, var: Variable) -> bool {
         match var.kind {
             VariableKind::ConstantArray { .. }
+            | VariableKind::UniformArray { .. }
             | VariableKind::SharedArray { .. }
             | VariableKind::Shared { .. }
             | VariableKind::GlobalInputArray(_)
             | VariableKind::GlobalOutputArray(_)
             | VariableKind::GlobalScalar(_)
+            | VariableKind::SpecConstant(_)
             | VariableKind::Constant(_) => true,
             VariableKind::Builtin(builtin) => match builtin {
                 Builtin::UnitPosPlane
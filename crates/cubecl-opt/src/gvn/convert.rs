@@ -83,6 +83,8 @@ pub fn value_of_var(var: &Variable) -> Option<Value> {
             unroll_factor,
         } => Value::ConstArray(id, item, length, unroll_factor),
         VariableKind::LocalMut { .. }
+        | VariableKind::SpecConstant(_)
+        | VariableKind::UniformArray { .. }
         | VariableKind::SharedArray { .. }
         | VariableKind::Shared { .. }
         | VariableKind::LocalArray { .. }
@@ -217,6 +217,7 @@ impl ValueTable {
                     | VariableKind::GlobalOutputArray { .. }
                     | VariableKind::GlobalScalar { .. } => self.lookup_or_add_var(var)?,
                     VariableKind::ConstantArray { length, .. }
+                    | VariableKind::UniformArray { length, .. }
                     | VariableKind::SharedArray { length, .. }
                     | VariableKind::LocalArray { length, .. } => {
                         let constant = length.into();
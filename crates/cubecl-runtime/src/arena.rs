@@ -0,0 +1,82 @@
+use crate::server::{Handle, IoError};
+use cubecl_common::backtrace::BackTrace;
+
+/// A stack of allocations bump allocated from a single backing buffer, created with
+/// [`empty_arena`](crate::client::ComputeClient::empty_arena).
+///
+/// The state of the arena can be [marked](Self::mark), and every allocation made after the mark
+/// [rolled back](Self::rollback) at once, which suits speculative work that explores and
+/// backtracks.
+#[derive(Debug)]
+pub struct Arena {
+    buffer: Handle,
+    alignment: u64,
+    offset: spin::Mutex<u64>,
+}
+
+/// The state of an [arena](Arena) to [roll back](Arena::rollback) to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaMark {
+    offset: u64,
+}
+
+impl Arena {
+    pub(crate) fn new(buffer: Handle, alignment: u64) -> Self {
+        Self {
+            buffer,
+            alignment: alignment.max(1),
+            offset: spin::Mutex::new(0),
+        }
+    }
+
+    /// Allocate `size` bytes at the top of the arena, starting at an offset aligned for binding.
+    ///
+    /// Fails with [`IoError::BufferTooBig`] when the arena has fewer bytes left.
+    pub fn alloc(&self, size: usize) -> Result<Handle, IoError> {
+        let mut offset = self.offset.lock();
+        let start = offset.next_multiple_of(self.alignment);
+        let end = start + size as u64;
+
+        if end > self.capacity() as u64 {
+            return Err(IoError::BufferTooBig {
+                size: size as u64,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        *offset = end;
+
+        Ok(self
+            .buffer
+            .clone()
+            .offset_start(start)
+            .offset_end(self.capacity() as u64 - end))
+    }
+
+    /// The current state of the arena.
+    pub fn mark(&self) -> ArenaMark {
+        ArenaMark {
+            offset: *self.offset.lock(),
+        }
+    }
+
+    /// Free every allocation made since the `mark`, so that their memory is reused by the next
+    /// allocations.
+    ///
+    /// The handles of the freed allocations must not be used anymore. Rolling back to a mark taken
+    /// after the current state does nothing.
+    pub fn rollback(&self, mark: ArenaMark) {
+        let mut offset = self.offset.lock();
+        *offset = (*offset).min(mark.offset);
+    }
+
+    /// The number of bytes allocated, including the padding between allocations.
+    pub fn used(&self) -> usize {
+        *self.offset.lock() as usize
+    }
+
+    /// The size of the backing buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.buffer.size_in_used() as usize
+    }
+}
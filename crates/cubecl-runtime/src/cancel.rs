@@ -0,0 +1,117 @@
+use crate::server::ServerError;
+use alloc::sync::Arc;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use cubecl_common::{backtrace::BackTrace, future::DynFut};
+
+/// Cancels a read started with
+/// [`read_cancellable`](crate::client::ComputeClient::read_cancellable).
+///
+/// Once cancelled, the read future stops waiting for the device and resolves to
+/// [`ServerError::Cancelled`], releasing the staging memory of the read. Copies already
+/// submitted to the device can't be recalled, so cancelling is meant to stop waiting on obsolete
+/// reads, e.g. a preview the user navigated away from.
+#[derive(Clone, Debug, Default)]
+pub struct ReadHandle {
+    state: Arc<spin::Mutex<CancelState>>,
+}
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: bool,
+    waker: Option<Waker>,
+}
+
+impl ReadHandle {
+    /// Cancel the read, waking the future if it is waiting.
+    pub fn cancel(&self) {
+        let mut state = self.state.lock();
+        state.cancelled = true;
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Whether the read was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.lock().cancelled
+    }
+}
+
+/// A future that resolves to [`ServerError::Cancelled`] once its [handle](ReadHandle) is
+/// cancelled, dropping the inner future.
+pub(crate) struct Cancellable<T> {
+    inner: Option<DynFut<Result<T, ServerError>>>,
+    handle: ReadHandle,
+}
+
+impl<T> Cancellable<T> {
+    /// Make the `inner` future cancellable.
+    pub(crate) fn new(inner: DynFut<Result<T, ServerError>>) -> (ReadHandle, Self) {
+        let handle = ReadHandle::default();
+        let future = Self {
+            inner: Some(inner),
+            handle: handle.clone(),
+        };
+
+        (handle, future)
+    }
+}
+
+impl<T> Future for Cancellable<T> {
+    type Output = Result<T, ServerError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        {
+            let mut state = self.handle.state.lock();
+
+            if state.cancelled {
+                core::mem::drop(state);
+                self.inner = None;
+                return Poll::Ready(Err(ServerError::Cancelled {
+                    backtrace: BackTrace::capture(),
+                }));
+            }
+
+            // Registered before polling the inner future, so a cancellation racing with the poll
+            // still wakes this task.
+            state.waker = Some(cx.waker().clone());
+        }
+
+        match self.inner.as_mut() {
+            Some(inner) => inner.as_mut().poll(cx),
+            None => panic!("Cancellable future polled after completion"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    #[test]
+    fn cancelled_future_resolves_to_an_error() {
+        let (handle, future) =
+            Cancellable::new(Box::pin(core::future::pending::<Result<u32, ServerError>>()));
+
+        handle.cancel();
+
+        assert!(handle.is_cancelled());
+        assert!(matches!(
+            cubecl_common::future::block_on(future),
+            Err(ServerError::Cancelled { .. })
+        ));
+    }
+
+    #[test]
+    fn uncancelled_future_resolves_to_the_inner_value() {
+        let (_handle, future) = Cancellable::new(Box::pin(async { Ok(4u32) }));
+
+        assert_eq!(cubecl_common::future::block_on(future).unwrap(), 4);
+    }
+}
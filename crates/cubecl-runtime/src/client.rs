@@ -1,18 +1,35 @@
 use crate::{
+    arena::Arena,
+    cancel::{Cancellable, ReadHandle},
+    compiler::CompilationError,
     config::{TypeNameFormatLevel, type_name_format},
-    kernel::KernelMetadata,
+    generation::GenerationId,
+    id::KernelId,
+    in_flight::InFlightGuard,
+    kernel::{CompiledBlob, KernelMetadata, Visibility},
     logging::ProfileLevel,
-    memory_management::{MemoryAllocationMode, MemoryUsage},
+    memory_management::{
+        AccessHint, ChunkInfo, ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage,
+    },
+    profiler::Profiler,
+    read_path::ReadPath,
+    read_stream::ReadStream,
     runtime::Runtime,
     server::{
-        CommunicationId, ComputeServer, CopyDescriptor, CubeCount, ExecutionMode, Handle, IoError,
-        KernelArguments, MemoryLayout, MemoryLayoutDescriptor, MemoryLayoutPolicy,
-        MemoryLayoutStrategy, ProfileError, ReduceOperation, ServerCommunication, ServerError,
-        ServerUtilities,
+        BindGroup, Binding, CommunicationId, ComputeServer, CopyDescriptor, CubeCount,
+        ExecutionMode, Handle, IoError, KernelArguments, LaunchError, MemoryLayout,
+        MemoryLayoutDescriptor, MemoryLayoutPolicy, MemoryLayoutStrategy, ProfileError,
+        ReduceOperation, ResourceLimitError, ServerCommunication, ServerError, ServerUtilities,
+        SubmissionId,
     },
+    shape_bucket::ShapeBucket,
+    spill::SpillMode,
+    staged::StagedWriter,
     storage::{ComputeStorage, ManagedResource},
+    validation::UninitReadMode,
+    verify::VerifyMode,
 };
-use alloc::{format, sync::Arc, vec, vec::Vec};
+use alloc::{boxed::Box, format, string::String, sync::Arc, vec, vec::Vec};
 use cubecl_common::{
     backtrace::BackTrace,
     bytes::{AllocationProperty, Bytes},
@@ -21,13 +38,68 @@ use cubecl_common::{
     future::DynFut,
     profile::ProfileDuration,
 };
-use cubecl_ir::{DeviceProperties, ElemType, VectorSize, features::Features};
+use cubecl_ir::{ConstantValue, DeviceProperties, ElemType, VectorSize, features::Features};
 use cubecl_zspace::Shape;
 
 #[allow(unused)]
 use cubecl_common::profile::TimingMethod;
 use cubecl_common::stream_id::StreamId;
 
+#[cfg(multi_threading)]
+use crate::server::{SyncReport, SyncTimeout};
+
+/// Header of the buffer export format, see [`ComputeClient::export`].
+#[cfg(feature = "std")]
+const EXPORT_MAGIC: &[u8; 8] = b"CUBECL\x00\x01";
+
+/// The hottest [access hint](AccessHint) of the descriptors sharing a single allocation.
+fn access_hint(descriptors: &[MemoryLayoutDescriptor]) -> Option<AccessHint> {
+    descriptors.iter().filter_map(|desc| desc.access_hint).max()
+}
+
+fn initialize_memory<S: ComputeServer>(
+    server: &mut S,
+    memory: ManagedMemoryHandle,
+    size: u64,
+    hint: Option<AccessHint>,
+    stream_id: StreamId,
+) -> Result<(), IoError> {
+    match hint {
+        Some(hint) => server.initialize_memory_with_hint(memory, size, hint, stream_id),
+        None => server.initialize_memory(memory, size, stream_id),
+    }
+}
+
+/// Launch the `kernel` on the `server`, with the buffers of the `group` bound first if any.
+unsafe fn launch_on<S: ComputeServer>(
+    server: &mut S,
+    kernel: S::Kernel,
+    count: CubeCount,
+    bindings: KernelArguments,
+    group: Option<BindGroup>,
+    mode: ExecutionMode,
+    stream_id: StreamId,
+) {
+    unsafe {
+        match group {
+            Some(group) => {
+                server.launch_with_group(kernel, count, group, bindings, mode, stream_id)
+            }
+            None => server.launch(kernel, count, bindings, mode, stream_id),
+        }
+    }
+}
+
+fn release_transient<S: ComputeServer>(
+    server: &mut S,
+    bindings: Vec<Binding>,
+    stream_id: StreamId,
+) {
+    if !bindings.is_empty() {
+        server.release(bindings, stream_id);
+    }
+}
+
 /// The `ComputeClient` is the entry point to require tasks from the `ComputeServer`.
 /// It should be obtained for a specific device via the Compute struct.
 pub struct ComputeClient<R: Runtime> {
@@ -65,6 +137,24 @@ impl<R: Runtime> ComputeClient<R> {
         }
     }
 
+    /// Create a new client with a new server that is only ever used from one thread at a time.
+    ///
+    /// The server executes the tasks of the client on the calling thread instead of on the
+    /// runner thread of the device, removing a cross-thread round trip from every call, e.g.
+    /// on a single-threaded dispatch loop. The clients [loaded](Self::load) afterward for the
+    /// device share the same mode. Collective operations aren't supported by exclusive servers.
+    pub fn init_exclusive<D: Device>(device: &D, server: R::Server) -> Self {
+        let utilities = server.utilities();
+        let context = DeviceHandle::<R::Server>::insert_exclusive(device.to_id(), server)
+            .expect("Can't create a new client on an already registered server");
+
+        Self {
+            device: context,
+            utilities,
+            stream_id: None,
+        }
+    }
+
     /// Load the client for the given device.
     pub fn load<D: Device>(device: &D) -> Self {
         let context = DeviceHandle::<R::Server>::new(device.to_id());
@@ -98,11 +188,113 @@ impl<R: Runtime> ComputeClient<R> {
         self.stream_id = Some(stream_id);
     }
 
+    /// Create a client sharing the same server, but operating on the given stream.
+    ///
+    /// Every task submitted with the returned client, such as launches, allocations and
+    /// synchronizations, goes to that stream instead of the stream of the current thread.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`set_stream`](Self::set_stream).
+    pub unsafe fn clone_onto(&self, stream_id: StreamId) -> Self {
+        Self {
+            stream_id: Some(stream_id),
+            ..self.clone()
+        }
+    }
+
     fn do_read(&self, descriptors: Vec<CopyDescriptor>) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        let verifier = &self.utilities.verifier;
+        let check = verifier.is_enabled().then(|| verifier.take(&descriptors));
+        let memories = cfg!(feature = "uninit-checks").then(|| {
+            descriptors
+                .iter()
+                .map(|desc| desc.handle.memory.descriptor().id)
+                .collect::<Vec<_>>()
+        });
+        let uninit_mode = self.utilities.uninit_reads.mode();
+        let fut = self.do_read_unverified(descriptors);
+
+        if check.is_none() && memories.is_none() {
+            return fut;
+        }
+
+        Box::pin(async move {
+            let bytes = fut.await?;
+            if let Some(check) = check {
+                check.check(&bytes)?;
+            }
+            if let Some(memories) = memories {
+                crate::validation::check_uninitialized_reads(&bytes, &memories, uninit_mode)?;
+            }
+            Ok(bytes)
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace",
+        skip(self, descriptors),
+        fields(
+            num_buffers = descriptors.len(),
+            bytes = descriptors
+                .iter()
+                .map(|it| it.shape.iter().product::<usize>() * it.elem_size)
+                .sum::<usize>(),
+        )
+    ))]
+    fn do_read_unverified(
+        &self,
+        descriptors: Vec<CopyDescriptor>,
+    ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        self.flush_staged();
         let stream_id = self.stream_id();
-        self.device
+        let bytes = descriptors
+            .iter()
+            .map(|it| it.shape.iter().product::<usize>() * it.elem_size)
+            .sum();
+        let guard = self.utilities.in_flight.track(bytes);
+        let read = self
+            .device
             .submit_blocking(move |server| server.read(descriptors, stream_id))
-            .unwrap()
+            .unwrap();
+
+        let tracked = async move {
+            let _guard = guard;
+            read.await
+        };
+        Box::pin(tracked)
+    }
+
+    /// Submit a `task` reserving memory on the server.
+    ///
+    /// When `checked`, the client waits on the task to return its error, otherwise the task is
+    /// queued and panics on the server if the memory can't be reserved.
+    fn submit_reservation(
+        &self,
+        checked: bool,
+        task: impl FnOnce(&mut R::Server) -> Result<(), IoError> + Send + 'static,
+    ) -> Result<(), IoError> {
+        if checked {
+            return self
+                .device
+                .submit_blocking(task)
+                .map_err(|err| IoError::Unknown {
+                    description: format!("Communication channel with the server is down: {err:?}"),
+                    backtrace: BackTrace::capture(),
+                })?;
+        }
+
+        self.device.submit(move |server| {
+            if let Err(err) = task(server) {
+                panic!("Failed to reserve the memory: {err}");
+            }
+        });
+        Ok(())
+    }
+
+    /// Count the data of the `descriptors` as in flight until the returned guard is dropped.
+    fn track_write(&self, descriptors: &[(CopyDescriptor, Bytes)]) -> InFlightGuard {
+        let bytes = descriptors.iter().map(|(_, data)| data.len()).sum();
+        self.utilities.in_flight.track(bytes)
     }
 
     /// Given bindings, returns owned resources as bytes.
@@ -127,9 +319,26 @@ impl<R: Runtime> ComputeClient<R> {
     ///
     /// # Remarks
     ///
-    /// Panics if the read operation fails.
+    /// Panics if the read operation fails, see [`try_read`](Self::try_read) to handle the error.
     pub fn read(&self, handles: Vec<Handle>) -> Vec<Bytes> {
-        cubecl_common::reader::read_sync(self.read_async(handles)).expect("TODO")
+        match self.try_read(handles) {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to read the handles: {err}"),
+        }
+    }
+
+    /// Given bindings, returns owned resources as bytes, or the error that made the read fail.
+    pub fn try_read(&self, handles: Vec<Handle>) -> Result<Vec<Bytes>, ServerError> {
+        cubecl_common::reader::read_sync(self.read_async(handles))
+    }
+
+    /// The [path](ReadPath) taken by the last completed read on this device, or `None` if no read
+    /// completed yet.
+    ///
+    /// Reads through [staging](ReadPath::Staged) or [chunked](ReadPath::Chunked) copies are
+    /// slower than [mapped](ReadPath::Mapped) reads, so this helps find why some reads are slow.
+    pub fn last_read_path(&self) -> Option<ReadPath> {
+        self.utilities.read_path.load()
     }
 
     /// Given a binding, returns owned resource as bytes.
@@ -137,6 +346,37 @@ impl<R: Runtime> ComputeClient<R> {
         Ok(cubecl_common::reader::read_sync(self.read_async(vec![handle]))?.remove(0))
     }
 
+    /// Given a handle, returns the owned resource as bytes, with a [handle](ReadHandle) to
+    /// cancel the read.
+    ///
+    /// Cancelling, or dropping the future, stops waiting for the device and releases the
+    /// staging memory of the read. The copy itself can't be recalled once submitted.
+    pub fn read_cancellable(
+        &self,
+        handle: Handle,
+    ) -> (
+        ReadHandle,
+        impl Future<Output = Result<Bytes, ServerError>> + Send + 'static,
+    ) {
+        let shape: Shape = [handle.size_in_used() as usize].into();
+        let descriptor = CopyDescriptor::new(handle.binding(), shape, [1].into(), 1);
+        let read = self.do_read(vec![descriptor]);
+        let (handle, read) = Cancellable::new(Box::pin(async move { Ok(read.await?.remove(0)) }));
+
+        (handle, read)
+    }
+
+    /// Given a handle, returns the owned resource as bytes, including the padding added to the
+    /// end of the allocation to respect the memory alignment.
+    ///
+    /// Unlike [`Self::read_one`], which only returns the logical bytes of the handle, the
+    /// content of the trailing padding is unspecified.
+    pub fn read_physical(&self, handle: Handle) -> Result<Bytes, ServerError> {
+        let handle = handle.physical(self.utilities.properties.memory.alignment);
+
+        self.read_one(handle)
+    }
+
     /// Given a binding, returns owned resource as bytes.
     ///
     /// # Remarks
@@ -209,13 +449,27 @@ impl<R: Runtime> ComputeClient<R> {
             .unwrap()
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace",
+        skip(self, descriptors, slices),
+        fields(
+            num_buffers = descriptors.len(),
+            bytes = slices.iter().map(|it| it.len()).sum::<usize>(),
+        )
+    ))]
     fn do_create_from_slices(
         &self,
         descriptors: Vec<MemoryLayoutDescriptor>,
         slices: Vec<Vec<u8>>,
+        checked: bool,
     ) -> Result<Vec<MemoryLayout>, IoError> {
         let stream_id = self.stream_id();
-        let (handle_base, layouts) = self.utilities.layout_policy.apply(stream_id, &descriptors);
+        let (handle_base, mut layouts) =
+            self.utilities.layout_policy.apply(stream_id, &descriptors);
+        self.tag_generation(
+            &handle_base,
+            layouts.iter_mut().map(|layout| &mut layout.memory),
+        );
+        let hint = access_hint(&descriptors);
 
         let descriptors = descriptors
             .into_iter()
@@ -235,23 +489,40 @@ impl<R: Runtime> ComputeClient<R> {
             .collect::<Vec<_>>();
 
         let (size, memory) = (handle_base.size(), handle_base.memory);
-        self.device.submit(move |server| {
-            server.initialize_memory(memory, size, stream_id);
+        let guard = self.track_write(&descriptors);
+        self.submit_reservation(checked, move |server| {
+            initialize_memory(server, memory, size, hint, stream_id)?;
             server.write(descriptors, stream_id);
-        });
+            core::mem::drop(guard);
+            Ok(())
+        })?;
 
         Ok(layouts)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace",
+        skip(self, descriptors, data),
+        fields(
+            num_buffers = descriptors.len(),
+            bytes = data.iter().map(|it| it.len()).sum::<usize>(),
+        )
+    ))]
     fn do_create(
         &self,
         descriptors: Vec<MemoryLayoutDescriptor>,
         mut data: Vec<Bytes>,
+        checked: bool,
     ) -> Result<Vec<MemoryLayout>, IoError> {
         self.staging(data.iter_mut(), true);
 
         let stream_id = self.stream_id();
-        let (handle_base, layouts) = self.utilities.layout_policy.apply(stream_id, &descriptors);
+        let (handle_base, mut layouts) =
+            self.utilities.layout_policy.apply(stream_id, &descriptors);
+        self.tag_generation(
+            &handle_base,
+            layouts.iter_mut().map(|layout| &mut layout.memory),
+        );
+        let hint = access_hint(&descriptors);
 
         let descriptors = descriptors
             .into_iter()
@@ -271,10 +542,13 @@ impl<R: Runtime> ComputeClient<R> {
             .collect::<Vec<_>>();
 
         let (size, memory) = (handle_base.size(), handle_base.memory);
-        self.device.submit(move |server| {
-            server.initialize_memory(memory, size, stream_id);
+        let guard = self.track_write(&descriptors);
+        self.submit_reservation(checked, move |server| {
+            initialize_memory(server, memory, size, hint, stream_id)?;
             server.write(descriptors, stream_id);
-        });
+            core::mem::drop(guard);
+            Ok(())
+        })?;
 
         Ok(layouts)
     }
@@ -294,12 +568,54 @@ impl<R: Runtime> ComputeClient<R> {
                 1,
             )],
             vec![slice.to_vec()],
+            false,
         )
         .unwrap()
         .remove(0)
         .memory
     }
 
+    /// Returns a zero-initialized resource handle of `size` bytes, along with a
+    /// [writer](StagedWriter) into a host copy of it.
+    ///
+    /// The buffer is never mapped: writes are staged in the host copy, and the range written
+    /// since the last flush is uploaded to the buffer before the next launch or read on the
+    /// device, through the regular [write](ComputeServer::write) path of the backend. This is
+    /// meant for buffers updated every frame, which avoids allocating a new buffer and uploading
+    /// it whole with [`Self::create`], but not the cost of the upload itself.
+    pub fn create_staged(&self, size: usize) -> (Handle, StagedWriter) {
+        let handle = self.create_from_slice(&vec![0; size]);
+        let writer = self.utilities.staged.register(handle.clone());
+
+        (handle, writer)
+    }
+
+    /// Upload the ranges of the [staged buffers](StagedWriter) written since the last flush.
+    fn flush_staged(&self) {
+        let dirty = self.utilities.staged.take_dirty();
+        if dirty.is_empty() {
+            return;
+        }
+
+        let stream_id = self.stream_id();
+        let descriptors = dirty
+            .into_iter()
+            .map(|range| {
+                let shape = [range.data.len()].into();
+                (
+                    CopyDescriptor::new(range.handle.binding(), shape, [1].into(), 1),
+                    Bytes::from_bytes_vec(range.data),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let guard = self.track_write(&descriptors);
+        self.device.submit(move |server| {
+            server.write(descriptors, stream_id);
+            core::mem::drop(guard);
+        });
+    }
+
     /// todo: docs
     pub fn exclusive<'a, Re: Send + 'static, F: FnOnce() -> Re + Send + 'a>(
         &'a self,
@@ -335,7 +651,25 @@ impl<R: Runtime> ComputeClient<R> {
     }
 
     /// Returns a resource handle containing the given [Bytes].
+    ///
+    /// Panics if the handle can't be reserved, see [`try_create`](Self::try_create) to handle the
+    /// error.
     pub fn create(&self, data: Bytes) -> Handle {
+        match self.create_contiguous(data, false) {
+            Ok(handle) => handle,
+            Err(err) => panic!("Failed to create the handle: {err}"),
+        }
+    }
+
+    /// Returns a resource handle containing the given [Bytes], or the error that kept it from
+    /// being reserved.
+    ///
+    /// Unlike [`create`](Self::create), this waits on the server to reserve the memory.
+    pub fn try_create(&self, data: Bytes) -> Result<Handle, IoError> {
+        self.create_contiguous(data, true)
+    }
+
+    fn create_contiguous(&self, data: Bytes, checked: bool) -> Result<Handle, IoError> {
         let shape = [data.len()].into();
 
         self.do_create(
@@ -345,10 +679,46 @@ impl<R: Runtime> ComputeClient<R> {
                 1,
             )],
             vec![data],
+            checked,
         )
-        .unwrap()
-        .remove(0)
-        .memory
+        .map(|mut layouts| layouts.remove(0).memory)
+    }
+
+    /// Returns a resource handle containing the given [Bytes], starting at an offset of its
+    /// storage that is a multiple of `alignment` bytes.
+    ///
+    /// See [`empty_aligned`](Self::empty_aligned) for the alignment requirements.
+    pub fn create_aligned(&self, data: Bytes, alignment: usize) -> Result<Handle, IoError> {
+        let handle = self.empty_aligned(data.len(), alignment)?;
+
+        let stream_id = self.stream_id();
+        let descriptor =
+            CopyDescriptor::new(handle.clone().binding(), [data.len()].into(), [1].into(), 1);
+        let descriptors = vec![(descriptor, data)];
+        let guard = self.track_write(&descriptors);
+        self.device.submit(move |server| {
+            server.write(descriptors, stream_id);
+            core::mem::drop(guard);
+        });
+
+        Ok(handle)
+    }
+
+    /// Returns a resource handle containing the given data.
+    ///
+    /// Unlike [create](Self::create), host visible backends take ownership of the allocation of
+    /// `data` when it's aligned to the storage alignment, without copying it. Other backends copy
+    /// it to the device.
+    pub fn create_owned(&self, data: Vec<u8>) -> Handle {
+        let stream_id = self.stream_id();
+        let mut handle = Handle::new(stream_id, data.len() as u64);
+        self.tag_generation(&handle.clone(), core::iter::once(&mut handle));
+        let memory = handle.clone();
+
+        self.device
+            .submit(move |server| server.create_owned(memory, data, stream_id));
+
+        handle
     }
 
     /// Given a resource and shape, stores it and returns the tensor handle and strides.
@@ -381,6 +751,7 @@ impl<R: Runtime> ComputeClient<R> {
                 elem_size,
             )],
             vec![slice.to_vec()],
+            false,
         )
         .unwrap()
         .remove(0)
@@ -407,6 +778,7 @@ impl<R: Runtime> ComputeClient<R> {
                 elem_size,
             )],
             vec![bytes],
+            false,
         )
         .unwrap()
         .remove(0)
@@ -430,7 +802,8 @@ impl<R: Runtime> ComputeClient<R> {
             descriptors_.push(a);
         }
 
-        self.do_create_from_slices(descriptors_, data).unwrap()
+        self.do_create_from_slices(descriptors_, data, false)
+            .unwrap()
     }
 
     /// Reserves all `shapes` in a single storage buffer, copies the corresponding `data` into each
@@ -442,132 +815,614 @@ impl<R: Runtime> ComputeClient<R> {
     ) -> Vec<MemoryLayout> {
         let (descriptors, data) = descriptors.into_iter().unzip();
 
-        self.do_create(descriptors, data).unwrap()
+        self.do_create(descriptors, data, false).unwrap()
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace",
+        skip(self, descriptors),
+        fields(num_buffers = descriptors.len())
+    ))]
     fn do_empty(
         &self,
         descriptors: Vec<MemoryLayoutDescriptor>,
+        checked: bool,
     ) -> Result<Vec<MemoryLayout>, IoError> {
         let stream_id = self.stream_id();
-        let (handle_base, layouts) = self.utilities.layout_policy.apply(stream_id, &descriptors);
+        let (handle_base, mut layouts) =
+            self.utilities.layout_policy.apply(stream_id, &descriptors);
+        self.tag_generation(
+            &handle_base,
+            layouts.iter_mut().map(|layout| &mut layout.memory),
+        );
+        let hint = access_hint(&descriptors);
 
+        let poison = cfg!(feature = "uninit-checks").then(|| handle_base.clone().binding());
         let (size, memory) = (handle_base.size(), handle_base.memory);
-        self.device.submit(move |server| {
-            server.initialize_memory(memory, size, stream_id);
-        });
+        self.submit_reservation(checked, move |server| {
+            initialize_memory(server, memory, size, hint, stream_id)?;
+            if let Some(binding) = poison {
+                server.fill(binding, vec![crate::validation::UNINIT_SENTINEL], stream_id);
+            }
+            Ok(())
+        })?;
 
         Ok(layouts)
     }
 
-    /// Reserves `size` bytes in the storage, and returns a handle over them.
-    pub fn empty(&self, size: usize) -> Handle {
-        let shape: Shape = [size].into();
-        let descriptor = MemoryLayoutDescriptor::new(MemoryLayoutStrategy::Contiguous, shape, 1);
-        self.do_empty(vec![descriptor]).unwrap().remove(0).memory
-    }
+    /// Fills the given handle on the device by repeating the `pattern` of bytes.
+    ///
+    /// Fails when the pattern is empty or when the size of the handle isn't a multiple of the
+    /// pattern length.
+    pub fn fill(&self, handle: Handle, pattern: &[u8]) -> Result<(), IoError> {
+        let size = handle.size_in_used();
+
+        if pattern.is_empty() || !size.is_multiple_of(pattern.len() as u64) {
+            return Err(IoError::InvalidFillPattern {
+                size,
+                pattern_len: pattern.len(),
+                backtrace: BackTrace::capture(),
+            });
+        }
 
-    /// Reserves `shape` in the storage, and returns a tensor handle for it.
-    /// See [`ComputeClient::create_tensor`]
-    pub fn empty_tensor(&self, shape: Shape, elem_size: usize) -> MemoryLayout {
-        let descriptor =
-            MemoryLayoutDescriptor::new(MemoryLayoutStrategy::Optimized, shape, elem_size);
-        self.do_empty(vec![descriptor]).unwrap().remove(0)
-    }
+        let stream_id = self.stream_id();
+        let binding = handle.binding();
+        let pattern = pattern.to_vec();
 
-    /// Reserves all `shapes` in a single storage buffer, and returns the handles for them.
-    /// See [`ComputeClient::create_tensor`]
-    pub fn empty_tensors(&self, descriptors: Vec<MemoryLayoutDescriptor>) -> Vec<MemoryLayout> {
-        self.do_empty(descriptors).unwrap()
+        self.device
+            .submit(move |server| server.fill(binding, pattern, stream_id));
+
+        Ok(())
     }
 
-    /// Marks the given [Bytes] as being a staging buffer, maybe transferring it to pinned memory
-    /// for faster data transfer with compute device.
+    /// Allocates a new buffer holding a copy of the contents of the `binding`.
     ///
-    /// TODO: This blocks the compute queue, so it will drop the compute utilization.
-    pub fn staging<'a, I>(&self, bytes: I, file_only: bool)
-    where
-        I: Iterator<Item = &'a mut Bytes>,
-    {
-        let has_staging = |b: &Bytes| match b.property() {
-            AllocationProperty::Pinned => false,
-            AllocationProperty::File => true,
-            AllocationProperty::Native | AllocationProperty::Other => !file_only,
-        };
+    /// The copy is done on the device when the backend supports it, without a round trip through
+    /// the host.
+    pub fn duplicate(&self, binding: Binding) -> Handle {
+        self.flush_staged();
 
-        let mut to_be_updated = Vec::new();
-        let sizes = bytes
-            .filter_map(|b| match has_staging(b) {
-                true => {
-                    let len = b.len();
-                    to_be_updated.push(b);
-                    Some(len)
-                }
-                false => None,
-            })
-            .collect::<Vec<usize>>();
+        let stream_id = self.stream_id();
+        let handle = self.empty(binding.size_in_used() as usize);
+        let dst = handle.clone().binding();
 
-        if sizes.is_empty() {
-            return;
+        self.device
+            .submit(move |server| server.copy_buffer(binding, dst, stream_id));
+
+        handle
+    }
+
+    /// Copies the first `min(src.size, dst.size)` bytes of the `src` binding into the `dst`
+    /// binding, and returns the number of bytes copied.
+    ///
+    /// Unlike [`duplicate`](Self::duplicate), the buffers can have different sizes, such as when
+    /// growing a buffer while preserving its contents. The bytes of `dst` past the copied ones
+    /// are left untouched.
+    pub fn copy_clamped(&self, mut src: Binding, mut dst: Binding) -> usize {
+        let src_size = src.size_in_used();
+        let dst_size = dst.size_in_used();
+        let size = src_size.min(dst_size);
+
+        if size == 0 {
+            return 0;
         }
 
+        self.flush_staged();
+
+        src.offset_end = Some(src.offset_end.unwrap_or(0) + (src_size - size));
+        dst.offset_end = Some(dst.offset_end.unwrap_or(0) + (dst_size - size));
         let stream_id = self.stream_id();
-        let sizes = sizes.to_vec();
-        let stagings = self
-            .device
-            .submit_blocking(move |server| server.staging(&sizes, stream_id))
-            .unwrap();
 
-        let stagings = match stagings {
-            Ok(val) => val,
-            Err(_) => return,
-        };
+        self.device
+            .submit(move |server| server.copy_buffer(src, dst, stream_id));
 
-        to_be_updated
-            .into_iter()
-            .zip(stagings)
-            .for_each(|(b, mut staging)| {
-                b.copy_into(&mut staging);
-                core::mem::swap(b, &mut staging);
-            });
+        size as usize
     }
 
-    /// Transfer data from one client to another
-    #[cfg_attr(
-        feature = "tracing",
-        tracing::instrument(level = "trace", skip(self, src, dst_server))
-    )]
-    pub fn to_client(&self, src: Handle, dst_server: &Self) -> Handle {
-        let shape = [src.size_in_used() as usize];
-        let src_descriptor = src.copy_descriptor(shape.into(), [1].into(), 1);
-
-        if R::Server::SERVER_COMM_ENABLED {
-            self.to_client_tensor(src_descriptor, dst_server)
-        } else {
-            let alloc_desc = MemoryLayoutDescriptor::new(
-                MemoryLayoutStrategy::Contiguous,
-                src_descriptor.shape.clone(),
-                src_descriptor.elem_size,
-            );
-            self.change_client_sync(src_descriptor, alloc_desc, dst_server)
-                .memory
-        }
-    }
+    /// Atomically adds `val` to the `u32` at `offset` bytes in the `binding`, and returns the
+    /// previous value.
+    ///
+    /// This is meant for counters shared between the host and the kernels of the stream, see
+    /// [`ComputeServer::fetch_add_u32`] for the guarantees of each backend.
+    pub fn fetch_add_u32(
+        &self,
+        binding: Binding,
+        offset: usize,
+        val: u32,
+    ) -> impl Future<Output = Result<u32, ServerError>> + Send + 'static {
+        self.flush_staged();
 
-    /// Wait on the communication stream.
-    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
-    pub fn sync_collective(&self) {
-        if DeviceHandle::<R::Server>::is_blocking() {
-            panic!("Can't use `sync_collective` with a blocking device handle");
-        }
         let stream_id = self.stream_id();
+        let (sender, receiver) = async_channel::bounded(1);
 
         self.device.submit(move |server| {
-            server.sync_collective(stream_id).unwrap();
+            let result = server.fetch_add_u32(binding, offset as u64, val, stream_id);
+            // The receiver is dropped when the result isn't needed anymore.
+            sender.try_send(result).ok();
         });
-
-        // We don't actually need or want to sync the server here, but we need to make sure any
-        // task enqueued on the communication channel is done.
+        self.device.flush_queue();
+
+        async move {
+            receiver.recv().await.unwrap_or_else(|_| {
+                Err(ServerError::Generic {
+                    reason: "Communication channel with the server is down".into(),
+                    backtrace: BackTrace::capture(),
+                })
+            })
+        }
+    }
+
+    /// Writes `value` to the `u32` at `offset` bytes in the `binding`.
+    ///
+    /// Only the 4 bytes of the value are uploaded, whatever the size of the binding, which makes
+    /// this suited to control flags set from the host. Fails without writing anything when the
+    /// value is out of the bounds of the binding.
+    pub fn write_u32(
+        &self,
+        mut binding: Binding,
+        offset: usize,
+        value: u32,
+    ) -> Result<(), IoError> {
+        let size = binding.size_in_used();
+        let excess = size
+            .checked_sub(offset as u64 + 4)
+            .ok_or_else(|| IoError::OutOfBounds {
+                offset: offset as u64,
+                len: 4,
+                size,
+                backtrace: BackTrace::capture(),
+            })?;
+
+        self.flush_staged();
+
+        binding.offset_start = Some(binding.offset_start.unwrap_or(0) + offset as u64);
+        binding.offset_end = Some(binding.offset_end.unwrap_or(0) + excess);
+        let descriptor = CopyDescriptor::new(binding, [1].into(), [1].into(), 4);
+        let data = Bytes::from_bytes_vec(value.to_le_bytes().to_vec());
+        let stream_id = self.stream_id();
+
+        self.device
+            .submit(move |server| server.write(vec![(descriptor, data)], stream_id));
+
+        Ok(())
+    }
+
+    /// Reserves a zeroed `u32` counter, for kernels to report a count atomically, such as the
+    /// number of elements written by a kernel with a variable output size.
+    ///
+    /// See [`read_counter`](Self::read_counter) to read it back.
+    pub fn empty_counter(&self) -> Handle {
+        self.create_from_slice(&0u32.to_le_bytes())
+    }
+
+    /// Reads the `u32` counter at the start of the `binding`.
+    ///
+    /// Only the 4 bytes of the counter are copied back, whatever the size of the binding.
+    pub fn read_counter(
+        &self,
+        mut binding: Binding,
+    ) -> impl Future<Output = Result<u32, ServerError>> + Send + 'static {
+        let size = binding.size_in_used();
+        let read = match size.checked_sub(4) {
+            Some(excess) => {
+                binding.offset_end = Some(binding.offset_end.unwrap_or(0) + excess);
+                let descriptor = CopyDescriptor::new(binding, [1].into(), [1].into(), 4);
+                Ok(self.do_read(vec![descriptor]))
+            }
+            None => Err(IoError::OutOfBounds {
+                offset: 0,
+                len: 4,
+                size,
+                backtrace: BackTrace::capture(),
+            }),
+        };
+
+        async move {
+            let bytes = read?.await?;
+            Ok(u32::from_le_bytes([
+                bytes[0][0],
+                bytes[0][1],
+                bytes[0][2],
+                bytes[0][3],
+            ]))
+        }
+    }
+
+    /// Reads the `(offset, len)` byte ranges of the `binding` in a single read, returning the
+    /// bytes of each range in order.
+    ///
+    /// This avoids a round trip per range for sparse accesses to a large buffer. Fails without
+    /// reading anything when a range is out of the bounds of the binding.
+    pub fn read_gather(
+        &self,
+        binding: Binding,
+        ranges: &[(usize, usize)],
+    ) -> impl Future<Output = Result<Vec<Bytes>, ServerError>> + Send + 'static + use<R> {
+        let size = binding.size_in_used();
+        let descriptors = ranges
+            .iter()
+            .map(|&(offset, len)| {
+                let excess = size
+                    .checked_sub(offset as u64 + len as u64)
+                    .ok_or_else(|| IoError::OutOfBounds {
+                        offset: offset as u64,
+                        len: len as u64,
+                        size,
+                        backtrace: BackTrace::capture(),
+                    })?;
+
+                let mut binding = binding.clone();
+                binding.offset_start = Some(binding.offset_start.unwrap_or(0) + offset as u64);
+                binding.offset_end = Some(binding.offset_end.unwrap_or(0) + excess);
+                Ok(CopyDescriptor::new(binding, [len].into(), [1].into(), 1))
+            })
+            .collect::<Result<Vec<_>, IoError>>();
+        let read = descriptors.map(|descriptors| self.do_read(descriptors));
+
+        async move { read?.await }
+    }
+
+    /// Reads the `binding` back to the host in chunks of `chunk` bytes, yielded in order as the
+    /// transfer of each chunk completes.
+    ///
+    /// Only a few chunks are in host memory at once, so results larger than the host memory can
+    /// be processed while the next chunks are transferred, see [`ReadStream`].
+    ///
+    /// The chunks are read from a copy of the binding made on the device right away, so they hold
+    /// its contents as of this call even when the work submitted afterward writes to it. The copy
+    /// is read on the stream of this client, wherever the stream is polled.
+    pub fn read_stream(&self, binding: Binding, chunk: usize) -> ReadStream<R> {
+        let staging = self.duplicate(binding);
+        let client = Self {
+            stream_id: Some(self.stream_id()),
+            ..self.clone()
+        };
+
+        ReadStream::new(client, staging.binding(), chunk)
+    }
+
+    /// Reads `rows` rows of `row_bytes` bytes from the `binding`, each starting `pitch` bytes
+    /// after the previous one, and returns them tightly packed.
+    ///
+    /// Backends with strided copies, such as CUDA, remove the padding between rows while copying.
+    /// The others read the whole pitched range and remove it on the host.
+    pub fn read_strided(
+        &self,
+        binding: Binding,
+        rows: usize,
+        row_bytes: usize,
+        pitch: usize,
+    ) -> impl Future<Output = Result<Bytes, ServerError>> + Send + 'static {
+        let len = match rows {
+            0 => 0,
+            rows => (rows - 1) * pitch + row_bytes,
+        };
+        let size = binding.size_in_used();
+
+        let read = if rows > 1 && row_bytes > pitch {
+            Err(IoError::UnsupportedStrides {
+                backtrace: BackTrace::capture(),
+            })
+        } else if len as u64 > size {
+            Err(IoError::OutOfBounds {
+                offset: 0,
+                len: len as u64,
+                size,
+                backtrace: BackTrace::capture(),
+            })
+        } else {
+            let mut binding = binding;
+            binding.offset_end = Some(binding.offset_end.unwrap_or(0) + size - len as u64);
+            let strided = CopyDescriptor::new(
+                binding.clone(),
+                [rows, row_bytes].into(),
+                [pitch, 1].into(),
+                1,
+            );
+
+            Ok((self.do_read(vec![strided]), binding))
+        };
+        let client = self.clone();
+
+        async move {
+            let (read, binding) = read?;
+
+            match read.await {
+                Err(ServerError::Io(IoError::UnsupportedStrides { .. })) => {
+                    let flat = CopyDescriptor::new(binding, [len].into(), [1].into(), 1);
+                    let data = client.do_read(vec![flat]).await?.remove(0);
+                    let packed = (0..rows)
+                        .flat_map(|row| &data[row * pitch..row * pitch + row_bytes])
+                        .copied()
+                        .collect();
+
+                    Ok(Bytes::from_bytes_vec(packed))
+                }
+                result => Ok(result?.remove(0)),
+            }
+        }
+    }
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them.
+    ///
+    /// Panics if the handle can't be reserved, see [`try_empty`](Self::try_empty) to handle the
+    /// error.
+    pub fn empty(&self, size: usize) -> Handle {
+        match self.empty_contiguous(size, false) {
+            Ok(handle) => handle,
+            Err(err) => panic!("Failed to reserve {size} bytes: {err}"),
+        }
+    }
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them, or the error that
+    /// kept them from being reserved.
+    ///
+    /// Unlike [`empty`](Self::empty), this waits on the server to reserve the memory.
+    pub fn try_empty(&self, size: usize) -> Result<Handle, IoError> {
+        self.empty_contiguous(size, true)
+    }
+
+    fn empty_contiguous(&self, size: usize, checked: bool) -> Result<Handle, IoError> {
+        let shape: Shape = [size].into();
+        let descriptor = MemoryLayoutDescriptor::new(MemoryLayoutStrategy::Contiguous, shape, 1);
+        self.do_empty(vec![descriptor], checked)
+            .map(|mut layouts| layouts.remove(0).memory)
+    }
+
+    /// Reserves a buffer of each of the `sizes` in bytes, and returns their handles in the same
+    /// order.
+    ///
+    /// Consecutive buffers are packed into shared allocations of at most the
+    /// [max allocation size](Self::max_alloc_size), reserved with a single request to the server,
+    /// which is cheaper than many calls to [`empty`](Self::empty) and keeps related buffers close
+    /// in memory. A shared allocation is only freed once all the handles packed into it are
+    /// dropped.
+    pub fn empty_many(&self, sizes: &[usize]) -> Vec<Handle> {
+        let alignment = self.properties().memory.alignment as usize;
+        let max_alloc_size = self.max_alloc_size();
+
+        let mut handles = Vec::with_capacity(sizes.len());
+        let mut descriptors = Vec::new();
+        let mut packed_size = 0;
+
+        for &size in sizes {
+            let aligned_size = size.next_multiple_of(alignment.max(1));
+
+            if !descriptors.is_empty() && packed_size + aligned_size > max_alloc_size {
+                handles.extend(self.do_empty_packed(core::mem::take(&mut descriptors)));
+                packed_size = 0;
+            }
+
+            descriptors.push(MemoryLayoutDescriptor::new(
+                MemoryLayoutStrategy::Contiguous,
+                [size].into(),
+                1,
+            ));
+            packed_size += aligned_size;
+        }
+
+        if !descriptors.is_empty() {
+            handles.extend(self.do_empty_packed(descriptors));
+        }
+
+        handles
+    }
+
+    fn do_empty_packed(
+        &self,
+        descriptors: Vec<MemoryLayoutDescriptor>,
+    ) -> impl Iterator<Item = Handle> {
+        match self.do_empty(descriptors, false) {
+            Ok(layouts) => layouts.into_iter().map(|layout| layout.memory),
+            Err(err) => panic!("Failed to reserve the buffers: {err}"),
+        }
+    }
+
+    /// Reserves `bytes` of memory in chunks of at most the max page size and writes zeros to
+    /// them, so that the pages are resident before the kernels that will use them run.
+    ///
+    /// The memory is released to the pools once warmed, where later allocations reuse it. Pools
+    /// that deallocate unused pages periodically can still free it past their dealloc period.
+    pub fn warm_memory(&self, bytes: usize) {
+        let page_size = (self.properties().memory.max_page_size as usize).max(1);
+        let sizes = (0..bytes)
+            .step_by(page_size)
+            .map(|offset| page_size.min(bytes - offset));
+
+        // Keep every chunk alive until all are warmed, so that they don't reuse the same memory.
+        let _handles = sizes
+            .map(|size| {
+                let handle = self.empty(size);
+                self.fill(handle.clone(), &[0])
+                    .expect("A single byte pattern fills any size");
+                handle
+            })
+            .collect::<Vec<_>>();
+    }
+
+    /// Reserves `size` bytes in the storage, starting at an offset that is a multiple of
+    /// `alignment` bytes, and returns a handle over them.
+    ///
+    /// This is meant for kernels with wide vectorized loads, which are slower or fault when their
+    /// buffers are less aligned than the backend minimum guarantees. Fails when `alignment` isn't
+    /// a power of two.
+    pub fn empty_aligned(&self, size: usize, alignment: usize) -> Result<Handle, IoError> {
+        if !alignment.is_power_of_two() {
+            return Err(IoError::InvalidAlignment {
+                alignment,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        let stream_id = self.stream_id();
+        let mut handle = Handle::new(stream_id, size as u64);
+        self.tag_generation(&handle.clone(), core::iter::once(&mut handle));
+        let memory = handle.memory.clone();
+
+        self.submit_reservation(true, move |server| {
+            server.initialize_memory_aligned(memory, size as u64, alignment as u64, stream_id)
+        })?;
+
+        Ok(handle)
+    }
+
+    /// Reserves `size` bytes in the storage for the current batch of work, and returns a handle
+    /// over them.
+    ///
+    /// The memory is released at the next [sync](Self::sync) or [flush](Self::flush) of the stream
+    /// of this client, even if handles to it are still alive, so one-shot intermediate buffers
+    /// don't need to be tracked to be freed. Syncing other streams leaves it alone. Using the handle after that boundary is invalid, and panics
+    /// in debug builds. While a [generation](Self::begin_generation) is current, the memory is
+    /// released with the generation instead.
+    pub fn empty_transient(&self, size: usize) -> Handle {
+        let mut handle = self.empty(size);
+        if handle.transient.is_none() {
+            let binding = handle.clone().binding();
+            handle.transient = Some(self.utilities.transient.register(handle.stream, binding));
+        }
+
+        handle
+    }
+
+    /// Start a new generation of allocations, which becomes the current one of the stream of
+    /// this client.
+    ///
+    /// Every handle allocated from now on on the stream is tagged with it, until the next
+    /// generation begins or it is [freed](Self::free_generation). This fits workloads like
+    /// frames, whose buffers all have the same lifetime.
+    pub fn begin_generation(&self) -> GenerationId {
+        self.utilities.generations.begin(self.stream_id())
+    }
+
+    /// Release the memory of every handle allocated during the `generation`, even if handles to
+    /// it are still alive.
+    ///
+    /// The memory is released in order with the work submitted so far, so it is only reused
+    /// once that work completes. Using the handles afterward is invalid, and panics in debug
+    /// builds. Freeing a generation twice does nothing.
+    pub fn free_generation(&self, generation: GenerationId) {
+        let bindings = self.utilities.generations.take(generation);
+        let stream_id = self.stream_id();
+
+        self.device
+            .submit(move |server| release_transient(server, bindings, stream_id));
+    }
+
+    /// Tag the `handles` of the allocation of `base` with the current generation, if any.
+    fn tag_generation<'a>(&self, base: &Handle, handles: impl Iterator<Item = &'a mut Handle>) {
+        if let Some(expiry) = self.utilities.generations.register(base) {
+            for handle in handles {
+                handle.transient = Some(expiry.clone());
+            }
+        }
+    }
+
+    /// Reserves `size` bytes in the storage, and returns an [arena](Arena) to allocate from
+    /// them.
+    pub fn empty_arena(&self, size: usize) -> Arena {
+        Arena::new(self.empty(size), self.properties().memory.alignment)
+    }
+
+    /// Reserves `shape` in the storage, and returns a tensor handle for it.
+    /// See [`ComputeClient::create_tensor`]
+    pub fn empty_tensor(&self, shape: Shape, elem_size: usize) -> MemoryLayout {
+        let descriptor =
+            MemoryLayoutDescriptor::new(MemoryLayoutStrategy::Optimized, shape, elem_size);
+        self.do_empty(vec![descriptor], false).unwrap().remove(0)
+    }
+
+    /// Reserves all `shapes` in a single storage buffer, and returns the handles for them.
+    /// See [`ComputeClient::create_tensor`]
+    pub fn empty_tensors(&self, descriptors: Vec<MemoryLayoutDescriptor>) -> Vec<MemoryLayout> {
+        self.do_empty(descriptors, false).unwrap()
+    }
+
+    /// Marks the given [Bytes] as being a staging buffer, maybe transferring it to pinned memory
+    /// for faster data transfer with compute device.
+    ///
+    /// TODO: This blocks the compute queue, so it will drop the compute utilization.
+    pub fn staging<'a, I>(&self, bytes: I, file_only: bool)
+    where
+        I: Iterator<Item = &'a mut Bytes>,
+    {
+        let has_staging = |b: &Bytes| match b.property() {
+            AllocationProperty::Pinned => false,
+            AllocationProperty::File => true,
+            AllocationProperty::Native | AllocationProperty::Other => !file_only,
+        };
+
+        let mut to_be_updated = Vec::new();
+        let sizes = bytes
+            .filter_map(|b| match has_staging(b) {
+                true => {
+                    let len = b.len();
+                    to_be_updated.push(b);
+                    Some(len)
+                }
+                false => None,
+            })
+            .collect::<Vec<usize>>();
+
+        if sizes.is_empty() {
+            return;
+        }
+
+        let stream_id = self.stream_id();
+        let sizes = sizes.to_vec();
+        let stagings = self
+            .device
+            .submit_blocking(move |server| server.staging(&sizes, stream_id))
+            .unwrap();
+
+        let stagings = match stagings {
+            Ok(val) => val,
+            Err(_) => return,
+        };
+
+        to_be_updated
+            .into_iter()
+            .zip(stagings)
+            .for_each(|(b, mut staging)| {
+                b.copy_into(&mut staging);
+                core::mem::swap(b, &mut staging);
+            });
+    }
+
+    /// Transfer data from one client to another
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip(self, src, dst_server))
+    )]
+    pub fn to_client(&self, src: Handle, dst_server: &Self) -> Handle {
+        let shape = [src.size_in_used() as usize];
+        let src_descriptor = src.copy_descriptor(shape.into(), [1].into(), 1);
+
+        if R::Server::SERVER_COMM_ENABLED {
+            self.to_client_tensor(src_descriptor, dst_server)
+        } else {
+            let alloc_desc = MemoryLayoutDescriptor::new(
+                MemoryLayoutStrategy::Contiguous,
+                src_descriptor.shape.clone(),
+                src_descriptor.elem_size,
+            );
+            self.change_client_sync(src_descriptor, alloc_desc, dst_server)
+                .memory
+        }
+    }
+
+    /// Wait on the communication stream.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn sync_collective(&self) {
+        if self.device.blocks() {
+            panic!("Can't use `sync_collective` with a blocking device handle");
+        }
+        let stream_id = self.stream_id();
+
+        self.device.submit(move |server| {
+            server.sync_collective(stream_id).unwrap();
+        });
+
+        // We don't actually need or want to sync the server here, but we need to make sure any
+        // task enqueued on the communication channel is done.
         self.device.flush_queue();
     }
 
@@ -584,7 +1439,7 @@ impl<R: Runtime> ComputeClient<R> {
         device_ids: Vec<DeviceId>,
         op: ReduceOperation,
     ) {
-        if DeviceHandle::<R::Server>::is_blocking() {
+        if self.device.blocks() {
             panic!("Can't use `all_reduce` with a blocking device handle");
         }
 
@@ -677,9 +1532,44 @@ impl<R: Runtime> ComputeClient<R> {
         kernel: <R::Server as ComputeServer>::Kernel,
         count: CubeCount,
         bindings: KernelArguments,
+        group: Option<BindGroup>,
         mode: ExecutionMode,
         stream_id: StreamId,
-    ) {
+    ) -> Result<(), ServerError> {
+        self.flush_staged();
+
+        let mut bindings = bindings;
+        if let Some(bucket) = bindings.shape_bucket
+            && let Some(constants) = self.utilities.shape_buckets.get(kernel.id(), bucket)
+        {
+            bindings.spec_constants = constants;
+        }
+
+        let uninit_checks =
+            cfg!(feature = "uninit-checks") && matches!(mode, ExecutionMode::Checked);
+        let mut group = group;
+        // The checks need every buffer of the launch, so the group is bound like the others.
+        if (self.utilities.verifier.is_enabled() || uninit_checks)
+            && let Some(group) = group.take()
+        {
+            bindings = bindings.with_bind_group(&group);
+        }
+
+        if self.utilities.verifier.is_enabled() {
+            self.verify_launch(&kernel, &count, &bindings);
+        }
+
+        if uninit_checks {
+            self.warn_uninitialized_inputs(&kernel, &bindings);
+        }
+
+        if let CubeCount::Dynamic(binding) = &count {
+            self.utilities
+                .last_dynamic_count
+                .lock()
+                .insert(stream_id, binding.clone());
+        }
+
         let level = self.utilities.logger.profile_level();
 
         match level {
@@ -687,31 +1577,43 @@ impl<R: Runtime> ComputeClient<R> {
                 let utilities = self.utilities.clone();
                 self.device.submit(move |state| {
                     let name = kernel.name();
-                    unsafe { state.launch(kernel, count, bindings, mode, stream_id) };
+                    unsafe { launch_on(state, kernel, count, bindings, group, mode, stream_id) };
 
                     if matches!(level, Some(ProfileLevel::ExecutionOnly)) {
                         let info = type_name_format(name, TypeNameFormatLevel::Balanced);
                         utilities.logger.register_execution(info);
                     }
                 });
+                Ok(())
             }
             Some(level) => {
                 let name = kernel.name();
                 let kernel_id = kernel.id();
                 let context = self.device.clone();
                 let count_moved = count.clone();
-                let (result, profile) = self
-                    .profile(
-                        move || {
-                            context
-                                .submit_blocking(move |state| unsafe {
-                                    state.launch(kernel, count_moved, bindings, mode, stream_id)
-                                })
-                                .unwrap()
-                        },
-                        name,
-                    )
-                    .unwrap();
+                let (result, profile) = self.profile(
+                    move || {
+                        context
+                            .submit_blocking(move |state| unsafe {
+                                launch_on(
+                                    state,
+                                    kernel,
+                                    count_moved,
+                                    bindings,
+                                    group,
+                                    mode,
+                                    stream_id,
+                                )
+                            })
+                            .map_err(|err| ServerError::Generic {
+                                reason: format!(
+                                    "Communication channel with the server is down: {err:?}"
+                                ),
+                                backtrace: BackTrace::capture(),
+                            })
+                    },
+                    name,
+                )?;
                 let info = match level {
                     ProfileLevel::Full => {
                         format!("{name}: {kernel_id} CubeCount {count:?}")
@@ -724,24 +1626,530 @@ impl<R: Runtime> ComputeClient<R> {
         }
     }
 
-    /// Launches the `kernel` with the given `bindings`.
+    /// Run the [CPU reference](crate::kernel::CpuReference) of the kernel, if any, on the
+    /// current content of its buffers, and register the results as their shadows.
+    fn verify_launch(
+        &self,
+        kernel: &<R::Server as ComputeServer>::Kernel,
+        count: &CubeCount,
+        bindings: &KernelArguments,
+    ) {
+        let verifier = &self.utilities.verifier;
+        let Some(reference) = kernel.cpu_reference() else {
+            verifier.invalidate(&bindings.buffers);
+            return;
+        };
+
+        let read = |bindings: &[Binding]| {
+            let descriptors = bindings
+                .iter()
+                .map(|binding| {
+                    let shape = [binding.size_in_used() as usize].into();
+                    CopyDescriptor::new(binding.clone(), shape, [1].into(), 1)
+                })
+                .collect();
+            cubecl_common::reader::read_sync(self.do_read_unverified(descriptors))
+        };
+
+        let cube_count = match count {
+            CubeCount::Static(x, y, z) => [*x, *y, *z],
+            CubeCount::Dynamic(binding) => match read(core::slice::from_ref(binding)) {
+                Ok(bytes) => {
+                    let count: &[u32] = bytemuck::cast_slice(&bytes[0][0..12]);
+                    [count[0], count[1], count[2]]
+                }
+                Err(_) => return verifier.invalidate(&bindings.buffers),
+            },
+        };
+        let mut buffers: Vec<Vec<u8>> = match read(&bindings.buffers) {
+            Ok(bytes) => bytes.into_iter().map(|bytes| bytes.to_vec()).collect(),
+            Err(_) => return verifier.invalidate(&bindings.buffers),
+        };
+
+        reference.execute(cube_count, &mut buffers, &bindings.info.data);
+        verifier.insert(kernel.name(), &bindings.buffers, buffers);
+    }
+
+    /// Warn about the read-only buffers of the launch that are still filled with the
+    /// [sentinel](crate::validation::UNINIT_SENTINEL) of uninitialized buffers.
+    ///
+    /// Every read-only buffer is read back before the launch, so this is only meant for debugging.
+    fn warn_uninitialized_inputs(
+        &self,
+        kernel: &<R::Server as ComputeServer>::Kernel,
+        bindings: &KernelArguments,
+    ) {
+        let Some(layout) = kernel.layout() else {
+            return;
+        };
+
+        let (indices, descriptors): (Vec<_>, Vec<_>) = layout
+            .buffers
+            .iter()
+            .zip(bindings.buffers.iter())
+            .enumerate()
+            .filter(|(_, (arg, _))| arg.access == Visibility::Read)
+            .map(|(index, (_, binding))| {
+                let shape = [binding.size_in_used() as usize].into();
+                let descriptor = CopyDescriptor::new(binding.clone(), shape, [1].into(), 1);
+                (index, descriptor)
+            })
+            .unzip();
+
+        if descriptors.is_empty() {
+            return;
+        }
+
+        let Ok(bytes) = cubecl_common::reader::read_sync(self.do_read_unverified(descriptors))
+        else {
+            return;
+        };
+
+        for (index, data) in indices.into_iter().zip(bytes) {
+            if crate::validation::is_uninitialized(&data) {
+                log::warn!(
+                    "Kernel {} reads buffer {index}, which was never written since it was reserved",
+                    kernel.name()
+                );
+            }
+        }
+    }
+
+    /// Launch the kernel with the specialization `constants` when its launches are tagged with
+    /// the shape `bucket`.
+    ///
+    /// Each bucket gets its own pipeline, specialized from the same compiled kernel, so an
+    /// autotuner can keep a table of tuned pipelines by shape that is consulted on every launch
+    /// with [`KernelArguments::with_shape_bucket`]. The table is shared by every client of the
+    /// device.
+    pub fn register_shape_bucket(
+        &self,
+        kernel: KernelId,
+        bucket: ShapeBucket,
+        constants: Vec<ConstantValue>,
+    ) {
+        self.utilities
+            .shape_buckets
+            .register(kernel, bucket, constants);
+    }
+
+    /// Set how kernels launched on this device are verified against their
+    /// [CPU reference](crate::kernel::CpuReference).
+    ///
+    /// The mode is shared by every client of the device.
+    pub fn set_verify_mode(&self, mode: VerifyMode) {
+        self.utilities.verifier.set_mode(mode);
+    }
+
+    /// Set what happens to the allocations that don't fit in the memory of the device.
+    ///
+    /// The mode is shared by every client of the device.
+    pub fn set_spill_mode(&self, mode: SpillMode) {
+        self.utilities.spill.set_mode(mode);
+    }
+
+    /// Whether the memory of the `handle` was [spilled](SpillMode) to host memory.
+    ///
+    /// Waits for the server to process the allocations submitted so far.
+    pub fn is_spilled(&self, handle: &Handle) -> bool {
+        // The allocation is made by the server, so it must be processed before the lookup.
+        self.device.submit_blocking(|_| {}).ok();
+        self.utilities.spill.is_spilled(&handle.memory)
+    }
+
+    /// Rewrite the source of the kernels (e.g. WGSL or CUDA C) with `transform` before the
+    /// backend compiles them, to inject pragmas, dump the sources or patch a kernel.
+    ///
+    /// Only the kernels compiled after the call are affected, the ones already compiled or loaded
+    /// from a cache are kept as is. Backends that don't compile kernels from their source, such as
+    /// SPIR-V, ignore the transform. It is shared by every client of the device.
+    pub fn set_source_transform(&self, transform: impl Fn(&str) -> String + Send + Sync + 'static) {
+        self.utilities
+            .source_transform
+            .set(Some(Arc::new(transform)));
+    }
+
+    /// Compile the source of the kernels as generated again, see
+    /// [`set_source_transform`](Self::set_source_transform).
+    pub fn clear_source_transform(&self) {
+        self.utilities.source_transform.set(None);
+    }
+
+    /// Set what happens when a read returns a buffer that was never written since it was
+    /// reserved.
+    ///
+    /// Only detected with the `uninit-checks` feature enabled. The mode is shared by every client
+    /// of the device.
+    pub fn set_uninit_read_mode(&self, mode: UninitReadMode) {
+        self.utilities.uninit_reads.set_mode(mode);
+    }
+
+    /// Launches the `kernel` with the given `bindings`.
+    #[track_caller]
+    pub fn launch(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) {
+        // SAFETY: Using checked execution mode.
+        let result = unsafe {
+            self.launch_inner(
+                kernel,
+                count,
+                bindings,
+                None,
+                ExecutionMode::Checked,
+                self.stream_id(),
+            )
+        };
+
+        if let Err(err) = result {
+            panic!("Failed to launch the kernel: {err}");
+        }
+    }
+
+    /// Launches the `kernel` with the given `bindings` after the work of the `dependencies`,
+    /// even when they share no buffer with it, and returns the id of the launch.
+    ///
+    /// Only buffers shared between submissions on different streams order them otherwise, so this
+    /// expresses the join points of independent branches running concurrently on their own
+    /// streams.
+    ///
+    /// No fence is recorded for the returned submission, so launches don't flush the stream:
+    /// [waiting](Self::sync_until) on it waits on the work of its stream submitted by the time of
+    /// the wait. Use [`submission`](Self::submission) to wait on this point precisely.
+    #[track_caller]
+    pub fn launch_after(
+        &self,
+        dependencies: &[SubmissionId],
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) -> SubmissionId {
+        let stream_id = self.stream_id();
+        let dependencies = dependencies
+            .iter()
+            .map(|submission| submission.stream)
+            .filter(|stream| *stream != stream_id)
+            .collect::<Vec<_>>();
+
+        if !dependencies.is_empty() {
+            self.device
+                .submit(move |server| server.wait_streams(dependencies, stream_id));
+        }
+
+        self.launch(kernel, count, bindings);
+        SubmissionId {
+            stream: stream_id,
+            index: self.utilities.submissions.next_index(stream_id),
+        }
+    }
+
+    /// The id of the work submitted so far by this client, for later launches to
+    /// [depend on](Self::launch_after) or for the host to [wait on](Self::sync_until).
+    ///
+    /// The stream is flushed to mark the end of the submission.
+    pub fn submission(&self) -> SubmissionId {
+        let stream_id = self.stream_id();
+        let index = self.utilities.submissions.next_index(stream_id);
+        let utilities = self.utilities.clone();
+
+        self.device.submit(move |server| {
+            let fence = server.fence(stream_id);
+            utilities.submissions.insert(stream_id, index, fence);
+        });
+
+        SubmissionId {
+            stream: stream_id,
+            index,
+        }
+    }
+
+    /// Wait for the completion of the `submission` and of the work submitted before it on its
+    /// stream, leaving the work submitted after in flight.
+    ///
+    /// This bounds how far ahead of the host the device runs, such as to pace frames. Only the
+    /// most recent submissions of each stream are tracked precisely; waiting on an older one, or
+    /// on one already waited on, may also wait on later work.
+    pub fn sync_until(&self, submission: SubmissionId) -> DynFut<Result<(), ServerError>> {
+        let utilities = self.utilities.clone();
+
+        self.device
+            .submit_blocking(move |server| match utilities.submissions.take(submission) {
+                Some(fence) => fence,
+                None => server.sync(submission.stream),
+            })
+            .unwrap()
+    }
+
+    /// Launches the `kernel` with the given `bindings` and flushes the stream, returning the
+    /// error that left the stream unhealthy if the launch, or any work before it, failed.
+    ///
+    /// Less work is batched than with [`launch`](Self::launch), since the stream is flushed after
+    /// every kernel.
+    #[track_caller]
+    pub fn try_launch(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) -> Result<(), ServerError> {
+        // SAFETY: Using checked execution mode.
+        unsafe {
+            self.launch_inner(
+                kernel,
+                count,
+                bindings,
+                None,
+                ExecutionMode::Checked,
+                self.stream_id(),
+            )?
+        };
+        self.flush()
+    }
+
+    /// Create a [`BindGroup`] from the given handles, to be reused across many launches with
+    /// [`Self::launch_with_group`].
+    ///
+    /// The server resolves the buffers of the group once and caches them, e.g. as a bind group
+    /// on WGPU or as an argument array on CUDA.
+    pub fn create_bind_group(&self, handles: Vec<Handle>) -> BindGroup {
+        let group = BindGroup::new(handles);
+        let group_moved = group.clone();
+        let stream_id = self.stream_id();
+
+        self.device
+            .submit(move |server| server.create_bind_group(group_moved, stream_id));
+
+        group
+    }
+
+    /// Launches the `kernel` with the buffers of the `group` bound first, followed by the
+    /// buffers of `extra`.
+    #[track_caller]
+    pub fn launch_with_group(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        group: &BindGroup,
+        extra: KernelArguments,
+    ) {
+        // SAFETY: Using checked execution mode.
+        let result = unsafe {
+            self.launch_inner(
+                kernel,
+                count,
+                extra,
+                Some(group.clone()),
+                ExecutionMode::Checked,
+                self.stream_id(),
+            )
+        };
+
+        if let Err(err) = result {
+            panic!("Failed to launch the kernel: {err}");
+        }
+    }
+
+    /// Launches the `kernel` with the buffers of the `group` bound first, followed by the
+    /// buffers of `extra`, in the execution mode of
+    /// [unchecked launches](Self::launch_unchecked).
+    ///
+    /// # Safety
+    ///
+    /// Same as [`launch_unchecked`](Self::launch_unchecked).
+    #[track_caller]
+    pub unsafe fn launch_with_group_unchecked(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        group: &BindGroup,
+        extra: KernelArguments,
+    ) {
+        // SAFETY: Caller has to uphold kernel being safe.
+        let result = unsafe {
+            self.launch_inner(
+                kernel,
+                count,
+                extra,
+                Some(group.clone()),
+                self.unchecked_mode(),
+                self.stream_id(),
+            )
+        };
+
+        if let Err(err) = result {
+            panic!("Failed to launch the kernel: {err}");
+        }
+    }
+
+    /// Launches the `kernel` with the given `bindings` cooperatively, with every cube
+    /// co-resident on the device so that they can synchronize grid-wide.
+    ///
+    /// Fails when the device doesn't support
+    /// [cooperative launches](Features::cooperative_launch), or when the cube count is larger
+    /// than what can be co-resident. Unlike other launches, this waits for the server to
+    /// validate the launch, and cooperative launches aren't profiled.
+    #[track_caller]
+    pub fn launch_cooperative(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) -> Result<(), ServerError> {
+        if !self.features().cooperative_launch {
+            return Err(LaunchError::Unknown {
+                reason: "Cooperative launches aren't supported on this device".into(),
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+
+        self.flush_staged();
+
+        let stream_id = self.stream_id();
+        if self.utilities.verifier.is_enabled() {
+            self.verify_launch(&kernel, &count, &bindings);
+        }
+
+        self.device
+            .submit_blocking(move |server| {
+                // SAFETY: Using checked execution mode.
+                unsafe {
+                    server.launch_cooperative(
+                        kernel,
+                        count,
+                        bindings,
+                        ExecutionMode::Checked,
+                        stream_id,
+                    )
+                }
+            })
+            .unwrap()
+    }
+
+    /// Launches the `kernel` with the given `bindings` only if the `condition` computed on the
+    /// device allows it.
+    ///
+    /// The `condition` handle holds the cube count of the dispatch as 3 `u32` values, like
+    /// [`CubeCount::Dynamic`]. A prior kernel writes the cube count to run, or zeros to skip the
+    /// dispatch, in which case the launch is a no-op.
+    ///
+    /// Backends with indirect dispatches, like WGPU, don't need a host round-trip. CUDA and HIP
+    /// read the cube count back before launching, which waits for the prior work on the stream.
     #[track_caller]
-    pub fn launch(
+    pub fn launch_if(
         &self,
+        condition: Handle,
         kernel: <R::Server as ComputeServer>::Kernel,
-        count: CubeCount,
         bindings: KernelArguments,
     ) {
-        // SAFETY: Using checked execution mode.
-        unsafe {
-            self.launch_inner(
-                kernel,
-                count,
-                bindings,
-                ExecutionMode::Checked,
-                self.stream_id(),
-            )
+        self.launch(kernel, CubeCount::Dynamic(condition.binding()), bindings)
+    }
+
+    /// Reads back the cube count of the last launch with a [`CubeCount::Dynamic`] made on the
+    /// stream of the client, as the 3 `u32` values the dispatch resolved.
+    ///
+    /// The count is read in order with the work of the stream, so it is the one used by the
+    /// dispatch unless a later task overwrites it. The buffer of the count is kept alive until it
+    /// is read, and only the first read after a launch succeeds. Fails when no dynamic launch was
+    /// made since the last read.
+    pub fn last_dynamic_count(
+        &self,
+    ) -> impl Future<Output = Result<[u32; 3], ServerError>> + Send + 'static {
+        let binding = self
+            .utilities
+            .last_dynamic_count
+            .lock()
+            .remove(&self.stream_id());
+        let read = binding.map(|binding| self.read_gather(binding, &[(0, 12)]));
+
+        async move {
+            let read = read.ok_or_else(|| ServerError::Generic {
+                reason: "No dynamic launch was made on this stream since the last read".into(),
+                backtrace: BackTrace::capture(),
+            })?;
+            let bytes = read.await?;
+            let value =
+                |index: usize| u32::from_le_bytes(bytes[0][index * 4..][..4].try_into().unwrap());
+
+            Ok([value(0), value(1), value(2)])
+        }
+    }
+
+    /// Launches the kernels made by `kernel` over `total` cubes, in tiles of at most `tile` cubes
+    /// along each axis, so that grids larger than the device limits can be dispatched.
+    ///
+    /// Before each launch, the cube offset of the tile is written as 3 `u32` values at the start
+    /// of the `offset_binding`, for the kernel to add to its cube position. The last tile along an
+    /// axis only covers the remaining cubes when `total` isn't a multiple of `tile`.
+    ///
+    /// Fails without launching anything when the tile is empty or larger than the
+    /// [max cube count](cubecl_ir::HardwareProperties::max_cube_count), or when the
+    /// `offset_binding` can't hold the offset.
+    #[track_caller]
+    pub fn execute_tiled(
+        &self,
+        kernel: impl Fn() -> <R::Server as ComputeServer>::Kernel,
+        total: [u32; 3],
+        tile: [u32; 3],
+        mut offset_binding: Binding,
+        bindings: KernelArguments,
+    ) -> Result<(), LaunchError> {
+        let (max_x, max_y, max_z) = self.properties().hardware.max_cube_count;
+        if tile.contains(&0) {
+            return Err(LaunchError::Unknown {
+                reason: format!("Can't launch tiles of {tile:?} cubes"),
+                backtrace: BackTrace::capture(),
+            });
+        }
+        if tile[0] > max_x || tile[1] > max_y || tile[2] > max_z {
+            return Err(ResourceLimitError::CubeCount {
+                requested: (tile[0], tile[1], tile[2]),
+                max: (max_x, max_y, max_z),
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+
+        let size = offset_binding.size_in_used();
+        let Some(excess) = size.checked_sub(12) else {
+            return Err(IoError::OutOfBounds {
+                offset: 0,
+                len: 12,
+                size,
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        };
+        offset_binding.offset_end = Some(offset_binding.offset_end.unwrap_or(0) + excess);
+
+        for z in (0..total[2]).step_by(tile[2] as usize) {
+            for y in (0..total[1]).step_by(tile[1] as usize) {
+                for x in (0..total[0]).step_by(tile[0] as usize) {
+                    let offset = [x, y, z]
+                        .iter()
+                        .flat_map(|it| it.to_le_bytes())
+                        .collect::<Vec<_>>();
+                    let descriptor =
+                        CopyDescriptor::new(offset_binding.clone(), [3].into(), [1].into(), 4);
+                    let data = vec![(descriptor, Bytes::from_bytes_vec(offset))];
+                    let stream_id = self.stream_id();
+                    self.device
+                        .submit(move |server| server.write(data, stream_id));
+
+                    let count = CubeCount::Static(
+                        tile[0].min(total[0] - x),
+                        tile[1].min(total[1] - y),
+                        tile[2].min(total[2] - z),
+                    );
+                    self.launch(kernel(), count, bindings.clone());
+                }
+            }
         }
+
+        Ok(())
     }
 
     /// Launches the `kernel` with the given `bindings` without performing any bound checks.
@@ -759,39 +2167,137 @@ impl<R: Runtime> ComputeClient<R> {
         bindings: KernelArguments,
     ) {
         // SAFETY: Caller has to uphold kernel being safe.
-        unsafe {
+        let result = unsafe {
             self.launch_inner(
                 kernel,
                 count,
                 bindings,
-                match self.utilities.check_mode {
-                    crate::config::compilation::BoundsCheckMode::Enforce => ExecutionMode::Checked,
-                    crate::config::compilation::BoundsCheckMode::Validate => {
-                        ExecutionMode::Validate
-                    }
-                    crate::config::compilation::BoundsCheckMode::Auto => ExecutionMode::Unchecked,
-                },
+                None,
+                self.unchecked_mode(),
                 self.stream_id(),
             )
+        };
+
+        if let Err(err) = result {
+            panic!("Failed to launch the kernel: {err}");
         }
     }
 
+    /// The execution mode of unchecked launches, based on the configured bounds check mode.
+    fn unchecked_mode(&self) -> ExecutionMode {
+        match self.utilities.check_mode {
+            crate::config::compilation::BoundsCheckMode::Enforce => ExecutionMode::Checked,
+            crate::config::compilation::BoundsCheckMode::Validate => ExecutionMode::Validate,
+            crate::config::compilation::BoundsCheckMode::Auto => ExecutionMode::Unchecked,
+        }
+    }
+
+    /// Launches the `kernel` with the given `bindings` like [`Self::launch_unchecked`], without
+    /// the client-side handling of the launch.
+    ///
+    /// The kernel is submitted straight to the server: no profiling, verification, execution
+    /// logging or tracing is performed, even when enabled. Writes to
+    /// [staged buffers](Self::create_staged) are still flushed first. The execution mode follows
+    /// the configured [bounds check mode](crate::config::compilation::BoundsCheckMode) like
+    /// [`Self::launch_unchecked`], so a kernel warmed up with [`Self::launch_unchecked`] isn't
+    /// compiled again.
+    ///
+    /// Only the client-side handling is skipped: the server still looks up the compiled kernel
+    /// by its id, validates the launch, resolves the bindings and builds the metadata as usual,
+    /// so a dispatch isn't cheaper on the server than with [`Self::launch_unchecked`] while
+    /// profiling and verification are disabled.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`Self::launch_unchecked`].
+    #[track_caller]
+    pub unsafe fn launch_raw(
+        &self,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) {
+        self.flush_staged();
+
+        let stream_id = self.stream_id();
+        let mode = self.unchecked_mode();
+
+        self.device.submit(move |server| {
+            // SAFETY: Caller has to uphold kernel being safe.
+            unsafe { server.launch(kernel, count, bindings, mode, stream_id) }
+        });
+    }
+
     /// Flush all outstanding commands.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn flush(&self) -> Result<(), ServerError> {
         let stream_id = self.stream_id();
+        let transient = self.utilities.transient.take(stream_id);
+
+        self.device
+            .submit_blocking(move |server| {
+                release_transient(server, transient, stream_id);
+                server.flush(stream_id)
+            })
+            .unwrap()
+    }
+
+    /// The time of the work of the stream that completed since the last poll, without waiting on
+    /// the work still running, such as to update a frame time graph every frame.
+    ///
+    /// This is an estimate, not a device timer: on WGPU, it's the wall-clock time from the
+    /// submission of the work until the poll that observed its completion, which also covers the
+    /// work submitted before it by other streams.
+    ///
+    /// Returns `None` when no work completed in the meantime, or when the backend can't time its
+    /// work without waiting on it.
+    pub fn poll_gpu_time(&self) -> Option<core::time::Duration> {
+        let stream_id = self.stream_id();
+        self.device
+            .submit_blocking(move |server| server.poll_gpu_time(stream_id))
+            .unwrap()
+    }
+
+    /// Poll the device once without blocking, running the callbacks of the work that completed,
+    /// such as the maps of reads.
+    ///
+    /// Returns whether submitted work is still in flight, so that an event loop can keep polling
+    /// until it returns `false`. This is what drives the device when automatic polling is
+    /// disabled, e.g. with `MapWait::Manual` on wgpu. Backends whose work completes without
+    /// polling return `false`.
+    pub fn poll_device(&self) -> bool {
+        self.device
+            .submit_blocking(|server| server.poll_device())
+            .unwrap()
+    }
+
+    /// Whether a [sync](Self::sync) would have work to wait on or submit, so idle iterations of
+    /// a loop can skip it.
+    ///
+    /// Backends that can't tell whether the device is idle always report pending work.
+    pub fn has_pending_work(&self) -> bool {
+        let stream_id = self.stream_id();
+        if self.utilities.staged.is_dirty() || !self.utilities.transient.is_empty(stream_id) {
+            return true;
+        }
 
         self.device
-            .submit_blocking(move |server| server.flush(stream_id))
+            .submit_blocking(move |server| server.has_pending_work(stream_id))
             .unwrap()
     }
 
     /// Wait for the completion of every task in the server.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn sync(&self) -> DynFut<Result<(), ServerError>> {
         let stream_id = self.stream_id();
+        let transient = self.utilities.transient.take(stream_id);
 
         let fut = self
             .device
-            .submit_blocking(move |server| server.sync(stream_id))
+            .submit_blocking(move |server| {
+                release_transient(server, transient, stream_id);
+                server.sync(stream_id)
+            })
             .unwrap();
 
         self.utilities.logger.profile_summary();
@@ -799,6 +2305,72 @@ impl<R: Runtime> ComputeClient<R> {
         fut
     }
 
+    /// Wait for the completion of every task in the server, failing with
+    /// [`SyncTimeout::Elapsed`] if the device doesn't complete them within `timeout`.
+    ///
+    /// The future of [`sync`](Self::sync) never resolves when the device stops making progress,
+    /// which this guards against. After a timeout, the synchronization keeps waiting on a
+    /// background thread and the device should be considered lost.
+    #[cfg(multi_threading)]
+    pub fn sync_timeout(
+        &self,
+        timeout: core::time::Duration,
+    ) -> impl Future<Output = Result<SyncReport, SyncTimeout>> + Send + 'static {
+        let (sender, receiver) = async_channel::bounded(1);
+        // The synchronization runs on another thread, so the client is bound to the stream of the
+        // caller instead of resolving the stream of that thread.
+        let mut client = self.clone();
+        client.stream_id = Some(self.stream_id());
+
+        std::thread::spawn(move || {
+            let (sender_sync, receiver_sync) = std::sync::mpsc::channel();
+            let start = web_time::Instant::now();
+
+            std::thread::spawn(move || {
+                let was_idle = !client.has_pending_work();
+                let result = cubecl_common::future::block_on(client.sync());
+                sender_sync.send((was_idle, result)).ok();
+            });
+
+            let result = match receiver_sync.recv_timeout(timeout) {
+                Ok((was_idle, result)) => result
+                    .map(|_| SyncReport {
+                        elapsed: start.elapsed(),
+                        was_idle,
+                    })
+                    .map_err(SyncTimeout::from),
+                Err(_) => Err(SyncTimeout::Elapsed {
+                    timeout,
+                    backtrace: BackTrace::capture(),
+                }),
+            };
+            sender.try_send(result).ok();
+        });
+
+        async move {
+            receiver
+                .recv()
+                .await
+                .expect("The timeout thread always sends a result")
+        }
+    }
+
+    /// Runs `task` on the server when it is of the concrete type `S`, returning `None` otherwise.
+    ///
+    /// This is the escape hatch for generic code to reach the extensions of a specific backend,
+    /// such as CUDA graphs, that aren't part of [`ComputeServer`].
+    pub fn downcast_server<S: ComputeServer, O: Send + 'static>(
+        &self,
+        task: impl FnOnce(&mut S) -> O + Send + 'static,
+    ) -> Option<O> {
+        self.device
+            .submit_blocking(move |server| {
+                let server: &mut dyn core::any::Any = server;
+                server.downcast_mut::<S>().map(task)
+            })
+            .unwrap()
+    }
+
     /// Get the features supported by the compute server.
     pub fn properties(&self) -> &DeviceProperties {
         &self.utilities.properties
@@ -809,6 +2381,24 @@ impl<R: Runtime> ComputeClient<R> {
         &self.utilities.properties.features
     }
 
+    /// The maximum size in bytes of the shared memory of a cube, including the
+    /// [dynamic shared memory](KernelArguments::with_dynamic_shared_mem) of the launch.
+    ///
+    /// On CUDA, this is the opt-in limit, which is larger than the default 48KB on most devices.
+    pub fn max_dynamic_shared_mem(&self) -> usize {
+        self.utilities.properties.hardware.max_shared_memory_size
+    }
+
+    /// The maximum size in bytes of a single allocation on the device.
+    ///
+    /// The memory pools refuse larger buffers, so larger tensors should be split across many
+    /// buffers. The `try_` variants, such as [`try_empty`](Self::try_empty), report the error of
+    /// the server instead of panicking. On WGPU, this is the maximum storage buffer binding size
+    /// of the device limits.
+    pub fn max_alloc_size(&self) -> usize {
+        self.utilities.properties.memory.max_page_size as usize
+    }
+
     /// # Warning
     ///
     /// For private use only.
@@ -816,11 +2406,203 @@ impl<R: Runtime> ComputeClient<R> {
         Arc::get_mut(&mut self.utilities).map(|state| &mut state.properties)
     }
 
-    /// Get the current memory usage of this client.
-    pub fn memory_usage(&self) -> Result<MemoryUsage, ServerError> {
+    /// Get the bytes of the reads and writes submitted from this client that haven't completed yet.
+    ///
+    /// Unlike the [memory usage](Self::memory_usage), which covers device resident memory, this
+    /// covers transient transfer memory, such as staging buffers. It is a single atomic load, so
+    /// it can be polled every frame to throttle transfers.
+    pub fn bytes_in_flight(&self) -> usize {
+        self.utilities.in_flight.load()
+    }
+
+    /// Get the current memory usage of the device.
+    ///
+    /// The usage is read from counters published by the allocators of every stream, without
+    /// waiting on the server, so it can be stale:
+    ///
+    /// - Allocations of tasks still queued on the server aren't counted yet.
+    /// - Freed memory is only reflected after a few more allocations, or a
+    ///   [cleanup](Self::memory_cleanup).
+    ///
+    /// Use [`Self::memory_usage_settled`] for an exact snapshot of the current stream.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.utilities.memory_usage.load()
+    }
+
+    /// Get the [layout](ChunkInfo) of every memory chunk of the current stream, listing which
+    /// byte ranges are allocated and which are free.
+    ///
+    /// This is meant to debug fragmentation, e.g. when a large allocation fails despite enough
+    /// free memory being reported.
+    pub fn chunk_map(&self) -> Result<Vec<ChunkInfo>, ServerError> {
+        let stream_id = self.stream_id();
+
+        self.device
+            .submit_blocking(move |server| server.chunk_map(stream_id))
+            .unwrap()
+    }
+
+    /// Get the memory usage of the current stream once the tasks submitted so far are done.
+    ///
+    /// Unlike [`Self::memory_usage`], this waits for the pending tasks to release the buffers
+    /// they hold, and reads the usage straight from the allocator of the stream instead of the
+    /// eventually consistent counters. This is meant for accurate snapshots, e.g. after each
+    /// training step.
+    pub fn memory_usage_settled(
+        &self,
+    ) -> impl Future<Output = Result<MemoryUsage, ServerError>> + Send + 'static {
+        let sync = self.sync();
+        let stream_id = self.stream_id();
+        let device = self.device.clone();
+
+        async move {
+            sync.await?;
+            device
+                .submit_blocking(move |server| server.memory_usage(stream_id))
+                .unwrap()
+        }
+    }
+
+    /// Hint that the memory of the given handle will soon be accessed by the device, or by the
+    /// host when `to_device` is false.
+    ///
+    /// With managed memory, this migrates the pages ahead of time instead of stalling on page
+    /// faults during the next kernel. This is a no-op on backends or memory that isn't managed.
+    pub fn prefetch(&self, handle: Handle, to_device: bool) {
+        let stream_id = self.stream_id();
+        let binding = handle.binding();
+
+        self.device
+            .submit(move |server| server.prefetch(binding, to_device, stream_id));
+    }
+
+    /// Compile the `kernels` for this device into a [`CompiledBlob`].
+    ///
+    /// Each kernel is compiled for [checked](Self::launch) and [unchecked](Self::launch_unchecked)
+    /// launches, unless it is already precompiled or cached. Backends that don't support
+    /// precompiled kernels return an empty blob.
+    pub fn compile_all(
+        &self,
+        kernels: Vec<<R::Server as ComputeServer>::Kernel>,
+    ) -> Result<CompiledBlob, LaunchError> {
+        let mut modes = vec![ExecutionMode::Checked];
+        if self.unchecked_mode() != ExecutionMode::Checked {
+            modes.push(self.unchecked_mode());
+        }
+
+        let kernels = self
+            .device
+            .submit_blocking(move |server| server.compile_binaries(kernels, modes))
+            .unwrap()?;
+
+        Ok(CompiledBlob {
+            properties_hash: self.utilities.properties_hash,
+            kernels,
+        })
+    }
+
+    /// Register the kernels of a [`CompiledBlob`], so that they are loaded instead of compiled
+    /// when launched.
+    ///
+    /// Fails when the blob was compiled for a device with different properties.
+    pub fn load_blob(&self, blob: &CompiledBlob) -> Result<(), CompilationError> {
+        if blob.properties_hash != self.utilities.properties_hash {
+            return Err(CompilationError::Generic {
+                reason: "The compiled blob was created for an incompatible device".into(),
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        let kernels = blob.kernels.clone();
+        self.device
+            .submit(move |server| server.load_compiled_kernels(kernels));
+
+        Ok(())
+    }
+
+    /// Suggest a number of units per cube (workgroup size) for the given kernel.
+    ///
+    /// Backends that can query the occupancy of the compiled kernel base the suggestion on the
+    /// resources it uses, others fall back to a heuristic based on the hardware properties.
+    ///
+    /// The cube dim is part of the kernel, so the suggestion is computed for the kernel compiled
+    /// with its current cube dim. Its resource usage, and thus the best cube size, can change once
+    /// it is compiled with the suggested size.
+    pub fn suggest_cube_size(&self, kernel: <R::Server as ComputeServer>::Kernel) -> u32 {
+        let stream_id = self.stream_id();
+        let suggested = self
+            .device
+            .submit_blocking(move |server| server.suggest_cube_size(kernel, stream_id))
+            .unwrap();
+
+        suggested.unwrap_or_else(|| {
+            let hardware = &self.utilities.properties.hardware;
+            (hardware.plane_size_max * 8)
+                .min(hardware.max_units_per_cube)
+                .max(1)
+        })
+    }
+
+    /// Bound the number of tasks of this client's stream that can be in flight on the device.
+    ///
+    /// Once more than `max_tasks` tasks may be running, the submission blocks until the oldest
+    /// ones complete, letting a fast producer be throttled to the rate of the device. This is a
+    /// no-op on backends that don't queue submissions.
+    pub fn set_max_in_flight(&self, max_tasks: usize) {
         let stream_id = self.stream_id();
+
+        self.device
+            .submit(move |server| server.set_max_in_flight(max_tasks, stream_id));
+    }
+
+    /// Bound the number of compiled pipelines kept by the device to `size`.
+    ///
+    /// Past it, the least recently used pipelines are evicted and compiled again on their next
+    /// launch, bounding the memory used by compiled code when many specialized kernels are
+    /// generated. The state kept for an evicted pipeline, such as the bind groups created for it,
+    /// is dropped with it.
+    ///
+    /// This is a no-op on backends that don't support evicting pipelines. Only WGPU does: CUDA
+    /// keeps every loaded module for the lifetime of its context, since a module can't be unloaded
+    /// while launches of its kernels may still be in flight.
+    pub fn set_pipeline_cache_size(&self, size: usize) {
+        self.device
+            .submit(move |server| server.set_pipeline_cache_size(size));
+    }
+
+    /// Get the `(hits, misses)` of the pipeline cache of the device.
+    ///
+    /// Returns `(0, 0)` on backends that don't support evicting pipelines.
+    pub fn pipeline_cache_stats(&self) -> (usize, usize) {
+        self.device
+            .submit_blocking(move |server| server.pipeline_cache_stats())
+            .unwrap()
+    }
+
+    /// Whether the `kernel` is already compiled, or will be compiled when first launched.
+    ///
+    /// The kernel counts as compiled when it was compiled for either checked or unchecked
+    /// launches. Backends that don't keep track of their compiled kernels always return `false`.
+    pub fn is_compiled(&self, kernel: &<R::Server as ComputeServer>::Kernel) -> bool {
+        let mut checked = kernel.id();
+        checked.mode(ExecutionMode::Checked);
+        let mut unchecked = checked.clone();
+        unchecked.mode(self.unchecked_mode());
+
+        self.device
+            .submit_blocking(move |server| {
+                server.is_compiled(&checked) || server.is_compiled(&unchecked)
+            })
+            .unwrap()
+    }
+
+    /// Get the `(free, total)` memory of the device in bytes, as reported by the driver.
+    ///
+    /// This is distinct from [`Self::memory_usage`], which only reflects the memory pools of
+    /// this client. Returns `None` when the backend can't report it.
+    pub fn device_memory(&self) -> Option<(u64, u64)> {
         self.device
-            .submit_blocking(move |server| server.memory_usage(stream_id))
+            .submit_blocking(move |server| server.device_memory())
             .unwrap()
     }
 
@@ -1002,6 +2784,59 @@ impl<R: Runtime> ComputeClient<R> {
         result
     }
 
+    /// Launch a kernel `iters` times and return the duration of each launch.
+    ///
+    /// A few warmup launches are run and discarded first, so the samples don't include the
+    /// compilation of the kernel. Each sample is [profiled](Self::profile) separately, so they can
+    /// be used for custom statistics and outlier filtering. Since a kernel is consumed by its
+    /// launch, `kernel` creates a new one for every launch.
+    pub fn benchmark(
+        &self,
+        kernel: impl Fn() -> <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+        iters: usize,
+    ) -> Result<Vec<core::time::Duration>, ProfileError> {
+        let num_warmup = 3;
+
+        for _ in 0..num_warmup {
+            self.launch(kernel(), count.clone(), bindings.clone());
+        }
+        cubecl_common::future::block_on(self.sync()).map_err(Box::new)?;
+
+        let mut durations = Vec::with_capacity(iters);
+        for _ in 0..iters {
+            let (kernel, count, bindings) = (kernel(), count.clone(), bindings.clone());
+            let name = kernel.name();
+            let client = self.clone();
+            let (_, duration) =
+                self.profile(move || client.launch(kernel, count, bindings), name)?;
+            let ticks = cubecl_common::future::block_on(duration.resolve());
+            durations.push(ticks.duration());
+        }
+
+        Ok(durations)
+    }
+
+    /// Launch the `kernel` and record its device time in the `profiler` under the `label`.
+    ///
+    /// The launch is [profiled](Self::profile), so this waits for the kernel to complete.
+    pub fn launch_profiled(
+        &self,
+        profiler: &Profiler,
+        label: &str,
+        kernel: <R::Server as ComputeServer>::Kernel,
+        count: CubeCount,
+        bindings: KernelArguments,
+    ) -> Result<(), ProfileError> {
+        let client = self.clone();
+        let (_, duration) = self.profile(move || client.launch(kernel, count, bindings), label)?;
+        let ticks = cubecl_common::future::block_on(duration.resolve());
+        profiler.record(label, ticks.duration());
+
+        Ok(())
+    }
+
     /// Transfer data from one client to another
     #[cfg_attr(
         feature = "tracing",
@@ -1041,14 +2876,91 @@ impl<R: Runtime> ComputeClient<R> {
         };
 
         let (size, memory) = (handle_base.size(), handle_base.memory);
-        dst_server.device.submit(move |server| {
-            server.initialize_memory(memory, size, stream_id);
-            server.write(vec![(desc_descriptor, data.remove(0))], stream_id)
-        });
+        let descriptors = vec![(desc_descriptor, data.remove(0))];
+        let guard = dst_server.track_write(&descriptors);
+        dst_server
+            .submit_reservation(false, move |server| {
+                server.initialize_memory(memory, size, stream_id)?;
+                server.write(descriptors, stream_id);
+                core::mem::drop(guard);
+                Ok(())
+            })
+            .unwrap();
 
         alloc
     }
 
+    /// Writes the content of the given handles to `writer`, so they can be restored later with
+    /// [`Self::import`].
+    ///
+    /// The format is a small header, the number of buffers, then for each buffer its size in
+    /// bytes followed by its content. All integers are little-endian `u64`.
+    #[cfg(feature = "std")]
+    pub fn export(
+        &self,
+        handles: Vec<Handle>,
+        mut writer: impl std::io::Write,
+    ) -> std::io::Result<()> {
+        let data = cubecl_common::reader::read_sync(self.read_async(handles))
+            .map_err(|err| std::io::Error::other(format!("{err:?}")))?;
+
+        writer.write_all(EXPORT_MAGIC)?;
+        writer.write_all(&(data.len() as u64).to_le_bytes())?;
+        for bytes in data.iter() {
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            writer.write_all(bytes)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads buffers written by [`Self::export`] from `reader`, and returns a handle for each of
+    /// them in the same order.
+    ///
+    /// Fails with [`std::io::ErrorKind::InvalidData`] if the data isn't in the export format, and
+    /// with [`std::io::ErrorKind::UnexpectedEof`] if the data is truncated.
+    #[cfg(feature = "std")]
+    pub fn import(&self, mut reader: impl std::io::Read) -> std::io::Result<Vec<Handle>> {
+        use std::io::Read;
+
+        fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+            let mut buffer = [0u8; 8];
+            reader.read_exact(&mut buffer)?;
+            Ok(u64::from_le_bytes(buffer))
+        }
+
+        let mut magic = [0u8; EXPORT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != EXPORT_MAGIC {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not a CubeCL buffer export",
+            ));
+        }
+
+        let num_buffers = read_u64(&mut reader)?;
+        let mut data = Vec::new();
+        for _ in 0..num_buffers {
+            let size = read_u64(&mut reader)?;
+            let mut bytes = Vec::new();
+            // Don't trust the size to pre-allocate, so a corrupted size errors instead of
+            // trying to allocate a giant buffer.
+            let read = reader.by_ref().take(size).read_to_end(&mut bytes)?;
+            if read as u64 != size {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!("Expected a buffer of {size} bytes, got {read} bytes"),
+                ));
+            }
+            data.push(bytes);
+        }
+
+        Ok(data
+            .iter()
+            .map(|bytes| self.create_from_slice(bytes))
+            .collect())
+    }
+
     /// Returns all vector sizes that are useful to perform optimal IO operation on the given element.
     pub fn io_optimized_vector_sizes(
         &self,
@@ -1065,3 +2977,22 @@ impl<R: Runtime> ComputeClient<R> {
         (0..num_candidates).map(|i| 2usize.pow(i)).rev()
     }
 }
+
+#[cfg(feature = "storage-bytes")]
+impl<R: Runtime> ComputeClient<R>
+where
+    R::Server: ComputeServer<Storage = crate::storage::BytesStorage>,
+{
+    /// Synchronously access the contents of the `handle`, for backends storing their buffers in
+    /// host memory like the CPU backend.
+    ///
+    /// Waits on the work using the handle, then gives zero-copy access to its region of the
+    /// underlying allocation through [`as_slice`](ManagedResource::as_slice), without the round
+    /// trip of a [read](Self::read_one).
+    pub fn read_sync(
+        &self,
+        handle: Handle,
+    ) -> Result<ManagedResource<crate::storage::BytesResource>, ServerError> {
+        self.get_resource(handle)
+    }
+}
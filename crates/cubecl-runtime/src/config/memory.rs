@@ -9,6 +9,13 @@ pub struct MemoryConfig {
     /// Configuration for persistent memory pools.
     #[serde(default)]
     pub persistent_memory: PersistentMemory,
+    /// The maximum number of chunks each memory management instance can reserve in the
+    /// storage, for drivers limiting the number of separate allocations.
+    ///
+    /// Once reached, unused chunks are freed to make room for new ones, and reservations that
+    /// still need a new chunk fail. Unlimited by default.
+    #[serde(default)]
+    pub max_chunks: Option<usize>,
 }
 
 /// Configuration options for persistent memory pools in `CubeCL` runtimes.
@@ -0,0 +1,104 @@
+use crate::{
+    server::{Binding, Handle},
+    transient::{TransientAllocations, TransientExpiry},
+};
+use alloc::vec::Vec;
+use cubecl_common::stream_id::StreamId;
+use hashbrown::HashMap;
+
+/// The id of a generation of allocations, see
+/// [`begin_generation`](crate::client::ComputeClient::begin_generation).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GenerationId {
+    value: u64,
+}
+
+/// The allocations of each generation that hasn't been freed yet.
+///
+/// The allocations made on a stream while one of its generations is current are tagged with it,
+/// and released together when it is freed.
+#[derive(Debug, Default)]
+pub struct Generations {
+    state: spin::Mutex<GenerationState>,
+}
+
+#[derive(Debug, Default)]
+struct GenerationState {
+    next: u64,
+    current: HashMap<StreamId, GenerationId>,
+    allocations: HashMap<GenerationId, TransientAllocations>,
+}
+
+impl Generations {
+    /// Start a new generation, tagging the allocations made on the `stream` from now on.
+    pub(crate) fn begin(&self, stream: StreamId) -> GenerationId {
+        let mut state = self.state.lock();
+        let id = GenerationId { value: state.next };
+        state.next += 1;
+        state.current.insert(stream, id);
+        state
+            .allocations
+            .insert(id, TransientAllocations::default());
+
+        id
+    }
+
+    /// Tag the allocation of the `base` handle with the current generation of its stream,
+    /// returning the expiry of its handles. Returns `None` when no generation is current.
+    pub(crate) fn register(&self, base: &Handle) -> Option<TransientExpiry> {
+        let state = self.state.lock();
+        let current = *state.current.get(&base.stream)?;
+
+        Some(state.allocations[&current].register(base.clone().binding()))
+    }
+
+    /// Take the bindings of the allocations of the generation and expire their handles.
+    ///
+    /// The generation stops being current, and nothing is returned when it was already freed.
+    pub(crate) fn take(&self, id: GenerationId) -> Vec<Binding> {
+        let mut state = self.state.lock();
+        state.current.retain(|_, current| *current != id);
+
+        match state.allocations.remove(&id) {
+            Some(allocations) => allocations.take(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STREAM: StreamId = StreamId { value: 0 };
+
+    fn handle() -> Handle {
+        Handle::new(STREAM, 16)
+    }
+
+    #[test_log::test]
+    fn allocations_are_tagged_with_the_current_generation() {
+        let generations = Generations::default();
+        assert!(generations.register(&handle()).is_none());
+
+        let first = generations.begin(STREAM);
+        let expiry = generations.register(&handle()).unwrap();
+        let second = generations.begin(STREAM);
+        generations.register(&handle()).unwrap();
+        generations.register(&handle()).unwrap();
+
+        assert_eq!(generations.take(first).len(), 1);
+        assert!(expiry.is_expired());
+        assert!(generations.take(first).is_empty());
+        assert_eq!(generations.take(second).len(), 2);
+        assert!(generations.register(&handle()).is_none());
+    }
+
+    #[test_log::test]
+    fn generations_are_current_on_their_stream_only() {
+        let generations = Generations::default();
+        generations.begin(StreamId { value: 1 });
+
+        assert!(generations.register(&handle()).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{client::ComputeClient, runtime::Runtime, server::Handle};
+use alloc::{sync::Arc, vec::Vec};
+use core::ops::Deref;
+
+/// A recycler of fixed-size [handles](Handle) created with [`ComputeClient::empty`].
+///
+/// Handles acquired from the pool are returned to its free list when dropped, so steady-state
+/// workloads that repeatedly allocate the same intermediate buffer don't go through the memory
+/// pool at all. At most `capacity` handles are kept around.
+///
+/// # Notes
+///
+/// A recycled handle keeps its previous content. Reusing it is ordered with the previous work
+/// on the same stream, like any other handle.
+///
+/// Bindings passed to launches don't reference the handle, so handles used by kernels still in
+/// flight are recycled. A handle that still has clones alive when the pooled handle is dropped is
+/// released to the memory pool instead of being recycled.
+pub struct HandlePool<R: Runtime> {
+    client: ComputeClient<R>,
+    size: usize,
+    free: Arc<FreeList>,
+}
+
+/// A [handle](Handle) acquired from a [`HandlePool`], returned to the pool when dropped.
+pub struct PooledHandle {
+    handle: Option<Handle>,
+    free: Arc<FreeList>,
+}
+
+struct FreeList {
+    handles: spin::Mutex<Vec<Handle>>,
+    capacity: usize,
+}
+
+impl<R: Runtime> HandlePool<R> {
+    /// Creates a new pool of handles of `size` bytes, keeping at most `capacity` free handles.
+    pub fn new(client: ComputeClient<R>, size: usize, capacity: usize) -> Self {
+        Self {
+            client,
+            size,
+            free: Arc::new(FreeList {
+                handles: spin::Mutex::new(Vec::with_capacity(capacity)),
+                capacity,
+            }),
+        }
+    }
+
+    /// Acquires a handle from the free list, or creates a new one when it is empty.
+    pub fn acquire(&self) -> PooledHandle {
+        let handle = self.free.handles.lock().pop();
+        let handle = handle.unwrap_or_else(|| self.client.empty(self.size));
+
+        PooledHandle {
+            handle: Some(handle),
+            free: self.free.clone(),
+        }
+    }
+
+    /// The size of the handles in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of handles ready to be acquired.
+    pub fn available(&self) -> usize {
+        self.free.handles.lock().len()
+    }
+}
+
+impl PooledHandle {
+    /// Detaches the handle from its pool, so it isn't recycled when dropped.
+    pub fn into_handle(mut self) -> Handle {
+        self.handle.take().unwrap()
+    }
+}
+
+impl Deref for PooledHandle {
+    type Target = Handle;
+
+    fn deref(&self) -> &Self::Target {
+        self.handle.as_ref().unwrap()
+    }
+}
+
+impl core::fmt::Debug for PooledHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PooledHandle")
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl Drop for PooledHandle {
+    fn drop(&mut self) {
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+
+        // A handle still cloned elsewhere can't be handed out again, it is freed when the last
+        // clone is dropped.
+        if !handle.can_mut() {
+            return;
+        }
+
+        let mut handles = self.free.handles.lock();
+        if handles.len() < self.free.capacity {
+            handles.push(handle);
+        }
+    }
+}
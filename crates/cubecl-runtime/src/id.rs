@@ -1,6 +1,7 @@
 use alloc::format;
 use alloc::string::String;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::{
     any::{Any, TypeId},
     fmt::Display,
@@ -10,7 +11,7 @@ use cubecl_common::{
     format::{DebugRaw, format_str},
     hash::{StableHash, StableHasher},
 };
-use cubecl_ir::AddressType;
+use cubecl_ir::{AddressType, ConstantValue};
 use derive_more::{Eq, PartialEq};
 
 use crate::server::{CubeDim, ExecutionMode};
@@ -59,6 +60,7 @@ pub struct KernelId {
     pub cube_dim: CubeDim,
     pub(crate) mode: ExecutionMode,
     pub(crate) info: Option<Info>,
+    pub(crate) spec_constants: Vec<ConstantValue>,
 }
 
 impl Hash for KernelId {
@@ -68,6 +70,7 @@ impl Hash for KernelId {
         self.cube_dim.hash(state);
         self.mode.hash(state);
         self.info.hash(state);
+        self.spec_constants.hash(state);
     }
 }
 
@@ -83,6 +86,9 @@ impl core::fmt::Debug for KernelId {
             Some(info) => debug_str.field("info", info),
             None => debug_str.field("info", &self.info),
         };
+        if !self.spec_constants.is_empty() {
+            debug_str.field("spec_constants", &self.spec_constants);
+        }
         debug_str.finish()
     }
 }
@@ -113,6 +119,7 @@ impl KernelId {
             cube_dim: CubeDim::new_single(),
             mode: ExecutionMode::Checked,
             address_type: Default::default(),
+            spec_constants: Vec::new(),
         }
     }
 
@@ -120,10 +127,14 @@ impl KernelId {
     ///
     /// Can be used as a persistent kernel cache key.
     pub fn stable_format(&self) -> String {
-        format!(
+        let format = format!(
             "{}-{}-{:?}-{:?}-{:?}",
             self.type_name, self.address_type, self.cube_dim, self.mode, self.info
-        )
+        );
+        match self.spec_constants.is_empty() {
+            true => format,
+            false => format!("{format}-{:?}", self.spec_constants),
+        }
     }
 
     /// Hash the key in a stable way that can be used between runs.
@@ -136,6 +147,10 @@ impl KernelId {
         self.cube_dim.hash(&mut hasher);
         self.mode.hash(&mut hasher);
         self.info.hash(&mut hasher);
+        // Only hashed when set, so the keys of kernels without them stay the same.
+        if !self.spec_constants.is_empty() {
+            self.spec_constants.hash(&mut hasher);
+        }
 
         hasher.finalize()
     }
@@ -152,6 +167,20 @@ impl KernelId {
         self
     }
 
+    /// Set the values of the specialization constants of the kernel.
+    ///
+    /// Backends that specialize kernels when they are compiled use it to cache a compiled kernel
+    /// for each set of values.
+    pub fn spec_constants(mut self, values: &[ConstantValue]) -> Self {
+        self.spec_constants = values.to_vec();
+        self
+    }
+
+    /// The values of the specialization constants of the kernel.
+    pub fn spec_constant_values(&self) -> &[ConstantValue] {
+        &self.spec_constants
+    }
+
     /// Set the [execution mode](ExecutionMode).
     pub fn mode(&mut self, mode: ExecutionMode) {
         self.mode = mode;
@@ -0,0 +1,62 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The bytes of the transfers submitted to a device that haven't completed yet.
+///
+/// Reads count until their future resolves or is dropped, writes until the server has taken
+/// their data. Unlike the [memory usage](crate::memory_management::MemoryUsage), which covers
+/// device resident memory, this only covers transient transfer memory, so it can be polled to
+/// throttle uploads and downloads before exhausting staging memory.
+#[derive(Debug, Default)]
+pub struct InFlightBytes {
+    bytes: AtomicUsize,
+}
+
+impl InFlightBytes {
+    /// The number of bytes currently in flight.
+    pub fn load(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Count `bytes` as in flight until the returned guard is dropped.
+    pub(crate) fn track(self: &Arc<Self>, bytes: usize) -> InFlightGuard {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        InFlightGuard {
+            counter: self.clone(),
+            bytes,
+        }
+    }
+}
+
+/// Keeps bytes [in flight](InFlightBytes) until it is dropped.
+pub(crate) struct InFlightGuard {
+    counter: Arc<InFlightBytes>,
+    bytes: usize,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.counter.bytes.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_are_released_when_guards_are_dropped() {
+        let in_flight = Arc::new(InFlightBytes::default());
+
+        let read = in_flight.track(64);
+        let write = in_flight.track(16);
+        assert_eq!(in_flight.load(), 80);
+
+        core::mem::drop(read);
+        assert_eq!(in_flight.load(), 16);
+
+        core::mem::drop(write);
+        assert_eq!(in_flight.load(), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use alloc::{
     boxed::Box,
     string::{String, ToString},
+    sync::Arc,
     vec::Vec,
 };
 use core::{
@@ -9,7 +10,7 @@ use core::{
     sync::atomic::{AtomicI8, Ordering},
 };
 
-use cubecl_common::format::format_str;
+use cubecl_common::{format::format_str, hash::StableHash};
 use cubecl_ir::{Id, Scope, StorageType, Type};
 use serde::{Deserialize, Serialize};
 
@@ -32,19 +33,102 @@ pub trait KernelMetadata: Send + Sync + 'static {
 
     /// Type of addresses in this kernel
     fn address_type(&self) -> StorageType;
+
+    /// The host implementation of the kernel, used to [verify](crate::verify::VerifyMode) the
+    /// results of the device.
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        None
+    }
+
+    /// The [layout](KernelLayout) of the arguments the kernel expects, to build or validate its
+    /// bindings without compiling it.
+    ///
+    /// Returns `None` for kernels without a [definition](KernelDefinition), e.g. precompiled
+    /// kernels.
+    fn layout(&self) -> Option<KernelLayout> {
+        None
+    }
+}
+
+/// Host implementation of a kernel, used as a reference to [verify](crate::verify::VerifyMode)
+/// the results of the device.
+pub trait CpuReference: Send + Sync {
+    /// Run the kernel on the host for the whole `cube_count`.
+    ///
+    /// The `buffers` hold the bytes of the buffer bindings of the launch in binding order, and
+    /// are updated in place. The `info` holds the packed scalars and metadata of the launch.
+    fn execute(&self, cube_count: [u32; 3], buffers: &mut [Vec<u8>], info: &[u64]);
 }
 
 #[derive(Debug, Clone)]
 #[allow(missing_docs)]
 pub struct KernelDefinition {
     pub buffers: Vec<KernelArg>,
+    /// Uniform buffers, bound after the storage buffers and indexed by their id. Their length is
+    /// fixed at compilation and stored in [`KernelArg::size`].
+    pub uniforms: Vec<KernelArg>,
     pub tensor_maps: Vec<KernelArg>,
     pub scalars: Vec<ScalarKernelArg>,
+    /// Types of the specialization constants, indexed by their id. Their values are given at
+    /// launch by [`KernelArguments::spec_constants`](crate::server::KernelArguments::spec_constants).
+    pub spec_constants: Vec<StorageType>,
     pub cube_dim: CubeDim,
     pub body: Scope,
     pub options: KernelOptions,
 }
 
+impl KernelDefinition {
+    /// The [layout](KernelLayout) of the arguments of the kernel.
+    pub fn layout(&self) -> KernelLayout {
+        let args = |args: &[KernelArg]| {
+            args.iter()
+                .map(|arg| ArgumentLayout {
+                    id: arg.id,
+                    elem_size: match arg.ty.is_semantic() {
+                        true => 0,
+                        false => arg.ty.storage_type().size(),
+                    },
+                    access: arg.visibility,
+                })
+                .collect()
+        };
+
+        KernelLayout {
+            buffers: args(&self.buffers),
+            uniforms: args(&self.uniforms),
+            tensor_maps: args(&self.tensor_maps),
+            scalars: self.scalars.clone(),
+        }
+    }
+}
+
+/// The arguments a kernel expects, in binding order.
+///
+/// It matches the fields of the [`KernelArguments`](crate::server::KernelArguments) the kernel
+/// must be launched with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KernelLayout {
+    /// The storage buffers.
+    pub buffers: Vec<ArgumentLayout>,
+    /// The uniform buffers, bound after the storage buffers.
+    pub uniforms: Vec<ArgumentLayout>,
+    /// The buffers backing the tensor maps.
+    pub tensor_maps: Vec<ArgumentLayout>,
+    /// The scalars, grouped by type.
+    pub scalars: Vec<ScalarKernelArg>,
+}
+
+/// A buffer argument of a [kernel layout](KernelLayout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgumentLayout {
+    /// The id of the argument in the kernel.
+    pub id: Id,
+    /// Size in bytes of the elements of the buffer, zero for semantic types.
+    pub elem_size: usize,
+    /// Whether the kernel only reads the buffer, or can also write to it.
+    pub access: Visibility,
+}
+
 #[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
 /// Options for a specific kernel compilation
 pub struct KernelOptions {
@@ -124,6 +208,10 @@ pub struct CompiledKernel<C: Compiler> {
     pub repr: Option<C::Representation>,
     /// Size of a cube for the compiled kernel
     pub cube_dim: CubeDim,
+    /// Size in bytes of the elements of each buffer argument, in binding order.
+    ///
+    /// Empty when the sizes aren't known.
+    pub buffer_elem_sizes: Vec<usize>,
     /// Extra debugging information about the compiled kernel.
     pub debug_info: Option<DebugInformation>,
 }
@@ -137,6 +225,73 @@ pub struct DebugInformation {
     pub id: KernelId,
 }
 
+/// A function rewriting the source of a kernel.
+pub type SourceTransformFn = dyn Fn(&str) -> String + Send + Sync;
+
+/// The rewrite applied to the source of the kernels of a device before the backend compiles
+/// them, see [`ComputeClient::set_source_transform`](crate::client::ComputeClient::set_source_transform).
+#[derive(Default)]
+pub struct SourceTransform {
+    transform: spin::Mutex<Option<Arc<SourceTransformFn>>>,
+}
+
+impl core::fmt::Debug for SourceTransform {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SourceTransform")
+            .field("enabled", &self.transform.lock().is_some())
+            .finish()
+    }
+}
+
+impl SourceTransform {
+    pub(crate) fn set(&self, transform: Option<Arc<SourceTransformFn>>) {
+        *self.transform.lock() = transform;
+    }
+
+    /// Rewrite the source of the `kernel`, which is left as is without a transform.
+    ///
+    /// Returns whether the source was rewritten, in which case the compiled kernel doesn't match
+    /// its id anymore and shouldn't be saved to a persistent cache.
+    pub fn apply<C: Compiler>(&self, kernel: &mut CompiledKernel<C>) -> bool {
+        // The transform is called without the lock, in case it takes a while.
+        let transform = self.transform.lock().clone();
+
+        match transform {
+            Some(transform) => {
+                kernel.source = transform(&kernel.source);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A kernel compiled to the binary format of the backend, e.g. PTX for CUDA.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelBinary {
+    /// The [stable hash](KernelId::stable_hash) of the kernel id.
+    pub id: StableHash,
+    /// The name of the kernel entrypoint.
+    pub entrypoint_name: String,
+    /// The amount of shared memory used by the kernel in bytes.
+    pub shared_mem_bytes: usize,
+    /// The [element sizes](CompiledKernel::buffer_elem_sizes) of the buffers of the kernel.
+    pub buffer_elem_sizes: Vec<usize>,
+    /// The compiled kernel.
+    pub binary: Vec<u8>,
+}
+
+/// A serializable set of compiled kernels, to ship precompiled kernels instead of compiling
+/// them when the application starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledBlob {
+    /// The [checksum](cubecl_ir::DeviceProperties::checksum) of the properties of the device the
+    /// kernels were compiled for.
+    pub properties_hash: u64,
+    /// The compiled kernels.
+    pub kernels: Vec<KernelBinary>,
+}
+
 /// Kernel that can be defined
 pub trait CubeKernel: KernelMetadata {
     /// Define the kernel for compilation
@@ -176,6 +331,12 @@ impl<C: Compiler, K: CubeKernel> CubeTask<C> for KernelTask<C, K> {
         let gpu_ir = self.kernel_definition.define();
         let entrypoint_name = gpu_ir.options.kernel_name.clone();
         let cube_dim = gpu_ir.cube_dim;
+        let buffer_elem_sizes = gpu_ir
+            .layout()
+            .buffers
+            .iter()
+            .map(|arg| arg.elem_size)
+            .collect();
         let lower_level_ir = compiler.compile(gpu_ir, compilation_options, mode, addr_type)?;
 
         Ok(CompiledKernel {
@@ -184,6 +345,7 @@ impl<C: Compiler, K: CubeKernel> CubeTask<C> for KernelTask<C, K> {
             source: lower_level_ir.to_string(),
             repr: Some(lower_level_ir),
             cube_dim,
+            buffer_elem_sizes,
             debug_info: None,
         })
     }
@@ -203,6 +365,14 @@ impl<C: Compiler, K: CubeKernel> KernelMetadata for KernelTask<C, K> {
     fn address_type(&self) -> StorageType {
         self.kernel_definition.address_type()
     }
+
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        self.kernel_definition.cpu_reference()
+    }
+
+    fn layout(&self) -> Option<KernelLayout> {
+        Some(self.kernel_definition.define().layout())
+    }
 }
 
 impl<C: Compiler> KernelMetadata for Box<dyn CubeTask<C>> {
@@ -219,6 +389,14 @@ impl<C: Compiler> KernelMetadata for Box<dyn CubeTask<C>> {
     fn address_type(&self) -> StorageType {
         self.as_ref().address_type()
     }
+
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        self.as_ref().cpu_reference()
+    }
+
+    fn layout(&self) -> Option<KernelLayout> {
+        self.as_ref().layout()
+    }
 }
 
 static COMPILATION_LEVEL: AtomicI8 = AtomicI8::new(-1);
@@ -294,3 +472,122 @@ source:
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use cubecl_common::backtrace::BackTrace;
+    use cubecl_ir::{ElemType, FloatKind, UIntKind};
+
+    fn buffer(id: Id, elem: ElemType, visibility: Visibility) -> KernelArg {
+        KernelArg {
+            id,
+            visibility,
+            ty: Type::scalar(elem),
+            size: None,
+            has_extended_meta: false,
+        }
+    }
+
+    #[test]
+    fn layout_lists_buffers_in_binding_order() {
+        let definition = KernelDefinition {
+            buffers: vec![
+                buffer(0, ElemType::Float(FloatKind::F32), Visibility::Read),
+                buffer(1, ElemType::UInt(UIntKind::U16), Visibility::ReadWrite),
+            ],
+            uniforms: vec![],
+            tensor_maps: vec![],
+            scalars: vec![ScalarKernelArg {
+                ty: ElemType::UInt(UIntKind::U32).into(),
+                count: 2,
+            }],
+            spec_constants: vec![],
+            cube_dim: CubeDim::new_1d(1),
+            body: Scope::root(false),
+            options: KernelOptions::default(),
+        };
+
+        let layout = definition.layout();
+
+        assert_eq!(
+            layout.buffers,
+            [
+                ArgumentLayout {
+                    id: 0,
+                    elem_size: 4,
+                    access: Visibility::Read,
+                },
+                ArgumentLayout {
+                    id: 1,
+                    elem_size: 2,
+                    access: Visibility::ReadWrite,
+                },
+            ]
+        );
+        assert_eq!(layout.scalars, definition.scalars);
+    }
+
+    #[derive(Debug, Clone)]
+    struct SourceCompiler;
+
+    impl Compiler for SourceCompiler {
+        type Representation = String;
+        type CompilationOptions = ();
+
+        fn compile(
+            &mut self,
+            _kernel: KernelDefinition,
+            _compilation_options: &Self::CompilationOptions,
+            _mode: ExecutionMode,
+            _addr_type: StorageType,
+        ) -> Result<Self::Representation, CompilationError> {
+            Err(CompilationError::Generic {
+                reason: "The test compiler only holds sources".into(),
+                backtrace: BackTrace::capture(),
+            })
+        }
+
+        fn elem_size(&self, elem: ElemType) -> usize {
+            elem.size()
+        }
+
+        fn extension(&self) -> &'static str {
+            "txt"
+        }
+    }
+
+    fn compiled(source: &str) -> CompiledKernel<SourceCompiler> {
+        CompiledKernel {
+            entrypoint_name: "main".into(),
+            debug_name: None,
+            source: source.into(),
+            repr: None,
+            cube_dim: CubeDim::new_1d(1),
+            buffer_elem_sizes: vec![],
+            debug_info: None,
+        }
+    }
+
+    #[test]
+    fn source_transform_rewrites_the_source_until_cleared() {
+        let transform = SourceTransform::default();
+
+        let mut kernel = compiled("body");
+        assert!(!transform.apply(&mut kernel));
+        assert_eq!(kernel.source, "body");
+
+        transform.set(Some(Arc::new(|source: &str| {
+            alloc::format!("#pragma unroll\n{source}")
+        })));
+        let mut kernel = compiled("body");
+        assert!(transform.apply(&mut kernel));
+        assert_eq!(kernel.source, "#pragma unroll\nbody");
+
+        transform.set(None);
+        let mut kernel = compiled("body");
+        assert!(!transform.apply(&mut kernel));
+        assert_eq!(kernel.source, "body");
+    }
+}
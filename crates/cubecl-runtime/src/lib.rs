@@ -23,6 +23,9 @@ pub mod stream;
 /// Compute client module.
 pub mod client;
 
+/// Recycler of fixed-size handles.
+pub mod handle_pool;
+
 /// Autotune module
 pub mod tune;
 
@@ -54,5 +57,47 @@ pub mod timestamp_profiler;
 /// Validation utils for shared properties
 pub mod validation;
 
+/// Verification of kernels against their CPU reference.
+pub mod verify;
+
+/// Buffers written through a host copy that is uploaded on demand.
+pub mod staged;
+
+/// Cancellation of in-flight reads.
+pub mod cancel;
+
+/// Tracking of the bytes of in-flight transfers.
+pub mod in_flight;
+
+/// Allocations released at the next synchronization.
+pub mod transient;
+
+/// Specialized pipelines of kernels by problem shape.
+pub mod shape_bucket;
+
+/// Stack allocations that can be rolled back.
+pub mod arena;
+
+/// Paths taken by reads to the host.
+pub mod read_path;
+
+/// Reads of device buffers in chunks, as an asynchronous stream.
+pub mod read_stream;
+
+/// Out of core processing of host data, one tile at a time.
+pub mod stream_processor;
+
+/// Device time of launches accumulated by label.
+pub mod profiler;
+
+/// Spilling of allocations to host memory under memory pressure.
+pub mod spill;
+
+/// Host waits on individual submissions.
+pub mod submission;
+
+/// Allocations freed together, such as the buffers of a frame.
+pub mod generation;
+
 /// Allocators moddule.
 pub mod allocator;
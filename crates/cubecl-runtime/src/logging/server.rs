@@ -197,6 +197,12 @@ impl AsyncLogger {
                 }
                 LogMessage::Profile(name, profile) => {
                     let duration = profile.resolve().await.duration();
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(
+                        kernel.name = %name,
+                        gpu_time_ns = duration.as_nanos() as u64,
+                        "Kernel profiled"
+                    );
                     self.profiled.update(&name, duration);
                     self.logger
                         .log_profiling(&format!("| {duration:<10?} | {name}"));
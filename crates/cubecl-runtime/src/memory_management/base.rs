@@ -1,9 +1,12 @@
+use crate::storage::StorageId;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
 
 /// Amount of memory in use by this allocator
 /// and statistics on how much memory is reserved and
 /// wasted in total.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct MemoryUsage {
     /// The number of allocations currently active.
     ///
@@ -24,6 +27,11 @@ pub struct MemoryUsage {
     /// be higher, as allocations reserve memory for future allocations
     /// and for padding.
     pub bytes_reserved: u64,
+    /// The number of chunks currently reserved on the device.
+    ///
+    /// Each chunk is a separate allocation of the storage, e.g. a driver allocation, holding
+    /// one or many slices.
+    pub number_chunks: u64,
 }
 
 impl MemoryUsage {
@@ -34,6 +42,93 @@ impl MemoryUsage {
             bytes_in_use: self.bytes_in_use + other.bytes_in_use,
             bytes_padding: self.bytes_padding + other.bytes_padding,
             bytes_reserved: self.bytes_reserved + other.bytes_reserved,
+            number_chunks: self.number_chunks + other.number_chunks,
+        }
+    }
+}
+
+/// The layout of a chunk of memory, as tracked by its memory pool.
+///
+/// Ranges are `(offset, size)` pairs in bytes, sorted by offset. Allocated ranges include their
+/// padding. Contiguous free ranges are listed separately until the pool merges them, which
+/// helps to understand why a large allocation doesn't fit in apparently free space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkInfo {
+    /// The storage of the chunk.
+    pub id: StorageId,
+    /// The size of the chunk in bytes.
+    pub total: u64,
+    /// The ranges free to be reserved.
+    pub free_ranges: Vec<(u64, u64)>,
+    /// The ranges reserved by allocations.
+    pub alloc_ranges: Vec<(u64, u64)>,
+}
+
+impl ChunkInfo {
+    /// Create the layout of an empty chunk of `total` bytes.
+    pub(crate) fn new(id: StorageId, total: u64) -> Self {
+        Self {
+            id,
+            total,
+            free_ranges: Vec::new(),
+            alloc_ranges: Vec::new(),
+        }
+    }
+
+    /// Add the range of `size` bytes at `offset`.
+    pub(crate) fn push(&mut self, offset: u64, size: u64, is_free: bool) {
+        match is_free {
+            true => self.free_ranges.push((offset, size)),
+            false => self.alloc_ranges.push((offset, size)),
+        }
+    }
+}
+
+/// Lock-free counters tracking the [`MemoryUsage`] of a device.
+///
+/// The counters are updated by [memory management](super::MemoryManagement) when it allocates
+/// or frees storage, and periodically when reserving memory so that freed slices are eventually
+/// reflected. Many memory management instances, e.g. one per stream, can share the same
+/// snapshot, which then tracks their combined usage.
+///
+/// Reading the counters never contends with the allocator, but the values are eventually
+/// consistent and each field is read independently, which makes them suitable for metrics and
+/// dashboards, not for allocation decisions.
+#[derive(Debug, Default)]
+pub struct MemoryUsageSnapshot {
+    number_allocs: AtomicU64,
+    bytes_in_use: AtomicU64,
+    bytes_padding: AtomicU64,
+    bytes_reserved: AtomicU64,
+    number_chunks: AtomicU64,
+}
+
+impl MemoryUsageSnapshot {
+    /// Replace the contribution `old` of a memory management instance with `new`.
+    pub fn update(&self, old: &MemoryUsage, new: &MemoryUsage) {
+        fn apply(counter: &AtomicU64, old: u64, new: u64) {
+            if new >= old {
+                counter.fetch_add(new - old, Ordering::Relaxed);
+            } else {
+                counter.fetch_sub(old - new, Ordering::Relaxed);
+            }
+        }
+
+        apply(&self.number_allocs, old.number_allocs, new.number_allocs);
+        apply(&self.bytes_in_use, old.bytes_in_use, new.bytes_in_use);
+        apply(&self.bytes_padding, old.bytes_padding, new.bytes_padding);
+        apply(&self.bytes_reserved, old.bytes_reserved, new.bytes_reserved);
+        apply(&self.number_chunks, old.number_chunks, new.number_chunks);
+    }
+
+    /// Read the current usage.
+    pub fn load(&self) -> MemoryUsage {
+        MemoryUsage {
+            number_allocs: self.number_allocs.load(Ordering::Relaxed),
+            bytes_in_use: self.bytes_in_use.load(Ordering::Relaxed),
+            bytes_padding: self.bytes_padding.load(Ordering::Relaxed),
+            bytes_reserved: self.bytes_reserved.load(Ordering::Relaxed),
+            number_chunks: self.number_chunks.load(Ordering::Relaxed),
         }
     }
 }
@@ -88,6 +183,7 @@ impl core::fmt::Display for MemoryUsage {
             "  Total bytes reserved: {}",
             bytes_format(self.bytes_reserved)
         )?;
+        writeln!(f, "  Number of chunks: {}", self.number_chunks)?;
         writeln!(f, "  Usage efficiency: {usage_percentage:.2}%")?;
         writeln!(f, "  Padding overhead: {padding_percentage:.2}%")
     }
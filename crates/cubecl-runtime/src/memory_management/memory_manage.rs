@@ -1,6 +1,6 @@
 use super::{
-    MemoryConfiguration, MemoryPoolOptions, MemoryUsage, PoolType,
-    memory_pool::{ExclusiveMemoryPool, MemoryPool, PersistentPool, SlicedPool},
+    ChunkInfo, MemoryConfiguration, MemoryPoolOptions, MemoryUsage, PoolType, SizeRounding,
+    memory_pool::{CustomPool, ExclusiveMemoryPool, MemoryPool, PersistentPool, SlicedPool},
 };
 use crate::{
     config::{
@@ -8,7 +8,7 @@ use crate::{
         memory::{MemoryLogLevel, PersistentMemory},
     },
     logging::ServerLogger,
-    memory_management::{BytesFormat, memory_pool::Slice},
+    memory_management::{BytesFormat, MemoryUsageSnapshot, memory_pool::Slice},
     server::IoError,
     storage::{ComputeStorage, StorageHandle},
 };
@@ -29,6 +29,7 @@ pub use super::memory_pool::{ManagedMemoryBinding, handle::*};
 enum DynamicPool {
     Sliced(SlicedPool),
     Exclusive(ExclusiveMemoryPool),
+    Custom(CustomPool),
 }
 
 impl MemoryPool for DynamicPool {
@@ -36,6 +37,7 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(pool) => pool.accept(size),
             DynamicPool::Exclusive(pool) => pool.accept(size),
+            DynamicPool::Custom(pool) => pool.accept(size),
         }
     }
 
@@ -43,6 +45,7 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.find(binding),
             DynamicPool::Exclusive(m) => m.find(binding),
+            DynamicPool::Custom(m) => m.find(binding),
         }
     }
 
@@ -51,6 +54,7 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.try_reserve(size),
             DynamicPool::Exclusive(m) => m.try_reserve(size),
+            DynamicPool::Custom(m) => m.try_reserve(size),
         }
     }
 
@@ -66,6 +70,7 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.alloc(storage, size),
             DynamicPool::Exclusive(m) => m.alloc(storage, size),
+            DynamicPool::Custom(m) => m.alloc(storage, size),
         }
     }
 
@@ -73,6 +78,15 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.get_memory_usage(),
             DynamicPool::Exclusive(m) => m.get_memory_usage(),
+            DynamicPool::Custom(m) => m.get_memory_usage(),
+        }
+    }
+
+    fn chunk_map(&self) -> Vec<ChunkInfo> {
+        match self {
+            DynamicPool::Sliced(m) => m.chunk_map(),
+            DynamicPool::Exclusive(m) => m.chunk_map(),
+            DynamicPool::Custom(m) => m.chunk_map(),
         }
     }
 
@@ -85,6 +99,7 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.cleanup(storage, alloc_nr, explicit),
             DynamicPool::Exclusive(m) => m.cleanup(storage, alloc_nr, explicit),
+            DynamicPool::Custom(m) => m.cleanup(storage, alloc_nr, explicit),
         };
         storage.flush();
     }
@@ -98,6 +113,19 @@ impl MemoryPool for DynamicPool {
         match self {
             DynamicPool::Sliced(m) => m.bind(reserved, assigned, cursor),
             DynamicPool::Exclusive(m) => m.bind(reserved, assigned, cursor),
+            DynamicPool::Custom(m) => m.bind(reserved, assigned, cursor),
+        }
+    }
+}
+
+impl DynamicPool {
+    /// Whether an allocation of the given size would share its page with other allocations.
+    fn shares_page(&self, size: u64) -> bool {
+        match self {
+            DynamicPool::Sliced(m) => m.shares_page(size),
+            DynamicPool::Exclusive(_) => false,
+            // Custom pools place every allocation they accept themselves.
+            DynamicPool::Custom(_) => true,
         }
     }
 }
@@ -113,18 +141,53 @@ pub enum MemoryAllocationMode {
     Persistent,
 }
 
+/// How a resource is expected to be accessed, used to bias where its memory is placed.
+///
+/// This is advisory only, memory management is free to ignore it. Only [`AccessHint::Hot`]
+/// currently changes the placement, the other hints use the default strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccessHint {
+    /// Accessed rarely.
+    Cold,
+    /// Accessed once or a few times in a row, like inputs or intermediate buffers.
+    Streaming,
+    /// Accessed by most dispatches over a long time, like model weights.
+    ///
+    /// Hot resources too big to share a page of the dynamic pools get an exact-size allocation
+    /// in persistent memory, instead of a mostly unused page that would be recycled for
+    /// short-lived buffers once freed. Smaller ones keep sharing pages with other resources.
+    Hot,
+}
+
 /// Reserves and keeps track of chunks of memory in the storage, and slices upon these chunks.
 pub struct MemoryManagement<Storage> {
     name: String,
     persistent: PersistentPool,
+    /// Chunks registered for a single allocation, which are never reused and are freed as soon
+    /// as the allocation is.
+    unshared: PersistentPool,
     pools: Vec<DynamicPool>,
     storage: Storage,
     alloc_reserve_count: u64,
     mode: MemoryAllocationMode,
     config: PersistentMemory,
     logger: Arc<ServerLogger>,
+    usage_snapshots: Vec<Arc<MemoryUsageSnapshot>>,
+    /// The usage last added to the snapshots, to only publish the changes.
+    usage_published: MemoryUsage,
+    /// Whether storage was allocated or freed since the usage was last published.
+    usage_stale: bool,
+    max_chunks: Option<usize>,
+    /// The size of the largest chunk the storage can allocate.
+    max_alloc_size: u64,
+    /// The alignment of the offsets of the slices in their chunk.
+    alignment: u64,
 }
 
+/// How many reservations can happen before the usage is published to the
+/// [snapshot](MemoryUsageSnapshot), so that freed slices are eventually reflected in it.
+const USAGE_PUBLISH_PERIOD: u64 = 64;
+
 fn generate_bucket_sizes(
     start_size: u64,
     end_size: u64,
@@ -150,6 +213,11 @@ fn generate_bucket_sizes(
     buckets
 }
 
+/// The smallest size in bytes of the allocations counted in each bucket of the
+/// [size histogram](MemoryManagement::size_histogram): below 1 KiB, up to 1 MiB, up to 100 MiB
+/// and above.
+pub const SIZE_HISTOGRAM_BUCKETS: [usize; 4] = [0, 1024, 1024 * 1024, 100 * 1024 * 1024];
+
 const DEALLOC_SCALE_MB: u64 = 1024 * 1024 * 1024;
 const BASE_DEALLOC_PERIOD: u64 = 5000;
 
@@ -160,6 +228,10 @@ pub struct MemoryManagementOptions {
     name: String,
     /// The [`MemoryAllocationOption`] used by this instance.
     memory: MemoryAllocationOption,
+    /// Where the memory usage is published.
+    usage_snapshot: Option<Arc<MemoryUsageSnapshot>>,
+    /// The maximum number of chunks, overriding the [`GlobalConfig`].
+    max_chunks: Option<usize>,
 }
 
 impl MemoryManagementOptions {
@@ -168,6 +240,8 @@ impl MemoryManagementOptions {
         Self {
             name: name.into(),
             memory: MemoryAllocationOption::FromConfig,
+            usage_snapshot: None,
+            max_chunks: None,
         }
     }
 
@@ -176,6 +250,20 @@ impl MemoryManagementOptions {
         self.memory = MemoryAllocationOption::Provided(mode);
         self
     }
+
+    /// Publishes the memory usage to the given [snapshot](MemoryUsageSnapshot), which can be
+    /// shared by many instances to track the usage of a whole device.
+    pub fn usage_snapshot(mut self, snapshot: Arc<MemoryUsageSnapshot>) -> Self {
+        self.usage_snapshot = Some(snapshot);
+        self
+    }
+
+    /// Limits the number of chunks reserved in the storage, see
+    /// [`MemoryConfig::max_chunks`](crate::config::memory::MemoryConfig::max_chunks).
+    pub fn max_chunks(mut self, max_chunks: usize) -> Self {
+        self.max_chunks = Some(max_chunks);
+        self
+    }
 }
 
 #[derive(Default, Debug)]
@@ -212,6 +300,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                 pools.push(MemoryPoolOptions {
                     pool_type: PoolType::ExclusivePages { max_alloc_size: 0 },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 });
 
                 let mut current = max_page;
@@ -244,6 +333,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                             max_slice_size: max,
                         },
                         dealloc_period: None,
+                        size_rounding: SizeRounding::default(),
                     });
                 }
 
@@ -254,6 +344,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                         max_slice_size: max_page / memory_alignment * memory_alignment,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 });
                 pools
             }
@@ -283,6 +374,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                                 max_alloc_size: size,
                             },
                             dealloc_period: Some(dealloc_period),
+                            size_rounding: SizeRounding::default(),
                         }
                     })
                     .collect()
@@ -307,29 +399,36 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             .map(|(pool_pos, options)| {
                 let pool_pos = pool_pos as u8;
 
-                match options.pool_type {
+                match &options.pool_type {
                     PoolType::SlicedPages {
                         page_size,
                         max_slice_size,
                     } => DynamicPool::Sliced(SlicedPool::new(
-                        page_size,
-                        max_slice_size,
+                        *page_size,
+                        *max_slice_size,
                         properties.alignment,
+                        options.size_rounding,
                         pool_pos,
                     )),
                     PoolType::ExclusivePages { max_alloc_size } => {
                         DynamicPool::Exclusive(ExclusiveMemoryPool::new(
-                            max_alloc_size,
+                            *max_alloc_size,
                             properties.alignment,
+                            options.size_rounding,
                             options.dealloc_period.unwrap_or(u64::MAX),
                             pool_pos,
                         ))
                     }
+                    PoolType::Custom(factory) => {
+                        DynamicPool::Custom(CustomPool::new(factory.create(), pool_pos))
+                    }
                 }
             })
             .collect();
 
-        let config = GlobalConfig::get().memory.persistent_memory.clone();
+        let memory_config = &GlobalConfig::get().memory;
+        let config = memory_config.persistent_memory.clone();
+        let max_chunks = options.max_chunks.or(memory_config.max_chunks);
 
         let mode = match options.memory {
             MemoryAllocationOption::Provided(mode) => mode,
@@ -347,12 +446,23 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                 properties.alignment,
                 pools.len() as u8,
             ),
+            unshared: PersistentPool::new(
+                properties.max_page_size,
+                properties.alignment,
+                pools.len() as u8 + 1,
+            ),
             pools,
             storage,
             alloc_reserve_count: 0,
             mode,
             config,
             logger,
+            usage_snapshots: options.usage_snapshot.into_iter().collect(),
+            usage_published: MemoryUsage::default(),
+            usage_stale: false,
+            max_chunks,
+            max_alloc_size: properties.max_page_size,
+            alignment: properties.alignment,
         }
     }
 
@@ -385,10 +495,59 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
 
         self.persistent
             .cleanup(&mut self.storage, self.alloc_reserve_count, explicit);
+        self.free_unshared();
 
         for pool in self.pools.iter_mut() {
             pool.cleanup(&mut self.storage, self.alloc_reserve_count, explicit);
         }
+
+        self.publish_usage();
+    }
+
+    /// Frees the unshared chunks whose allocation was freed.
+    fn free_unshared(&mut self) {
+        if self.unshared.has_free_slice() {
+            self.unshared
+                .cleanup(&mut self.storage, self.alloc_reserve_count, true);
+            self.usage_stale = true;
+        }
+    }
+
+    /// Adds the changes in memory usage since the last call to the
+    /// [snapshots](MemoryUsageSnapshot), if any.
+    fn publish_usage(&mut self) {
+        self.usage_stale = false;
+
+        if !self.usage_snapshots.is_empty() {
+            let usage = self.memory_usage();
+            for snapshot in self.usage_snapshots.iter() {
+                snapshot.update(&self.usage_published, &usage);
+            }
+            self.usage_published = usage;
+        }
+    }
+
+    /// Publish the usage to the `snapshot` as well, e.g. for each server sharing the memory
+    /// management through a [`SharedMemoryManagement`](super::SharedMemoryManagement).
+    ///
+    /// Adding a snapshot the usage is already published to does nothing.
+    pub fn add_usage_snapshot(&mut self, snapshot: Arc<MemoryUsageSnapshot>) {
+        if self
+            .usage_snapshots
+            .iter()
+            .any(|s| Arc::ptr_eq(s, &snapshot))
+        {
+            return;
+        }
+        snapshot.update(&MemoryUsage::default(), &self.usage_published);
+        self.usage_snapshots.push(snapshot);
+        self.publish_usage();
+    }
+
+    /// Log the memory events to the `logger` instead of the one the memory management was
+    /// created with.
+    pub fn set_logger(&mut self, logger: Arc<ServerLogger>) {
+        self.logger = logger;
     }
 
     /// Returns the storage from the specified binding
@@ -401,6 +560,9 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
     fn find(&self, binding: ManagedMemoryBinding) -> Result<&Slice, IoError> {
         let id = binding.descriptor();
 
+        if id.location().pool == self.pools.len() as u8 + 1 {
+            return self.unshared.find(&binding);
+        }
         if id.location().pool >= self.pools.len() as u8 {
             return self.persistent.find(&binding);
         }
@@ -449,6 +611,70 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
     /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn reserve(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
+        self.reserve_with_hint(size, None)
+    }
+
+    /// Same as [`Self::reserve`], but biases the placement of the resource using the given
+    /// [access hint](AccessHint).
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_with_hint(
+        &mut self,
+        size: u64,
+        hint: Option<AccessHint>,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.free_unshared();
+        let reserved = self.reserve_slice(size, hint);
+
+        if self.usage_stale
+            || self
+                .alloc_reserve_count
+                .is_multiple_of(USAGE_PUBLISH_PERIOD)
+        {
+            self.publish_usage();
+        }
+
+        reserved
+    }
+
+    /// Same as [`Self::reserve`], but the resource starts at an offset of its chunk that is a
+    /// multiple of `alignment` bytes.
+    ///
+    /// Alignments the slices of the pools don't already satisfy get a chunk of their own in
+    /// persistent memory, starting the resource at offset zero.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reserve_aligned(
+        &mut self,
+        size: u64,
+        alignment: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        if self.alignment.is_multiple_of(alignment) {
+            return self.reserve(size);
+        }
+
+        if size > self.max_alloc_size {
+            return Err(IoError::BufferTooBig {
+                size,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        self.alloc_reserve_count += 1;
+        if let Some(val) = self.persistent.try_reserve(size) {
+            return Ok(val);
+        }
+
+        self.make_room_for_chunk(size)?;
+        let allocated = self.persistent.alloc(&mut self.storage, size);
+        self.publish_usage();
+
+        allocated
+    }
+
+    fn reserve_slice(
+        &mut self,
+        size: u64,
+        hint: Option<AccessHint>,
+    ) -> Result<ManagedMemoryHandle, IoError> {
         // If this happens every nanosecond, counts overflows after 585 years, so not worth thinking too
         // hard about overflow here.
         self.alloc_reserve_count += 1;
@@ -466,8 +692,20 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             return Ok(val);
         }
 
-        if matches!(self.mode, MemoryAllocationMode::Persistent) || self.persistent.has_size(size) {
+        let hot_unshared = matches!(hint, Some(AccessHint::Hot))
+            && self
+                .pools
+                .iter()
+                .find(|p| p.accept(size))
+                .is_some_and(|p| !p.shares_page(size));
+
+        if matches!(self.mode, MemoryAllocationMode::Persistent)
+            || hot_unshared
+            || self.persistent.has_size(size)
+        {
+            self.make_room_for_chunk(size)?;
             let allocated = self.persistent.alloc(&mut self.storage, size);
+            self.usage_stale = true;
 
             self.logger.log_memory(
                 |level| !matches!(level, MemoryLogLevel::Disabled),
@@ -481,6 +719,15 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             return allocated;
         }
 
+        // Fail before the pools ask the storage for a page it can't allocate. Persistent
+        // allocations are left to the storage, which reports the sizes it can't allocate.
+        if size > self.max_alloc_size {
+            return Err(IoError::BufferTooBig {
+                size,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
         self.logger.log_memory(
             |level| matches!(level, MemoryLogLevel::Full),
             || {
@@ -493,20 +740,22 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         );
 
         // Find first pool that fits this allocation
-        let pool = self
-            .pools
-            .iter_mut()
-            .find(|p| p.accept(size))
-            .ok_or(IoError::BufferTooBig {
-                size,
-                backtrace: BackTrace::capture(),
-            })?;
+        let pool_index =
+            self.pools
+                .iter()
+                .position(|p| p.accept(size))
+                .ok_or(IoError::BufferTooBig {
+                    size,
+                    backtrace: BackTrace::capture(),
+                })?;
 
-        if let Some(slice) = pool.try_reserve(size) {
+        if let Some(slice) = self.pools[pool_index].try_reserve(size) {
             return Ok(slice);
         }
 
-        let allocated = pool.alloc(&mut self.storage, size);
+        self.make_room_for_chunk(size)?;
+        let allocated = self.pools[pool_index].alloc(&mut self.storage, size);
+        self.usage_stale = true;
 
         self.logger.log_memory(
             |level| matches!(level, MemoryLogLevel::Full),
@@ -521,6 +770,69 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         allocated
     }
 
+    /// Frees the unused chunks when the [chunk limit](MemoryManagementOptions::max_chunks) is
+    /// reached, and fails if none could be freed.
+    fn make_room_for_chunk(&mut self, size: u64) -> Result<(), IoError> {
+        let Some(max_chunks) = self.max_chunks else {
+            return Ok(());
+        };
+
+        if self.chunk_count() < max_chunks {
+            return Ok(());
+        }
+
+        self.cleanup(true);
+
+        if self.chunk_count() < max_chunks {
+            return Ok(());
+        }
+
+        Err(IoError::OutOfMemory {
+            size,
+            reason: format!("the limit of {max_chunks} memory chunks is reached"),
+            backtrace: BackTrace::capture(),
+        })
+    }
+
+    /// The number of chunks currently reserved in the storage.
+    pub fn chunk_count(&self) -> usize {
+        self.memory_usage().number_chunks as usize
+    }
+
+    /// The [layout](ChunkInfo) of every chunk currently reserved in the storage.
+    pub fn chunk_map(&self) -> Vec<ChunkInfo> {
+        let mut chunks = self.persistent.chunk_map();
+        chunks.extend(self.unshared.chunk_map());
+        for pool in self.pools.iter() {
+            chunks.extend(pool.chunk_map());
+        }
+        chunks
+    }
+
+    /// The bytes held by the allocations currently in use, by size.
+    ///
+    /// Each entry is the smallest size of a bucket of [`SIZE_HISTOGRAM_BUCKETS`], with the total
+    /// bytes of the allocations in that bucket. This shows whether the memory is held by a few
+    /// large buffers or by many small ones. Allocations are counted with the bytes reserved for
+    /// them, rounded up to their page with exclusive pages. Allocations of custom pools aren't
+    /// counted since their layout isn't known.
+    pub fn size_histogram(&self) -> [(usize, usize); SIZE_HISTOGRAM_BUCKETS.len()] {
+        let mut histogram = SIZE_HISTOGRAM_BUCKETS.map(|min_size| (min_size, 0));
+
+        for chunk in self.chunk_map() {
+            for (_offset, size) in chunk.alloc_ranges {
+                let size = size as usize;
+                let bucket = SIZE_HISTOGRAM_BUCKETS
+                    .iter()
+                    .rposition(|min_size| size >= *min_size)
+                    .unwrap_or(0);
+                histogram[bucket].1 += size;
+            }
+        }
+
+        histogram
+    }
+
     /// Fetch the storage used by the memory manager.
     ///
     /// # Notes
@@ -543,10 +855,13 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                 bytes_in_use: 0,
                 bytes_padding: 0,
                 bytes_reserved: 0,
+                number_chunks: 0,
             },
             |m1, m2| m1.combine(m2),
         );
-        memory_usage.combine(self.persistent.get_memory_usage())
+        memory_usage
+            .combine(self.persistent.get_memory_usage())
+            .combine(self.unshared.get_memory_usage())
     }
 
     /// Print out a report of the current memory usage.
@@ -555,6 +870,45 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         log::info!("{}", self.memory_usage());
     }
 
+    /// Register storage that was allocated outside of the memory management as a persistent chunk
+    /// holding `size` bytes, and returns a handle to it.
+    ///
+    /// The chunk is managed like any other once registered, and is deallocated by the storage.
+    pub fn register(&mut self, storage: StorageHandle, size: u64) -> ManagedMemoryHandle {
+        let handle = self.persistent.register(storage, size);
+        self.usage_stale = true;
+        self.publish_usage();
+
+        handle
+    }
+
+    /// Register storage that was allocated outside of the memory management as a chunk
+    /// holding `size` bytes for the returned handle only.
+    ///
+    /// Unlike with [`register`](Self::register), the chunk is never reused for other
+    /// allocations: the storage deallocates it once the handle is freed.
+    pub fn register_unshared(&mut self, storage: StorageHandle, size: u64) -> ManagedMemoryHandle {
+        let handle = self.unshared.register(storage, size);
+        self.usage_stale = true;
+        self.publish_usage();
+
+        handle
+    }
+
+    /// Release the memory of the binding, even if handles to it are still alive.
+    ///
+    /// The slice is handed a new handle, so it can be reused as soon as the binding is dropped,
+    /// and looking up the memory with the remaining handles fails.
+    pub fn release(&mut self, binding: ManagedMemoryBinding) -> Result<(), IoError> {
+        let slice = self.find(binding)?;
+        let (reserved, cursor) = (slice.handle.clone(), slice.cursor);
+
+        self.bind(reserved, ManagedMemoryHandle::new(), cursor)?;
+        self.publish_usage();
+
+        Ok(())
+    }
+
     /// Binds the given [handle](HandleId) to a [`MemorySlot`].
     pub fn bind(
         &mut self,
@@ -572,6 +926,9 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         }
 
         let pool_index = descriptor.location().pool as usize;
+        if pool_index == self.pools.len() + 1 {
+            return self.unshared.bind(reserved, assigned, cursor);
+        }
         if pool_index >= self.pools.len() {
             return self.persistent.bind(reserved, assigned, cursor);
         }
@@ -586,17 +943,27 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
     }
 }
 
+impl<Storage> Drop for MemoryManagement<Storage> {
+    fn drop(&mut self) {
+        for snapshot in self.usage_snapshots.iter() {
+            snapshot.update(&self.usage_published, &MemoryUsage::default());
+        }
+    }
+}
+
 impl<Storage: ComputeStorage> core::fmt::Display for MemoryManagement<Storage> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str("\n# MemoryManagement\n\n")?;
         f.write_fmt(format_args!(" - name: {:?}\n", self.name))?;
         f.write_fmt(format_args!("\n## Persistent\n\n{}", self.persistent))?;
+        f.write_fmt(format_args!("\n## Unshared\n\n{}", self.unshared))?;
         f.write_str("\n## Dynamic\n\n")?;
 
         for pool in self.pools.iter() {
             match pool {
                 DynamicPool::Sliced(pool) => f.write_fmt(format_args!("{pool}\n"))?,
                 DynamicPool::Exclusive(pool) => f.write_fmt(format_args!("{pool}\n"))?,
+                DynamicPool::Custom(pool) => f.write_fmt(format_args!("{pool}\n"))?,
             }
         }
         let memory_usage = self.memory_usage();
@@ -621,7 +988,12 @@ impl<Storage> core::fmt::Debug for MemoryManagement<Storage> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{memory_management::MemoryManagement, storage::BytesStorage};
+    use crate::{
+        memory_management::{
+            CustomMemoryPool, CustomPoolFactory, MemoryManagement, StorageAllocator,
+        },
+        storage::BytesStorage,
+    };
     use alloc::vec;
 
     const DUMMY_MEM_PROPS: MemoryDeviceProperties = MemoryDeviceProperties {
@@ -630,10 +1002,7 @@ mod tests {
     };
 
     fn options() -> MemoryManagementOptions {
-        MemoryManagementOptions {
-            name: "test".into(),
-            memory: MemoryAllocationOption::FromConfig,
-        }
+        MemoryManagementOptions::new("test")
     }
 
     // Test pools with slices.
@@ -669,6 +1038,7 @@ mod tests {
                         max_alloc_size: max_page_size,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -687,6 +1057,100 @@ mod tests {
         assert_eq!(usage, usage_new);
     }
 
+    #[test_log::test]
+    #[cfg(not(exclusive_memory_only))]
+    fn size_histogram_sums_the_bytes_in_use_by_size() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::SubSlices,
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+        let _small = memory_management.reserve(512).unwrap();
+        let _medium = memory_management.reserve(4096).unwrap();
+        let _other = memory_management.reserve(8192).unwrap();
+        let freed = memory_management.reserve(2 * 1024 * 1024).unwrap();
+        drop(freed);
+
+        assert_eq!(
+            memory_management.size_histogram(),
+            [
+                (0, 512),
+                (1024, 4096 + 8192),
+                (1024 * 1024, 0),
+                (100 * 1024 * 1024, 0)
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn max_chunks_frees_unused_chunks_or_fails() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::ExclusivePages {
+                        max_alloc_size: 512,
+                    },
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options().max_chunks(2),
+        );
+        let first = memory_management.reserve(100).unwrap();
+        let _second = memory_management.reserve(100).unwrap();
+        assert_eq!(memory_management.chunk_count(), 2);
+        assert_eq!(memory_management.memory_usage().number_chunks, 2);
+
+        assert!(matches!(
+            memory_management.reserve(100),
+            Err(IoError::OutOfMemory { .. })
+        ));
+
+        // The freed chunk is too small to be reused, so it's freed to make room for a new one.
+        drop(first);
+        let _third = memory_management.reserve(400).unwrap();
+        assert_eq!(memory_management.chunk_count(), 2);
+    }
+
+    #[test_log::test]
+    fn reserve_larger_than_max_alloc_size_fails() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::default(),
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        assert!(matches!(
+            memory_management.reserve(DUMMY_MEM_PROPS.max_page_size + 1),
+            Err(IoError::BufferTooBig { .. })
+        ));
+        assert_eq!(memory_management.chunk_count(), 0);
+    }
+
+    #[test_log::test]
+    fn persistent_reserve_larger_than_max_alloc_size_is_left_to_the_storage() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &MemoryDeviceProperties {
+                max_page_size: 1024,
+                alignment: 32,
+            },
+            MemoryConfiguration::default(),
+            Arc::new(ServerLogger::default()),
+            options().mode(MemoryAllocationMode::Persistent),
+        );
+
+        let _handle = memory_management.reserve(2048).unwrap();
+        assert_eq!(memory_management.chunk_count(), 1);
+    }
+
     #[test_log::test]
     fn alloc_two_chunks_on_one_page() {
         let page_size = 2048;
@@ -701,6 +1165,7 @@ mod tests {
                         max_slice_size: page_size,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -717,6 +1182,73 @@ mod tests {
         assert_eq!(usage.bytes_reserved, page_size);
     }
 
+    #[test_log::test]
+    fn chunk_map_lists_free_and_allocated_ranges() {
+        let page_size = 2048;
+
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::SlicedPages {
+                        page_size,
+                        max_slice_size: page_size,
+                    },
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        let first = memory_management.reserve(512).unwrap();
+        let _second = memory_management.reserve(512).unwrap();
+        drop(first);
+
+        let chunks = memory_management.chunk_map();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].total, page_size);
+        assert_eq!(chunks[0].alloc_ranges, [(512, 512)]);
+        assert_eq!(chunks[0].free_ranges, [(0, 512), (1024, 1024)]);
+    }
+
+    #[test_log::test]
+    fn released_memory_is_reused_while_handles_are_alive() {
+        let page_size = 512;
+
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::SlicedPages {
+                        page_size,
+                        max_slice_size: page_size,
+                    },
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        let reserved = memory_management.reserve(page_size).unwrap();
+        let handle = ManagedMemoryHandle::new();
+        memory_management.bind(reserved, handle.clone(), 0).unwrap();
+
+        memory_management.release(handle.clone().binding()).unwrap();
+        assert_eq!(memory_management.memory_usage().number_allocs, 0);
+
+        let _new_handle = memory_management.reserve(page_size).unwrap();
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.number_allocs, 1);
+        assert_eq!(usage.bytes_reserved, page_size);
+        drop(handle);
+    }
+
     #[test_log::test]
     fn alloc_reuses_storage() {
         // If no storage is re-used, this will allocate two pages.
@@ -732,6 +1264,7 @@ mod tests {
                         max_slice_size: page_size,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -749,6 +1282,140 @@ mod tests {
         assert_eq!(usage.bytes_reserved, page_size);
     }
 
+    #[test_log::test]
+    fn alloc_reuses_fully_freed_page() {
+        let page_size = 2048;
+
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::SlicedPages {
+                        page_size,
+                        max_slice_size: page_size,
+                    },
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        // Fill the page with padded slices.
+        let handles: Vec<_> = (0..4)
+            .map(|_| memory_management.reserve(500).unwrap())
+            .collect();
+        assert_eq!(memory_management.memory_usage().bytes_reserved, page_size);
+        drop(handles);
+
+        // The whole page is free again, so it can be reserved as a single slice.
+        let handle = memory_management.reserve(page_size).unwrap();
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.number_allocs, 1);
+        assert_eq!(usage.bytes_in_use, page_size);
+        assert_eq!(usage.bytes_reserved, page_size);
+
+        // Same thing with a single padded slice covering the whole page.
+        drop(handle);
+        let handle = memory_management.reserve(page_size - 8).unwrap();
+        drop(handle);
+        let _handle = memory_management.reserve(page_size).unwrap();
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.number_allocs, 1);
+        assert_eq!(usage.bytes_reserved, page_size);
+    }
+
+    #[test_log::test]
+    #[cfg(not(exclusive_memory_only))]
+    fn hot_access_hint_uses_persistent_memory_for_unshared_pages() {
+        let page_size = 1024;
+
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::SlicedPages {
+                        page_size,
+                        max_slice_size: page_size,
+                    },
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        // Small hot resources share the page of the dynamic pool.
+        let _small = memory_management
+            .reserve_with_hint(128, Some(AccessHint::Hot))
+            .unwrap();
+        let _other = memory_management.reserve(128).unwrap();
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.bytes_reserved, page_size);
+
+        // A big hot resource gets an exact-size persistent allocation instead of its own page.
+        let _big = memory_management
+            .reserve_with_hint(768, Some(AccessHint::Hot))
+            .unwrap();
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.number_allocs, 3);
+        assert_eq!(usage.bytes_reserved, page_size + 768);
+    }
+
+    #[test_log::test]
+    fn usage_snapshot_combines_instances() {
+        let snapshot = Arc::new(MemoryUsageSnapshot::default());
+        let new_memory_management = || {
+            MemoryManagement::from_configuration(
+                BytesStorage::default(),
+                &DUMMY_MEM_PROPS,
+                MemoryConfiguration::ExclusivePages,
+                Arc::new(ServerLogger::default()),
+                options().usage_snapshot(snapshot.clone()),
+            )
+        };
+
+        let mut first = new_memory_management();
+        let mut second = new_memory_management();
+        let handle = first.reserve(1024).unwrap();
+        let _other = second.reserve(2048).unwrap();
+
+        let usage = snapshot.load();
+        assert_eq!(usage.number_allocs, 2);
+        assert_eq!(usage.bytes_in_use, 1024 + 2048);
+
+        core::mem::drop(handle);
+        first.cleanup(true);
+        assert_eq!(snapshot.load(), second.memory_usage());
+
+        core::mem::drop(second);
+        assert_eq!(snapshot.load(), first.memory_usage());
+    }
+
+    #[test_log::test]
+    fn added_usage_snapshot_starts_from_the_current_usage() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::ExclusivePages,
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+        let _first = memory_management.reserve(1024).unwrap();
+
+        let snapshot = Arc::new(MemoryUsageSnapshot::default());
+        memory_management.add_usage_snapshot(snapshot.clone());
+        assert_eq!(snapshot.load(), memory_management.memory_usage());
+
+        memory_management.add_usage_snapshot(snapshot.clone());
+        let _second = memory_management.reserve(2048).unwrap();
+        assert_eq!(snapshot.load(), memory_management.memory_usage());
+    }
+
     #[test_log::test]
     fn alloc_allocs_new_storage() {
         let page_size = 1024;
@@ -763,6 +1430,7 @@ mod tests {
                         max_slice_size: page_size,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -795,6 +1463,7 @@ mod tests {
                         max_slice_size: page_size,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -808,6 +1477,81 @@ mod tests {
         assert_eq!(usage.bytes_padding, 10 * 2);
     }
 
+    #[test_log::test]
+    #[cfg(not(exclusive_memory_only))]
+    fn reserve_aligned_starts_at_an_aligned_offset() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::SubSlices,
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+        let _first = memory_management.reserve(32).unwrap();
+        let aligned = memory_management.reserve_aligned(32, 128).unwrap();
+
+        let storage = memory_management.get_storage(aligned.binding()).unwrap();
+        assert!(storage.offset().is_multiple_of(128));
+    }
+
+    #[test_log::test]
+    #[cfg(not(exclusive_memory_only))]
+    fn unshared_chunks_are_freed_instead_of_reused() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::SubSlices,
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+        let storage = ComputeStorage::alloc(memory_management.storage(), 64).unwrap();
+        let unshared_id = storage.id;
+        let unshared = memory_management.register_unshared(storage, 64);
+        assert_eq!(memory_management.chunk_count(), 1);
+        core::mem::drop(unshared);
+
+        let reserved = memory_management.reserve(64).unwrap();
+
+        let storage = memory_management.get_storage(reserved.binding()).unwrap();
+        assert_ne!(storage.id, unshared_id);
+        assert_eq!(memory_management.chunk_count(), 1);
+    }
+
+    #[test_log::test]
+    fn alloc_respects_size_rounding() {
+        let page_size = 4096;
+        let padding = |size_rounding| {
+            let mut memory_management = MemoryManagement::from_configuration(
+                BytesStorage::default(),
+                &DUMMY_MEM_PROPS,
+                MemoryConfiguration::Custom {
+                    pool_options: vec![MemoryPoolOptions {
+                        pool_type: PoolType::SlicedPages {
+                            page_size,
+                            max_slice_size: page_size,
+                        },
+                        dealloc_period: None,
+                        size_rounding,
+                    }],
+                },
+                Arc::new(ServerLogger::default()),
+                options(),
+            );
+            let _handle = memory_management.reserve(600);
+            memory_management.memory_usage().bytes_padding
+        };
+
+        assert_eq!(padding(SizeRounding::Exact), 8);
+        assert_eq!(padding(SizeRounding::NextMultiple(256)), 168);
+        assert_eq!(padding(SizeRounding::PowerOfTwo), 424);
+    }
+
+    #[test_log::test]
+    fn size_rounding_is_capped_to_the_max_size() {
+        assert_eq!(SizeRounding::PowerOfTwo.padding(600, 32, 700), 100 + 4);
+        assert_eq!(SizeRounding::NextMultiple(256).padding(600, 32, 512), 8);
+    }
+
     #[test_log::test]
     fn allocs_on_correct_page() {
         let sizes = [100, 200, 300, 400];
@@ -820,6 +1564,7 @@ mod tests {
                     max_slice_size: *size,
                 },
                 dealloc_period: None,
+                size_rounding: SizeRounding::default(),
             })
             .collect();
         let mut memory_management = MemoryManagement::from_configuration(
@@ -940,6 +1685,7 @@ mod tests {
                         max_alloc_size: 1024,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -968,6 +1714,7 @@ mod tests {
                         max_alloc_size: 1024,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -996,6 +1743,7 @@ mod tests {
                         max_alloc_size: 1024,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -1025,6 +1773,7 @@ mod tests {
                         max_alloc_size: 50 * 20,
                     },
                     dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
                 }],
             },
             Arc::new(ServerLogger::default()),
@@ -1048,6 +1797,7 @@ mod tests {
                     max_slice_size: size,
                 },
                 dealloc_period: None,
+                size_rounding: SizeRounding::default(),
             })
             .collect();
         let mut memory_management = MemoryManagement::from_configuration(
@@ -1098,4 +1848,83 @@ mod tests {
         assert_eq!(usage_before.bytes_in_use, usage_after.bytes_in_use);
         assert_eq!(usage_before.bytes_reserved, usage_after.bytes_reserved);
     }
+
+    /// Allocates a chunk for every reservation, and frees it once the reservation is released.
+    struct ChunkPerAllocPool {
+        deallocs: Arc<core::sync::atomic::AtomicUsize>,
+        bytes_in_use: u64,
+        number_allocs: u64,
+    }
+
+    impl CustomMemoryPool for ChunkPerAllocPool {
+        fn accept(&self, _size: u64) -> bool {
+            true
+        }
+
+        fn reserve(
+            &mut self,
+            storage: &mut dyn StorageAllocator,
+            size: u64,
+        ) -> Result<StorageHandle, IoError> {
+            self.bytes_in_use += size;
+            self.number_allocs += 1;
+            storage.alloc(size)
+        }
+
+        fn dealloc(&mut self, storage: &mut dyn StorageAllocator, region: StorageHandle) {
+            self.bytes_in_use -= region.size();
+            self.number_allocs -= 1;
+            self.deallocs
+                .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+            storage.dealloc(region.id);
+        }
+
+        fn usage(&self) -> MemoryUsage {
+            MemoryUsage {
+                number_allocs: self.number_allocs,
+                bytes_in_use: self.bytes_in_use,
+                bytes_padding: 0,
+                bytes_reserved: self.bytes_in_use,
+                number_chunks: self.number_allocs,
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn custom_pool_reuses_released_reservations() {
+        let deallocs = Arc::new(core::sync::atomic::AtomicUsize::new(0));
+        let factory = {
+            let deallocs = deallocs.clone();
+            CustomPoolFactory::new(move || ChunkPerAllocPool {
+                deallocs: deallocs.clone(),
+                bytes_in_use: 0,
+                number_allocs: 0,
+            })
+        };
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::Custom {
+                pool_options: vec![MemoryPoolOptions {
+                    pool_type: PoolType::Custom(factory),
+                    dealloc_period: None,
+                    size_rounding: SizeRounding::default(),
+                }],
+            },
+            Arc::new(ServerLogger::default()),
+            options(),
+        );
+
+        let first = memory_management.reserve(100).unwrap();
+        let _second = memory_management.reserve(200).unwrap();
+        assert_eq!(memory_management.memory_usage().bytes_in_use, 300);
+
+        drop(first);
+        let third = memory_management.reserve(50).unwrap();
+
+        assert_eq!(deallocs.load(core::sync::atomic::Ordering::Relaxed), 1);
+        assert_eq!(memory_management.memory_usage().bytes_in_use, 250);
+        assert_eq!(memory_management.memory_usage().number_allocs, 2);
+        assert!(third.can_mut());
+    }
 }
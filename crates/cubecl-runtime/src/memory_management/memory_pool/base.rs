@@ -1,9 +1,10 @@
 use super::{ManagedMemoryBinding, ManagedMemoryDescriptor, ManagedMemoryHandle};
 use crate::{
-    memory_management::MemoryUsage,
+    memory_management::{ChunkInfo, MemoryUsage},
     server::IoError,
     storage::{ComputeStorage, StorageHandle},
 };
+use alloc::vec::Vec;
 
 /// Declares how memory is allocated in a reusable pool.
 pub trait MemoryPool {
@@ -63,6 +64,9 @@ pub trait MemoryPool {
     /// Computes the [`MemoryUsage`] for this pool.
     fn get_memory_usage(&self) -> MemoryUsage;
 
+    /// The [layout](ChunkInfo) of every chunk of this pool.
+    fn chunk_map(&self) -> Vec<ChunkInfo>;
+
     /// Cleanup the memory pool, maybe freeing some memory using the [`ComputeStorage`].
     fn cleanup<Storage: ComputeStorage>(
         &mut self,
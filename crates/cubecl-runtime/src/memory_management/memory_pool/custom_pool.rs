@@ -0,0 +1,225 @@
+use crate::{
+    memory_management::{ChunkInfo, MemoryLocation, MemoryUsage},
+    server::IoError,
+    storage::{ComputeStorage, StorageHandle, StorageId},
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use cubecl_common::backtrace::BackTrace;
+
+use super::{ManagedMemoryBinding, ManagedMemoryHandle, MemoryPool, Slice};
+
+/// The chunks of memory of the storage a [`CustomMemoryPool`] places its allocations in.
+pub trait StorageAllocator {
+    /// The alignment the offsets of the allocations in a chunk must have.
+    fn alignment(&self) -> usize;
+
+    /// Allocates a new chunk of `size` bytes.
+    fn alloc(&mut self, size: u64) -> Result<StorageHandle, IoError>;
+
+    /// Deallocates the chunk, once the memory of the stream is no longer in use.
+    fn dealloc(&mut self, id: StorageId);
+}
+
+impl<Storage: ComputeStorage> StorageAllocator for Storage {
+    fn alignment(&self) -> usize {
+        ComputeStorage::alignment(self)
+    }
+
+    fn alloc(&mut self, size: u64) -> Result<StorageHandle, IoError> {
+        ComputeStorage::alloc(self, size)
+    }
+
+    fn dealloc(&mut self, id: StorageId) {
+        ComputeStorage::dealloc(self, id)
+    }
+}
+
+/// A user provided allocation strategy for a memory pool, see [`PoolType::Custom`].
+///
+/// The pool hands out regions of the chunks it allocates from the storage. The memory
+/// management keeps track of the handles of the regions, and gives them back to the pool with
+/// [`dealloc`](Self::dealloc) once they are no longer used.
+///
+/// [`PoolType::Custom`]: crate::memory_management::PoolType::Custom
+pub trait CustomMemoryPool: Send {
+    /// Whether the pool handles allocations of `size` bytes.
+    fn accept(&self, size: u64) -> bool;
+
+    /// Reserves a region of `size` bytes, allocating new chunks from the `storage` if needed.
+    ///
+    /// The offset of the region in its chunk must be a multiple of the
+    /// [alignment](StorageAllocator::alignment) of the storage.
+    fn reserve(
+        &mut self,
+        storage: &mut dyn StorageAllocator,
+        size: u64,
+    ) -> Result<StorageHandle, IoError>;
+
+    /// Gives back a region returned by [`reserve`](Self::reserve) that is no longer used.
+    fn dealloc(&mut self, storage: &mut dyn StorageAllocator, region: StorageHandle);
+
+    /// The memory usage of the pool.
+    fn usage(&self) -> MemoryUsage;
+
+    /// Frees the chunks that aren't needed anymore, or all unused chunks if `explicit`.
+    fn cleanup(&mut self, _storage: &mut dyn StorageAllocator, _explicit: bool) {}
+}
+
+/// Creates the [`CustomMemoryPool`] of each memory management using a [`PoolType::Custom`].
+///
+/// [`PoolType::Custom`]: crate::memory_management::PoolType::Custom
+#[derive(Clone)]
+pub struct CustomPoolFactory {
+    factory: Arc<dyn Fn() -> Box<dyn CustomMemoryPool> + Send + Sync>,
+}
+
+impl CustomPoolFactory {
+    /// Create a factory calling `factory` for every new pool.
+    pub fn new<F, P>(factory: F) -> Self
+    where
+        F: Fn() -> P + Send + Sync + 'static,
+        P: CustomMemoryPool + 'static,
+    {
+        Self {
+            factory: Arc::new(move || Box::new(factory())),
+        }
+    }
+
+    pub(crate) fn create(&self) -> Box<dyn CustomMemoryPool> {
+        (self.factory)()
+    }
+}
+
+impl core::fmt::Debug for CustomPoolFactory {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CustomPoolFactory").finish()
+    }
+}
+
+/// A memory pool delegating where allocations are placed to a [`CustomMemoryPool`].
+pub(crate) struct CustomPool {
+    pool: Box<dyn CustomMemoryPool>,
+    slices: Vec<Slice>,
+    location_base: MemoryLocation,
+}
+
+impl core::fmt::Display for CustomPool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            " - Custom Pool allocations={}\n",
+            self.slices.len()
+        ))?;
+        f.write_fmt(format_args!("\n{}\n", self.get_memory_usage()))
+    }
+}
+
+impl CustomPool {
+    pub(crate) fn new(pool: Box<dyn CustomMemoryPool>, pool_pos: u8) -> Self {
+        Self {
+            pool,
+            slices: Vec::new(),
+            location_base: MemoryLocation::new(pool_pos, 0, 0),
+        }
+    }
+
+    /// Give the regions that are no longer used back to the custom pool.
+    fn release_free<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        if !self.slices.iter().any(|slice| slice.is_free()) {
+            return;
+        }
+
+        let slices = core::mem::take(&mut self.slices);
+        for slice in slices {
+            if slice.is_free() {
+                self.pool.dealloc(storage, slice.storage);
+                continue;
+            }
+
+            slice.descriptor().update_slice(self.slices.len() as u32);
+            self.slices.push(slice);
+        }
+    }
+}
+
+impl MemoryPool for CustomPool {
+    fn accept(&self, size: u64) -> bool {
+        self.pool.accept(size)
+    }
+
+    // The custom pool needs the storage to reserve memory, so every reservation goes through
+    // `alloc`.
+    fn try_reserve(&mut self, _size: u64) -> Option<ManagedMemoryHandle> {
+        None
+    }
+
+    fn alloc<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.release_free(storage);
+
+        let region = self.pool.reserve(storage, size)?;
+        let slice = Slice::new(region, 0);
+        let handle = slice.handle.clone();
+
+        let mut location = self.location_base;
+        location.slice = self.slices.len() as u32;
+        handle.descriptor().update_location(location);
+        self.slices.push(slice);
+
+        Ok(handle)
+    }
+
+    fn get_memory_usage(&self) -> MemoryUsage {
+        self.pool.usage()
+    }
+
+    // The chunks are owned by the custom pool, which doesn't expose their layout.
+    fn chunk_map(&self) -> Vec<ChunkInfo> {
+        Vec::new()
+    }
+
+    fn cleanup<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        _alloc_nr: u64,
+        explicit: bool,
+    ) {
+        self.release_free(storage);
+        self.pool.cleanup(storage, explicit);
+    }
+
+    fn bind(
+        &mut self,
+        reserved: ManagedMemoryHandle,
+        assigned: ManagedMemoryHandle,
+        cursor: u64,
+    ) -> Result<(), IoError> {
+        let descriptor = reserved.descriptor();
+        let slice = self
+            .slices
+            .get_mut(descriptor.slice())
+            .ok_or_else(|| IoError::NotFound {
+                backtrace: BackTrace::capture(),
+                reason: "Reserved memory isn't in the custom pool".into(),
+            })?;
+        assigned.descriptor().update_location(descriptor.location());
+
+        slice.handle = assigned;
+        slice.cursor = cursor;
+
+        Ok(())
+    }
+
+    fn find(&self, binding: &ManagedMemoryBinding) -> Result<&Slice, IoError> {
+        let slice_index = binding.descriptor().slice();
+
+        self.slices
+            .get(slice_index)
+            .ok_or_else(|| IoError::NotFound {
+                backtrace: BackTrace::capture(),
+                reason: alloc::format!("Memory slice {slice_index} doesn't exist").into(),
+            })
+    }
+}
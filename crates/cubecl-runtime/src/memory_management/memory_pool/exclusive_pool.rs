@@ -1,5 +1,5 @@
 use crate::{
-    memory_management::{BytesFormat, MemoryLocation, MemoryUsage},
+    memory_management::{BytesFormat, ChunkInfo, MemoryLocation, MemoryUsage, SizeRounding},
     server::IoError,
     storage::{ComputeStorage, StorageUtilization},
 };
@@ -7,7 +7,7 @@ use crate::{
 use alloc::vec::Vec;
 use cubecl_common::backtrace::BackTrace;
 
-use super::{ManagedMemoryBinding, ManagedMemoryHandle, MemoryPool, Slice, calculate_padding};
+use super::{ManagedMemoryBinding, ManagedMemoryHandle, MemoryPool, Slice};
 
 /// A memory pool that allocates buffers in a range of sizes and reuses them to minimize allocations.
 ///
@@ -18,6 +18,7 @@ pub struct ExclusiveMemoryPool {
     pages: Vec<MemoryPage>,
     pages_tmp: Vec<MemoryPage>,
     alignment: u64,
+    rounding: SizeRounding,
     dealloc_period: u64,
     last_dealloc_check: u64,
     max_alloc_size: u64,
@@ -62,6 +63,7 @@ impl ExclusiveMemoryPool {
     pub(crate) fn new(
         max_alloc_size: u64,
         alignment: u64,
+        rounding: SizeRounding,
         dealloc_period: u64,
         pool_pos: u8,
     ) -> Self {
@@ -72,6 +74,7 @@ impl ExclusiveMemoryPool {
             pages: Vec::new(),
             pages_tmp: Vec::new(),
             alignment,
+            rounding,
             dealloc_period,
             last_dealloc_check: 0,
             max_alloc_size,
@@ -80,6 +83,11 @@ impl ExclusiveMemoryPool {
         }
     }
 
+    fn padding(&self, size: u64) -> u64 {
+        self.rounding
+            .padding(size, self.alignment, self.max_alloc_size)
+    }
+
     /// Finds a free page that can contain the given size
     /// Returns a slice on that page if successful.
     fn get_free_page(&mut self, size: u64) -> Option<&mut MemoryPage> {
@@ -95,13 +103,13 @@ impl ExclusiveMemoryPool {
         storage: &mut Storage,
         size: u64,
     ) -> Result<(usize, &mut MemoryPage), IoError> {
+        let padding = self.padding(size);
         let alloc_size = (self.cur_avg_size as u64)
-            .max(size)
+            .max(size + padding)
             .next_multiple_of(self.alignment);
 
         let storage = storage.alloc(alloc_size)?;
 
-        let padding = calculate_padding(size, self.alignment);
         let mut slice = Slice::new(storage, padding);
 
         // Return a smaller part of the slice. By construction, we only ever
@@ -135,9 +143,9 @@ impl MemoryPool for ExclusiveMemoryPool {
         self.cur_avg_size =
             self.cur_avg_size * (1.0 - SIZE_AVG_DECAY) + size as f64 * SIZE_AVG_DECAY;
 
-        let padding = calculate_padding(size, self.alignment);
+        let padding = self.padding(size);
 
-        self.get_free_page(size).map(|page| {
+        self.get_free_page(size + padding).map(|page| {
             // Return a smaller part of the slice. By construction, we only ever
             // get a page with a big enough size, so this is ok to do.
             page.slice.storage.utilization = StorageUtilization { offset: 0, size };
@@ -187,9 +195,21 @@ impl MemoryPool for ExclusiveMemoryPool {
                 .sum(),
             bytes_padding: used_slices.iter().map(|page| page.slice.padding).sum(),
             bytes_reserved: self.pages.iter().map(|page| page.alloc_size).sum(),
+            number_chunks: self.pages.len() as u64,
         }
     }
 
+    fn chunk_map(&self) -> Vec<ChunkInfo> {
+        self.pages
+            .iter()
+            .map(|page| {
+                let mut info = ChunkInfo::new(page.slice.storage.id, page.alloc_size);
+                info.push(0, page.alloc_size, page.slice.is_free());
+                info
+            })
+            .collect()
+    }
+
     fn cleanup<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
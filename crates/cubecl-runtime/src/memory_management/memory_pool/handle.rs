@@ -1,5 +1,5 @@
 use crate::memory_management::MemoryHandle;
-use alloc::sync::Arc;
+use alloc::sync::{Arc, Weak};
 use core::cell::Cell;
 
 /// Managed Memory handle
@@ -193,6 +193,11 @@ impl ManagedMemoryBinding {
     pub(crate) fn descriptor(&self) -> &ManagedMemoryDescriptor {
         &self.descriptor
     }
+
+    /// Returns a weak reference to the descriptor, which doesn't keep the memory in use.
+    pub(crate) fn downgrade(&self) -> Weak<ManagedMemoryDescriptor> {
+        Arc::downgrade(&self.descriptor)
+    }
 }
 
 impl Default for ManagedMemoryHandle {
@@ -1,7 +1,7 @@
 use crate::{
     memory_management::{
-        BytesFormat, ManagedMemoryBinding, ManagedMemoryHandle, MemoryLocation, MemoryUsage,
-        memory_pool::{Slice, calculate_padding},
+        BytesFormat, ChunkInfo, ManagedMemoryBinding, ManagedMemoryHandle, MemoryLocation,
+        MemoryUsage, SizeRounding, memory_pool::Slice,
     },
     server::IoError,
     storage::{StorageHandle, StorageUtilization},
@@ -22,17 +22,24 @@ pub struct MemoryPage {
     slices_tmp: Vec<Slice>,
     /// Memory alignment.
     alignment: u64,
+    rounding: SizeRounding,
     location_base: MemoryLocation,
 }
 
 impl MemoryPage {
-    /// Creates a new memory page with the given storage and memory alignment.
-    pub fn new(storage: StorageHandle, alignment: u64, location_base: MemoryLocation) -> Self {
+    /// Creates a new memory page with the given storage, memory alignment and size rounding.
+    pub fn new(
+        storage: StorageHandle,
+        alignment: u64,
+        rounding: SizeRounding,
+        location_base: MemoryLocation,
+    ) -> Self {
         let mut this = MemoryPage {
             storage: storage.clone(),
             slices: Vec::new(),
             slices_tmp: Vec::new(),
             alignment,
+            rounding,
             location_base,
         };
 
@@ -69,6 +76,7 @@ impl MemoryPage {
             bytes_in_use: 0,
             bytes_padding: 0,
             bytes_reserved: 0,
+            number_chunks: 1,
         };
 
         for slice in self.slices.iter() {
@@ -84,6 +92,21 @@ impl MemoryPage {
         usage
     }
 
+    /// Gets the [layout](ChunkInfo) of the current memory page.
+    pub fn chunk_info(&self) -> ChunkInfo {
+        let mut info = ChunkInfo::new(self.storage.id, self.storage.size());
+
+        for slice in self.slices.iter() {
+            info.push(
+                slice.storage.offset(),
+                slice.effective_size(),
+                slice.handle.is_free(),
+            );
+        }
+
+        info
+    }
+
     /// Gets the [summary](MemoryPageSummary) of the current memory page.
     ///
     /// # Arguments
@@ -122,27 +145,34 @@ impl MemoryPage {
     /// you can call the [`Self::coalesce()`] function to merge those.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub fn try_reserve(&mut self, size: u64) -> Option<ManagedMemoryHandle> {
-        let padding = calculate_padding(size, self.alignment);
+        let padding = self
+            .rounding
+            .padding(size, self.alignment, self.storage.size());
         let effective_size = size + padding;
 
         for (index, slice) in self.slices.iter_mut().enumerate() {
-            let can_use_slice =
-                slice.storage.utilization.size >= effective_size && slice.handle.is_free();
+            // The padding of a freed slice is part of the space it can hand out.
+            let effective_size_old = slice.effective_size();
+            let can_use_slice = effective_size_old >= effective_size && slice.handle.is_free();
 
             if !can_use_slice {
                 continue;
             }
 
-            let can_be_split = slice.storage.utilization.size > effective_size;
+            let can_be_split = effective_size_old > effective_size;
             let handle = slice.handle.clone();
-            let storage_old = slice.storage.clone();
+            let mut storage_new = slice.storage.clone();
 
             // Updates the current storage utilization.
             slice.storage.utilization.size = size;
             slice.padding = padding;
 
             if can_be_split {
-                let new_slice = Slice::new(storage_old.offset_start(effective_size), 0);
+                storage_new.utilization = StorageUtilization {
+                    offset: storage_new.offset() + effective_size,
+                    size: effective_size_old - effective_size,
+                };
+                let new_slice = Slice::new(storage_new, 0);
                 self.add_new_slice(index, size, new_slice);
             }
 
@@ -709,9 +739,77 @@ mod tests {
         );
     }
 
+    #[test_log::test]
+    fn test_reuse_padded_slice() {
+        let mut page = new_memory_page(32);
+
+        // A single slice covering the whole page, with 2 bytes of padding.
+        let slice = page
+            .try_reserve(30)
+            .expect("Enough space to allocate a new slice");
+        assert_eq!(page.summary(false).num_total, 1);
+        core::mem::drop(slice);
+
+        // Nothing to coalesce, but the padding can be reclaimed.
+        let slice = page
+            .try_reserve(32)
+            .expect("The freed page to be reusable as a whole");
+
+        assert_eq!(
+            page.find(&slice.binding()).unwrap().storage.utilization,
+            StorageUtilization {
+                offset: 0,
+                size: 32
+            },
+        );
+    }
+
+    #[test_log::test]
+    fn test_split_padded_slice() {
+        let mut page = new_memory_page(32);
+
+        let slice_1 = page
+            .try_reserve(7)
+            .expect("Enough space to allocate a new slice");
+        let _slice_2 = page
+            .try_reserve(24)
+            .expect("Enough space to allocate a new slice");
+        core::mem::drop(slice_1);
+
+        // Splitting the freed slice must not lose its padding.
+        let _slice_3 = page
+            .try_reserve(4)
+            .expect("Enough space to allocate a new slice");
+
+        let summary = page.summary(true);
+        assert_eq!(
+            summary.blocks,
+            vec![
+                MemoryBlock {
+                    is_free: false,
+                    size: 4
+                },
+                MemoryBlock {
+                    is_free: true,
+                    size: 4
+                },
+                MemoryBlock {
+                    is_free: false,
+                    size: 24
+                },
+            ]
+        );
+        assert_eq!(summary.amount_free + summary.amount_full, 32);
+    }
+
     fn new_memory_page(size: u64) -> MemoryPage {
         let storage = StorageHandle::new(StorageId::new(), StorageUtilization { offset: 0, size });
 
-        MemoryPage::new(storage, 4, MemoryLocation::new(0, 0, 0))
+        MemoryPage::new(
+            storage,
+            4,
+            SizeRounding::default(),
+            MemoryLocation::new(0, 0, 0),
+        )
     }
 }
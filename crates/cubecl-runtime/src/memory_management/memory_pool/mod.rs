@@ -1,4 +1,5 @@
 mod base;
+mod custom_pool;
 mod exclusive_pool;
 pub(crate) mod handle;
 mod memory_page;
@@ -6,6 +7,8 @@ mod persistent_pool;
 mod sliced_pool;
 
 pub(crate) use base::*;
+pub(crate) use custom_pool::CustomPool;
+pub use custom_pool::{CustomMemoryPool, CustomPoolFactory, StorageAllocator};
 pub(crate) use exclusive_pool::*;
 pub(crate) use memory_page::*;
 pub(crate) use persistent_pool::*;
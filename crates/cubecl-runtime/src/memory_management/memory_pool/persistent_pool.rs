@@ -1,6 +1,6 @@
 use super::{ManagedMemoryHandle, MemoryPool, Slice, calculate_padding};
-use crate::memory_management::{BytesFormat, MemoryLocation};
-use crate::storage::StorageUtilization;
+use crate::memory_management::{BytesFormat, ChunkInfo, MemoryLocation};
+use crate::storage::{StorageHandle, StorageUtilization};
 use crate::{memory_management::MemoryUsage, server::IoError};
 use alloc::vec;
 use alloc::vec::Vec;
@@ -62,6 +62,38 @@ impl PersistentPool {
         let effective_size = size + padding;
         self.sizes.contains_key(&effective_size)
     }
+
+    /// Whether any slice is currently unused.
+    pub fn has_free_slice(&self) -> bool {
+        self.slices.iter().any(|slice| slice.is_free())
+    }
+
+    /// Add a slice of `size` bytes over storage that was already allocated, the rest of the
+    /// storage being padding.
+    pub fn register(&mut self, storage_handle: StorageHandle, size: u64) -> ManagedMemoryHandle {
+        let effective_size = storage_handle.size();
+        let mut slice = Slice::new(storage_handle, effective_size - size);
+        slice.storage.utilization = StorageUtilization { offset: 0, size };
+        let slice_id = slice.descriptor();
+        let slice_pos = self.slices.len();
+        let mut location = self.location_base;
+        location.slice = slice_pos as u32;
+        slice_id.update_location(location);
+
+        match self.sizes.get_mut(&effective_size) {
+            Some(vals) => {
+                vals.push(slice_pos);
+            }
+            None => {
+                self.sizes.insert(effective_size, vec![slice_pos]);
+            }
+        }
+
+        let handle = slice.handle.clone();
+        self.slices.push(slice);
+
+        handle
+    }
 }
 
 impl MemoryPool for PersistentPool {
@@ -86,9 +118,12 @@ impl MemoryPool for PersistentPool {
 
         if let Some(positions) = self.sizes.get_mut(&effective_size) {
             for pos in positions {
-                let slice = &self.slices[*pos];
+                let slice = &mut self.slices[*pos];
 
                 if slice.is_free() {
+                    // Slices of the same effective size are reused for any size that fits them.
+                    slice.storage.utilization.size = size;
+                    slice.padding = padding;
                     return Some(slice.handle.clone());
                 }
             }
@@ -106,27 +141,8 @@ impl MemoryPool for PersistentPool {
         let effective_size = size + padding;
 
         let storage_handle = storage.alloc(effective_size)?;
-        let mut slice = Slice::new(storage_handle, padding);
-        slice.storage.utilization = StorageUtilization { offset: 0, size };
-        let slice_id = slice.descriptor();
-        let slice_pos = self.slices.len();
-        let mut location = self.location_base;
-        location.slice = slice_pos as u32;
-        slice_id.update_location(location);
 
-        match self.sizes.get_mut(&effective_size) {
-            Some(vals) => {
-                vals.push(slice_pos);
-            }
-            None => {
-                self.sizes.insert(effective_size, vec![slice_pos]);
-            }
-        }
-
-        let handle = slice.handle.clone();
-        self.slices.push(slice);
-
-        Ok(handle)
+        Ok(self.register(storage_handle, size))
     }
 
     fn get_memory_usage(&self) -> MemoryUsage {
@@ -141,9 +157,21 @@ impl MemoryPool for PersistentPool {
             bytes_in_use: used_slices.iter().map(|slice| slice.storage.size()).sum(),
             bytes_padding: used_slices.iter().map(|slice| slice.padding).sum(),
             bytes_reserved: self.slices.iter().map(|slice| slice.effective_size()).sum(),
+            number_chunks: self.slices.len() as u64,
         }
     }
 
+    fn chunk_map(&self) -> Vec<ChunkInfo> {
+        self.slices
+            .iter()
+            .map(|slice| {
+                let mut info = ChunkInfo::new(slice.storage.id, slice.effective_size());
+                info.push(0, slice.effective_size(), slice.is_free());
+                info
+            })
+            .collect()
+    }
+
     fn cleanup<Storage: crate::storage::ComputeStorage>(
         &mut self,
         storage: &mut Storage,
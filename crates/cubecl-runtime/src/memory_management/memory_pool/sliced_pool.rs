@@ -1,6 +1,6 @@
 use crate::{
     memory_management::{
-        BytesFormat, ManagedMemoryHandle, MemoryLocation, MemoryUsage,
+        BytesFormat, ChunkInfo, ManagedMemoryHandle, MemoryLocation, MemoryUsage, SizeRounding,
         memory_pool::{MemoryPage, MemoryPool, Slice},
     },
     server::IoError,
@@ -14,21 +14,34 @@ pub struct SlicedPool {
     pages_tmp: Vec<(MemoryPage, StorageId)>,
     page_size: u64,
     alignment: u64,
+    rounding: SizeRounding,
     max_alloc_size: u64,
     location_base: MemoryLocation,
 }
 
 impl SlicedPool {
-    pub fn new(page_size: u64, max_slice_size: u64, alignment: u64, pool_pos: u8) -> Self {
+    pub fn new(
+        page_size: u64,
+        max_slice_size: u64,
+        alignment: u64,
+        rounding: SizeRounding,
+        pool_pos: u8,
+    ) -> Self {
         Self {
             pages: Vec::new(),
             pages_tmp: Vec::new(),
             page_size,
             alignment,
+            rounding,
             max_alloc_size: max_slice_size,
             location_base: MemoryLocation::new(pool_pos, 0, 0),
         }
     }
+
+    /// Whether at least two allocations of the given size fit in a page.
+    pub fn shares_page(&self, size: u64) -> bool {
+        size.saturating_mul(2) <= self.page_size
+    }
 }
 
 impl MemoryPool for SlicedPool {
@@ -74,7 +87,7 @@ impl MemoryPool for SlicedPool {
         let mut location_base = self.location_base;
         location_base.page = self.pages.len() as u16;
 
-        let mut page = MemoryPage::new(storage, self.alignment, location_base);
+        let mut page = MemoryPage::new(storage, self.alignment, self.rounding, location_base);
         let returned = page.try_reserve(size);
         self.pages.push((page, storage_id));
 
@@ -87,6 +100,7 @@ impl MemoryPool for SlicedPool {
             bytes_in_use: 0,
             bytes_padding: 0,
             bytes_reserved: 0,
+            number_chunks: 0,
         };
 
         for (page, _) in self.pages.iter() {
@@ -97,6 +111,13 @@ impl MemoryPool for SlicedPool {
         usage
     }
 
+    fn chunk_map(&self) -> Vec<ChunkInfo> {
+        self.pages
+            .iter()
+            .map(|(page, _)| page.chunk_info())
+            .collect()
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "trace", skip(self, storage))
@@ -1,4 +1,5 @@
 pub(crate) mod memory_pool;
+pub use memory_pool::{CustomMemoryPool, CustomPoolFactory, StorageAllocator};
 
 mod base;
 
@@ -11,6 +12,9 @@ pub use base::*;
 mod memory_manage;
 pub use memory_manage::*;
 
+mod shared;
+pub use shared::*;
+
 use alloc::vec::Vec;
 
 /// The type of memory pool to use.
@@ -28,6 +32,42 @@ pub enum PoolType {
         /// The maximum size of a slice to allocate in the pool.
         max_slice_size: u64,
     },
+    /// Use a user provided [allocation strategy](CustomMemoryPool).
+    Custom(CustomPoolFactory),
+}
+
+/// How a memory pool rounds up the size of allocations.
+///
+/// Rounding up lets freed memory be reused by allocations of slightly different sizes, which
+/// reduces fragmentation at the cost of the unused space at the end of each allocation. Sizes are
+/// always rounded up to the memory alignment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SizeRounding {
+    /// Round up to the next power of two.
+    PowerOfTwo,
+    /// Round up to the next multiple of the given number of bytes.
+    NextMultiple(usize),
+    /// Only round up to the memory alignment.
+    #[default]
+    Exact,
+}
+
+impl SizeRounding {
+    /// The padding to add after an allocation of `size` bytes.
+    ///
+    /// The rounded size never exceeds `max_size`, unless only the alignment makes it so.
+    pub(crate) fn padding(&self, size: u64, alignment: u64, max_size: u64) -> u64 {
+        let rounded = match self {
+            SizeRounding::PowerOfTwo => size.checked_next_power_of_two().unwrap_or(size),
+            SizeRounding::NextMultiple(multiple) => size
+                .checked_next_multiple_of(*multiple as u64)
+                .unwrap_or(size),
+            SizeRounding::Exact => size,
+        };
+        let rounded = rounded.min(max_size).max(size);
+
+        rounded - size + memory_pool::calculate_padding(rounded, alignment)
+    }
 }
 
 /// Options to create a memory pool.
@@ -35,6 +75,8 @@ pub enum PoolType {
 pub struct MemoryPoolOptions {
     /// What kind of pool to use.
     pub pool_type: PoolType,
+    /// How the size of allocations is rounded up.
+    pub size_rounding: SizeRounding,
     /// Period after which allocations are deemed unused and deallocated.
     ///
     /// This period is measured in the number of allocations in the parent allocator. If a page
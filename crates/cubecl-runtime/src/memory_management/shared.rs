@@ -0,0 +1,79 @@
+use super::MemoryManagement;
+use cubecl_common::stub::Arc;
+
+/// A [memory management](MemoryManagement) that can be shared between the servers of a device, so
+/// they reserve memory from the same pools instead of each keeping their own.
+///
+/// Every clone refers to the same pools. The servers sharing them don't synchronize with each
+/// other, so memory written by one server must be synced before the other one uses it.
+pub struct SharedMemoryManagement<Storage> {
+    inner: Arc<spin::Mutex<MemoryManagement<Storage>>>,
+}
+
+impl<Storage> SharedMemoryManagement<Storage> {
+    /// Share the given memory management.
+    pub fn new(memory_management: MemoryManagement<Storage>) -> Self {
+        Self {
+            inner: Arc::new(spin::Mutex::new(memory_management)),
+        }
+    }
+
+    /// Lock the memory management for the duration of the returned guard.
+    pub fn lock(&self) -> spin::MutexGuard<'_, MemoryManagement<Storage>> {
+        self.inner.lock()
+    }
+}
+
+impl<Storage> Clone for SharedMemoryManagement<Storage> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Storage> From<MemoryManagement<Storage>> for SharedMemoryManagement<Storage> {
+    fn from(memory_management: MemoryManagement<Storage>) -> Self {
+        Self::new(memory_management)
+    }
+}
+
+impl<Storage> core::fmt::Debug for SharedMemoryManagement<Storage> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "SharedMemoryManagement {:?}",
+            core::any::type_name::<Storage>(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        logging::ServerLogger,
+        memory_management::{MemoryConfiguration, MemoryManagementOptions},
+        storage::BytesStorage,
+    };
+    use cubecl_ir::MemoryDeviceProperties;
+
+    #[test_log::test]
+    fn clones_reserve_from_the_same_pools() {
+        let memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &MemoryDeviceProperties {
+                max_page_size: 128 * 1024 * 1024,
+                alignment: 32,
+            },
+            MemoryConfiguration::ExclusivePages,
+            Arc::new(ServerLogger::default()),
+            MemoryManagementOptions::new("test"),
+        );
+        let first = SharedMemoryManagement::new(memory_management);
+        let second = first.clone();
+
+        let _handle = first.lock().reserve(64).unwrap();
+
+        assert_eq!(second.lock().memory_usage().number_allocs, 1);
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Write, time::Duration};
+use hashbrown::HashMap;
+
+/// The device time recorded by a [`Profiler`] for a label.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingStats {
+    /// The number of recorded launches.
+    pub count: usize,
+    /// The total time of the launches.
+    pub total: Duration,
+    /// The time of the fastest launch.
+    pub min: Duration,
+    /// The time of the slowest launch.
+    pub max: Duration,
+}
+
+impl TimingStats {
+    /// The mean time of the launches.
+    pub fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count as u32,
+        }
+    }
+
+    fn record(&mut self, duration: Duration) {
+        self.min = match self.count {
+            0 => duration,
+            _ => self.min.min(duration),
+        };
+        self.max = self.max.max(duration);
+        self.total += duration;
+        self.count += 1;
+    }
+}
+
+/// Accumulates the device time of [profiled launches](crate::client::ComputeClient::launch_profiled)
+/// by label.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    stats: spin::Mutex<HashMap<String, TimingStats>>,
+}
+
+impl Profiler {
+    /// Create a profiler without any recorded time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `duration` of a launch under the `label`.
+    pub fn record(&self, label: &str, duration: Duration) {
+        let mut stats = self.stats.lock();
+        match stats.get_mut(label) {
+            Some(timing) => timing.record(duration),
+            None => {
+                let mut timing = TimingStats::default();
+                timing.record(duration);
+                stats.insert(label.to_string(), timing);
+            }
+        }
+    }
+
+    /// The timings recorded for the `label`, if any.
+    pub fn stats(&self, label: &str) -> Option<TimingStats> {
+        self.stats.lock().get(label).copied()
+    }
+
+    /// Discard every recorded time.
+    pub fn clear(&self) {
+        self.stats.lock().clear();
+    }
+
+    /// A table of the recorded timings, one row per label sorted by decreasing total time.
+    pub fn report(&self) -> String {
+        let mut rows: Vec<(String, TimingStats)> = self
+            .stats
+            .lock()
+            .iter()
+            .map(|(label, timing)| (label.clone(), *timing))
+            .collect();
+        rows.sort_by(|a, b| b.1.total.cmp(&a.1.total).then_with(|| a.0.cmp(&b.0)));
+
+        let width = rows
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0)
+            .max("Label".len());
+
+        let mut report = format!(
+            "{:<width$} | {:>8} | {:>12} | {:>12} | {:>12} | {:>12}\n",
+            "Label", "Count", "Total", "Mean", "Min", "Max"
+        );
+        for (label, timing) in rows {
+            writeln!(
+                report,
+                "{label:<width$} | {:>8} | {:>12} | {:>12} | {:>12} | {:>12}",
+                timing.count,
+                format!("{:?}", timing.total),
+                format!("{:?}", timing.mean()),
+                format!("{:?}", timing.min),
+                format!("{:?}", timing.max),
+            )
+            .unwrap();
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn timings_are_accumulated_by_label() {
+        let profiler = Profiler::new();
+        profiler.record("matmul", Duration::from_millis(3));
+        profiler.record("matmul", Duration::from_millis(1));
+        profiler.record("reduce", Duration::from_millis(5));
+
+        let matmul = profiler.stats("matmul").unwrap();
+        assert_eq!(matmul.count, 2);
+        assert_eq!(matmul.total, Duration::from_millis(4));
+        assert_eq!(matmul.mean(), Duration::from_millis(2));
+        assert_eq!(matmul.min, Duration::from_millis(1));
+        assert_eq!(matmul.max, Duration::from_millis(3));
+
+        let report = profiler.report();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("reduce"));
+        assert!(lines[2].starts_with("matmul"));
+    }
+}
@@ -0,0 +1,44 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How the data of a read was brought back to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadPath {
+    /// The memory was read directly by the host, without a device copy.
+    Mapped,
+    /// The memory was copied into a host visible staging buffer, then read from it.
+    Staged,
+    /// The memory was copied into pageable host memory, which the driver does through its own
+    /// staging buffers in chunks. This is the slowest path.
+    Chunked,
+}
+
+/// The [path](ReadPath) taken by the last completed read on a device.
+///
+/// Servers [record](Self::record) the path of every read, so it can be polled to diagnose slow
+/// reads.
+#[derive(Debug, Default)]
+pub struct LastReadPath {
+    path: AtomicU8,
+}
+
+impl LastReadPath {
+    /// Record the path taken by a read that completed.
+    pub fn record(&self, path: ReadPath) {
+        let value = match path {
+            ReadPath::Mapped => 1,
+            ReadPath::Staged => 2,
+            ReadPath::Chunked => 3,
+        };
+        self.path.store(value, Ordering::Relaxed);
+    }
+
+    /// The path taken by the last completed read, or `None` if no read completed yet.
+    pub fn load(&self) -> Option<ReadPath> {
+        match self.path.load(Ordering::Relaxed) {
+            1 => Some(ReadPath::Mapped),
+            2 => Some(ReadPath::Staged),
+            3 => Some(ReadPath::Chunked),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    client::ComputeClient,
+    runtime::Runtime,
+    server::{Binding, ServerError},
+};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use cubecl_common::{bytes::Bytes, future::DynFut};
+use futures_core::Stream;
+
+/// The chunks of a buffer read back to the host one after the other, see
+/// [`ComputeClient::read_stream`].
+///
+/// Up to a [depth](Self::with_depth) of chunks are read at once, so that the transfer of the next
+/// chunks overlaps with the processing of the current one, while the host memory used stays
+/// bounded by the size of the chunks in flight.
+///
+/// The chunks are read from a copy of the buffer made when the stream was created, which is kept
+/// on the device until the stream is dropped.
+pub struct ReadStream<R: Runtime> {
+    client: ComputeClient<R>,
+    binding: Binding,
+    chunk: usize,
+    depth: usize,
+    /// The offset of the next chunk to read.
+    offset: usize,
+    size: usize,
+    pending: VecDeque<DynFut<Result<Vec<Bytes>, ServerError>>>,
+    failed: bool,
+}
+
+impl<R: Runtime> ReadStream<R> {
+    pub(crate) fn new(client: ComputeClient<R>, binding: Binding, chunk: usize) -> Self {
+        let size = binding.size_in_used() as usize;
+
+        Self {
+            client,
+            binding,
+            chunk: chunk.max(1),
+            depth: 2,
+            offset: 0,
+            size,
+            pending: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    /// Set the number of chunks read at once, double buffered by default.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Start reading chunks until the depth is reached or the whole buffer is read.
+    fn fill(&mut self) {
+        while self.pending.len() < self.depth && self.offset < self.size {
+            let len = self.chunk.min(self.size - self.offset);
+            let read = self
+                .client
+                .read_gather(self.binding.clone(), &[(self.offset, len)]);
+
+            self.pending.push_back(Box::pin(read));
+            self.offset += len;
+        }
+    }
+}
+
+impl<R: Runtime> Stream for ReadStream<R> {
+    type Item = Result<Bytes, ServerError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.failed {
+            return Poll::Ready(None);
+        }
+
+        this.fill();
+
+        let Some(read) = this.pending.front_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match read.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(result) => {
+                this.pending.pop_front();
+                // The chunks after a failed read are skipped, the stream ends with the error.
+                let result = result.map(|mut bytes| bytes.remove(0));
+                if result.is_err() {
+                    this.failed = true;
+                    this.pending.clear();
+                }
+                Poll::Ready(Some(result))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.failed {
+            true => 0,
+            false => self.pending.len() + (self.size - self.offset).div_ceil(self.chunk),
+        };
+
+        (remaining, Some(remaining))
+    }
+}
@@ -1,15 +1,29 @@
-use super::Handle;
+use super::{Handle, ScalarArgs};
 use crate::{
     client::ComputeClient,
     compiler::CompilationError,
     config::{GlobalConfig, compilation::BoundsCheckMode},
-    kernel::KernelMetadata,
+    generation::Generations,
+    id::KernelId,
+    in_flight::InFlightBytes,
+    kernel::{KernelBinary, KernelMetadata, SourceTransform},
     logging::ServerLogger,
-    memory_management::{ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage},
+    memory_management::{
+        AccessHint, ChunkInfo, ManagedMemoryHandle, MemoryAllocationMode, MemoryUsage,
+        MemoryUsageSnapshot,
+    },
+    read_path::LastReadPath,
     runtime::Runtime,
-    server::Binding,
+    server::{BindGroup, Binding},
+    shape_bucket::{ShapeBucket, ShapeBuckets},
+    spill::SpillPolicy,
+    staged::StagedBuffers,
     storage::{ComputeStorage, ManagedResource},
+    submission::SubmissionFences,
     tma::{OobFill, TensorMapFormat, TensorMapInterleave, TensorMapPrefetch, TensorMapSwizzle},
+    transient::StreamTransients,
+    validation::UninitReadPolicy,
+    verify::Verifier,
 };
 use ahash::AHasher;
 use alloc::boxed::Box;
@@ -17,6 +31,7 @@ use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
 use alloc::sync::Arc;
+use alloc::vec;
 use alloc::vec::Vec;
 use core::{
     fmt::Debug,
@@ -31,9 +46,9 @@ use cubecl_common::{
     stream_id::StreamId,
     stub::RwLock,
 };
-use cubecl_ir::{DeviceProperties, ElemType, StorageType};
+use cubecl_ir::{ConstantValue, DeviceProperties, ElemType, StorageType};
 use cubecl_zspace::{Shape, Strides, metadata::Metadata};
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use thiserror::Error;
 
 #[derive(Error, Clone)]
@@ -97,6 +112,43 @@ pub struct ServerUtilities<Server: ComputeServer> {
     pub check_mode: BoundsCheckMode,
     /// A set containing the ids for which the inter-device communication has already been initialized.
     pub initialized_comms: RwLock<HashSet<CommunicationId>>,
+    /// The memory usage of this device, readable without locking the server.
+    ///
+    /// Servers should [publish](crate::memory_management::MemoryManagementOptions::usage_snapshot)
+    /// the usage of the device memory to it.
+    pub memory_usage: Arc<MemoryUsageSnapshot>,
+    /// The shadow buffers of the kernels [verified](crate::verify::VerifyMode) on this device.
+    pub verifier: Verifier,
+    /// The buffers created with [`create_staged`](crate::client::ComputeClient::create_staged)
+    /// on this device.
+    pub staged: StagedBuffers,
+    /// The bytes of the transfers submitted to this device that haven't completed yet.
+    pub in_flight: Arc<InFlightBytes>,
+    /// The transient allocations of each stream, to release at its next sync or flush.
+    pub transient: StreamTransients,
+    /// The allocations of the generations that haven't been freed yet.
+    pub generations: Generations,
+    /// The specialization constants used by the launches of each shape bucket.
+    pub shape_buckets: ShapeBuckets,
+    /// The path taken by the last completed read on this device.
+    ///
+    /// Servers should record the path of every read to it.
+    pub read_path: LastReadPath,
+    /// What happens when a read returns a buffer that was never written.
+    pub uninit_reads: UninitReadPolicy,
+    /// Whether allocations are spilled to host memory when the device memory is exhausted.
+    ///
+    /// Servers supporting it should [record](SpillPolicy::record) the spilled memory to it.
+    pub spill: SpillPolicy,
+    /// The rewrite of the source of the kernels before they are compiled.
+    ///
+    /// Servers compiling kernels from their source should [apply](SourceTransform::apply) it to
+    /// every kernel before compiling it.
+    pub source_transform: Arc<SourceTransform>,
+    /// The fences of the [submissions](SubmissionId) the host may wait on.
+    pub submissions: SubmissionFences,
+    /// The cube count of the last [dynamic](CubeCount::Dynamic) launch of each stream.
+    pub last_dynamic_count: spin::Mutex<HashMap<StreamId, Binding>>,
 }
 
 /// Defines how the memory layout is determined.
@@ -160,6 +212,19 @@ impl<S: ComputeServer> ServerUtilities<S> {
             layout_policy: allocator,
             check_mode: GlobalConfig::get().compilation.check_mode,
             initialized_comms: RwLock::new(HashSet::default()),
+            memory_usage: Arc::new(MemoryUsageSnapshot::default()),
+            verifier: Verifier::default(),
+            staged: StagedBuffers::default(),
+            in_flight: Arc::new(InFlightBytes::default()),
+            transient: StreamTransients::default(),
+            generations: Generations::default(),
+            shape_buckets: ShapeBuckets::default(),
+            read_path: LastReadPath::default(),
+            uninit_reads: UninitReadPolicy::default(),
+            spill: SpillPolicy::default(),
+            source_transform: Arc::new(SourceTransform::default()),
+            submissions: SubmissionFences::default(),
+            last_dynamic_count: spin::Mutex::new(HashMap::new()),
         }
     }
 }
@@ -203,6 +268,38 @@ pub enum LaunchError {
     /// Can't launch because of an IO Error.
     #[error("An io error happened during launch\nCaused by:\n  {0}")]
     IoError(#[from] IoError),
+
+    /// A buffer doesn't hold a whole number of the elements the kernel expects.
+    #[error(
+        "Buffer {index} has a size of {size} bytes, which isn't a multiple of its element size of {elem_size} bytes\nBacktrace\n{backtrace}"
+    )]
+    BindingSize {
+        /// The index of the buffer in the bindings.
+        index: usize,
+        /// The size of the buffer in bytes.
+        size: u64,
+        /// The size of the elements expected by the kernel in bytes.
+        elem_size: usize,
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
+    /// Two buffers are bound to overlapping memory in a way the kernel or backend can't handle.
+    #[error(
+        "Buffers {first} and {second} are bound to overlapping memory, but {reason}\nBacktrace\n{backtrace}"
+    )]
+    AliasedBindings {
+        /// The index of the first buffer in the bindings.
+        first: usize,
+        /// The index of the second buffer in the bindings.
+        second: usize,
+        /// Why the overlap isn't supported.
+        reason: String,
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
 }
 
 /// Resource limit errors.
@@ -235,6 +332,47 @@ pub enum ResourceLimitError {
         #[cfg_attr(std_io, serde(skip))]
         backtrace: BackTrace,
     },
+    /// Cube count exceeds what can be co-resident for a cooperative launch
+    #[error(
+        "Too many cubes for a cooperative launch.\nRequested {requested} cubes, at most {max} can be co-resident.\nBacktrace\n{backtrace}"
+    )]
+    CoResidentCubes {
+        /// Requested value
+        requested: u64,
+        /// Maximum value
+        max: u64,
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+    /// Uniform buffer exceeds maximum
+    #[error(
+        "Uniform buffer {index} is too large.\nRequested {requested} bytes, maximum {max} bytes available.\nBacktrace\n{backtrace}"
+    )]
+    UniformSize {
+        /// The index of the buffer in the uniform bindings.
+        index: usize,
+        /// Value requested
+        requested: u64,
+        /// Maximum value
+        max: u64,
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+    /// `CubeCount` exceeds maximum
+    #[error(
+        "Cube count exceeds maximum bounds.\nRequested {requested:?}, max is {max:?}.\nBacktrace\n{backtrace}"
+    )]
+    CubeCount {
+        /// Requested value
+        requested: (u32, u32, u32),
+        /// Maximum value
+        max: (u32, u32, u32),
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
     /// `CubeDim` exceeds maximum
     #[error(
         "Cube dim exceeds maximum bounds.\nRequested {requested:?}, max is {max:?}.\nBacktrace\n{backtrace}"
@@ -288,6 +426,14 @@ pub enum ServerError {
     #[error("An execution error happened during profiling\nCaused by:\n  {0}")]
     Io(#[from] IoError),
 
+    /// The operation was cancelled before it completed.
+    #[error("The operation was cancelled\nBacktrace:\n{backtrace}")]
+    Cancelled {
+        /// The backtrace for this error.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
     /// The server is an invalid state.
     #[error("The server is in an invalid state\nCaused by:\n  {errors:?}")]
     ServerUnhealthy {
@@ -299,6 +445,48 @@ pub enum ServerError {
     },
 }
 
+/// Report of a [synchronization](crate::client::ComputeClient::sync_timeout) that completed in
+/// time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The time the device took to complete every task.
+    pub elapsed: core::time::Duration,
+    /// Whether there was no [pending work](crate::client::ComputeClient::has_pending_work) to
+    /// wait on.
+    pub was_idle: bool,
+}
+
+/// Error returned by a [synchronization](crate::client::ComputeClient::sync_timeout) with a
+/// timeout.
+#[derive(Error, Debug, Clone)]
+pub enum SyncTimeout {
+    /// The device didn't complete its tasks in time.
+    #[error("The device didn't complete its tasks within {timeout:?}\nBacktrace:\n{backtrace}")]
+    Elapsed {
+        /// The timeout that elapsed.
+        timeout: core::time::Duration,
+        /// The backtrace for this error.
+        backtrace: BackTrace,
+    },
+
+    /// The device completed its tasks in time, but with an error.
+    #[error("The synchronization failed\nCaused by:\n  {0}")]
+    Server(#[from] ServerError),
+}
+
+/// Identifies the work submitted so far on a stream, for later launches to
+/// [depend on](crate::client::ComputeClient::launch_after).
+///
+/// Work on a stream executes in order, so depending on a submission orders a launch after it and
+/// everything submitted before it on the same stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubmissionId {
+    /// The stream the work was submitted on.
+    pub stream: StreamId,
+    /// The position of the submission on its stream, increasing with every id taken.
+    pub index: u64,
+}
+
 /// How errors are handled in a stream when executing a task.
 #[derive(Clone, Copy)]
 pub struct StreamErrorMode {
@@ -327,7 +515,50 @@ where
     type Storage: ComputeStorage;
 
     /// Initializes [memory](ManagedMemoryHandle) on the given [stream](StreamId) with the given size.
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, stream_id: StreamId);
+    ///
+    /// Fails when the memory can't be reserved, e.g. when it is larger than the device allows.
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError>;
+
+    /// Same as [`initialize_memory`](Self::initialize_memory), with an [access hint](AccessHint)
+    /// to bias where the memory is placed. Servers without tiered memory ignore the hint.
+    fn initialize_memory_with_hint(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        _hint: AccessHint,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        self.initialize_memory(memory, size, stream_id)
+    }
+
+    /// Same as [`initialize_memory`](Self::initialize_memory), but the memory starts at an offset
+    /// of its storage that is a multiple of `alignment` bytes, a power of two.
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError>;
+
+    /// Initializes the memory of the [handle](Handle) with the contents of `data`.
+    ///
+    /// By default, the memory is initialized and `data` is written to it; host visible backends
+    /// should override this to take ownership of the allocation of `data` instead of copying it.
+    fn create_owned(&mut self, handle: Handle, data: Vec<u8>, stream_id: StreamId) {
+        let size = data.len();
+        if let Err(err) = self.initialize_memory(handle.memory.clone(), handle.size(), stream_id) {
+            panic!("Failed to reserve {size} bytes: {err}");
+        }
+
+        let descriptor = CopyDescriptor::new(handle.binding(), [size].into(), [1].into(), 1);
+        self.write(vec![(descriptor, Bytes::from_bytes_vec(data))], stream_id);
+    }
 
     /// Reserves N [Bytes] of the provided sizes to be used as staging to load data.
     fn staging(
@@ -357,9 +588,103 @@ where
     /// Writes the specified bytes into the buffers given
     fn write(&mut self, descriptors: Vec<(CopyDescriptor, Bytes)>, stream_id: StreamId);
 
+    /// Fills the given [binding](Binding) by repeating the `pattern`.
+    ///
+    /// The size of the binding must be a multiple of the pattern length. By default, the
+    /// repeated pattern is uploaded from the host; backends should override this with a
+    /// device-side memset when available.
+    fn fill(&mut self, binding: Binding, pattern: Vec<u8>, stream_id: StreamId) {
+        let size = binding.size_in_used() as usize;
+        let data = pattern
+            .iter()
+            .copied()
+            .cycle()
+            .take(size)
+            .collect::<Vec<_>>();
+        let descriptor = CopyDescriptor::new(binding, [size].into(), [1].into(), 1);
+
+        self.write(vec![(descriptor, Bytes::from_bytes_vec(data))], stream_id);
+    }
+
+    /// Copies the contents of the `src` [binding](Binding) into the `dst` binding, which must be
+    /// at least as large.
+    ///
+    /// By default, the contents are read back and uploaded again, which blocks the server until
+    /// the prior work on the stream completes; backends should override this with a
+    /// device-to-device copy when available.
+    fn copy_buffer(&mut self, src: Binding, dst: Binding, stream_id: StreamId) {
+        let size = src.size_in_used() as usize;
+        let descriptor = CopyDescriptor::new(src, [size].into(), [1].into(), 1);
+
+        // A failed read is reported again when the source is read, so there is nothing to copy.
+        let data = self.read(vec![descriptor], stream_id);
+        if let Ok(mut data) = self.block_on(data) {
+            let descriptor = CopyDescriptor::new(dst, [size].into(), [1].into(), 1);
+            self.write(vec![(descriptor, data.remove(0))], stream_id);
+        }
+    }
+
+    /// Atomically adds `val` to the `u32` at `offset` bytes in the [binding](Binding), and
+    /// returns the previous value.
+    ///
+    /// By default, the value is read back and the sum is written on the stream, which blocks
+    /// the server until the prior work on the stream completes. This is atomic with respect to
+    /// other host operations and to the kernels of the stream, but not to kernels running
+    /// concurrently on other streams.
+    fn fetch_add_u32(
+        &mut self,
+        binding: Binding,
+        offset: u64,
+        val: u32,
+        stream_id: StreamId,
+    ) -> Result<u32, ServerError> {
+        let size = binding.size_in_used();
+        if offset.checked_add(4).is_none_or(|end| end > size) {
+            return Err(IoError::OutOfBounds {
+                offset,
+                len: 4,
+                size,
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+
+        let mut binding = binding;
+        binding.offset_start = Some(binding.offset_start.unwrap_or(0) + offset);
+        binding.offset_end = Some(binding.offset_end.unwrap_or(0) + size - offset - 4);
+
+        let descriptor = CopyDescriptor::new(binding.clone(), [1].into(), [1].into(), 4);
+        let bytes = self.read(vec![descriptor], stream_id);
+        let bytes = self.block_on(bytes)?;
+        let previous = u32::from_le_bytes([bytes[0][0], bytes[0][1], bytes[0][2], bytes[0][3]]);
+
+        let descriptor = CopyDescriptor::new(binding, [1].into(), [1].into(), 4);
+        let data = previous.wrapping_add(val).to_le_bytes().to_vec();
+        self.write(vec![(descriptor, Bytes::from_bytes_vec(data))], stream_id);
+
+        Ok(previous)
+    }
+
+    /// Blocks on a future of the server, such as a read, from within the server.
+    ///
+    /// Backends whose futures only complete when the device is polled override this to poll
+    /// while waiting.
+    fn block_on<T>(&mut self, future: DynFut<T>) -> T {
+        cubecl_common::future::block_on(future)
+    }
+
     /// Wait for the completion of every task in the server.
     fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>>;
 
+    /// Flush the stream and return a future completing with the work submitted so far, but not
+    /// with the work submitted after.
+    ///
+    /// Unlike [`sync`](Self::sync), the errors of the stream are left for the next
+    /// synchronization to report. Defaults to [`sync`](Self::sync).
+    fn fence(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        self.sync(stream_id)
+    }
+
     /// Given a resource handle, returns the storage resource.
     fn get_resource(
         &mut self,
@@ -384,15 +709,176 @@ where
         stream_id: StreamId,
     );
 
+    /// Executes the `kernel` cooperatively, with all cubes co-resident so they can synchronize
+    /// grid-wide.
+    ///
+    /// Only called when [`Features::cooperative_launch`](cubecl_ir::features::Features::cooperative_launch) is
+    /// supported. Servers must return an error when the cube count exceeds what can be
+    /// co-resident on the device.
+    ///
+    /// The default implementation returns an error without launching the kernel.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`launch`](Self::launch).
+    unsafe fn launch_cooperative(
+        &mut self,
+        _kernel: Self::Kernel,
+        _count: CubeCount,
+        _bindings: KernelArguments,
+        _kind: ExecutionMode,
+        _stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        Err(LaunchError::Unknown {
+            reason: "Cooperative launches aren't supported by this server".into(),
+            backtrace: BackTrace::capture(),
+        }
+        .into())
+    }
+
+    /// Resolves the buffers of the bind `group` and caches them, to be reused by every
+    /// [launch with the group](Self::launch_with_group).
+    ///
+    /// The default implementation caches nothing.
+    fn create_bind_group(&mut self, _group: BindGroup, _stream_id: StreamId) {}
+
+    /// Executes the `kernel` with the buffers of the bind `group` bound first, followed by the
+    /// buffers of `bindings`.
+    ///
+    /// The default implementation launches the kernel with the buffers of the group inserted in
+    /// the `bindings`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`Self::launch`].
+    unsafe fn launch_with_group(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        group: BindGroup,
+        bindings: KernelArguments,
+        kind: ExecutionMode,
+        stream_id: StreamId,
+    ) {
+        let bindings = bindings.with_bind_group(&group);
+        unsafe { self.launch(kernel, count, bindings, kind, stream_id) }
+    }
+
     /// Flush all outstanding tasks in the server.
     fn flush(&mut self, stream_id: StreamId) -> Result<(), ServerError>;
 
+    /// Order the work submitted next on the stream after the work submitted so far on the
+    /// `dependencies`, even when they share no binding.
+    ///
+    /// Does nothing by default, which is only correct for servers executing the work of all
+    /// streams in submission order.
+    fn wait_streams(&mut self, _dependencies: Vec<StreamId>, _stream_id: StreamId) {}
+
     /// The current memory usage of the server.
     fn memory_usage(&mut self, stream_id: StreamId) -> Result<MemoryUsage, ServerError>;
 
+    /// The [layout](ChunkInfo) of every memory chunk of the server.
+    fn chunk_map(&mut self, stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError>;
+
     /// Ask the server to release memory that it can release.
     fn memory_cleanup(&mut self, stream_id: StreamId);
 
+    /// Hint that the memory of the given [binding](Binding) will soon be accessed by the device,
+    /// or by the host when `to_device` is false.
+    ///
+    /// Only useful for managed memory that migrates on access, it is a no-op otherwise.
+    fn prefetch(&mut self, _binding: Binding, _to_device: bool, _stream_id: StreamId) {}
+
+    /// Release the memory of the given [bindings](Binding), even if handles to it are still
+    /// alive.
+    ///
+    /// Those handles are invalid afterward. Backends that don't support it keep the memory until
+    /// the handles are dropped.
+    fn release(&mut self, _bindings: Vec<Binding>, _stream_id: StreamId) {}
+
+    /// Compile the `kernels` for each of the execution `modes`, returning their binaries.
+    ///
+    /// The kernels that are precompiled or cached aren't compiled again. The binaries aren't kept
+    /// by the server. Returns nothing on backends that don't support loading precompiled kernels.
+    fn compile_binaries(
+        &mut self,
+        _kernels: Vec<Self::Kernel>,
+        _modes: Vec<ExecutionMode>,
+    ) -> Result<Vec<KernelBinary>, LaunchError> {
+        Ok(Vec::new())
+    }
+
+    /// Register precompiled kernels, used instead of compiling the matching kernels.
+    fn load_compiled_kernels(&mut self, _kernels: Vec<KernelBinary>) {}
+
+    /// Suggest the number of units per cube that maximizes the occupancy of the device for the
+    /// given kernel, based on the resources it uses when compiled with its own cube dim.
+    ///
+    /// Returns `None` when the backend can't compute it.
+    fn suggest_cube_size(&mut self, _kernel: Self::Kernel, _stream_id: StreamId) -> Option<u32> {
+        None
+    }
+
+    /// Whether work submitted on the [stream](StreamId) hasn't completed yet.
+    ///
+    /// Servers that can't tell report pending work, so callers never skip a needed sync.
+    fn has_pending_work(&mut self, _stream_id: StreamId) -> bool {
+        true
+    }
+
+    /// Poll the device once without blocking, running the callbacks of the work that completed.
+    ///
+    /// Returns whether submitted work is still in flight, i.e. whether the device needs to be
+    /// polled again. Servers whose work completes without polling return `false`.
+    fn poll_device(&mut self) -> bool {
+        false
+    }
+
+    /// The time of the work of the [stream](StreamId) that completed since the last call, without
+    /// waiting on the work still running.
+    ///
+    /// The time may be estimated on the host, e.g. from the submission of the work until its
+    /// completion is observed.
+    ///
+    /// Returns `None` when no work completed in the meantime, or when the server can't time its
+    /// work without waiting on it.
+    fn poll_gpu_time(&mut self, _stream_id: StreamId) -> Option<core::time::Duration> {
+        None
+    }
+
+    /// Bound the number of tasks submitted to the device that can be in flight on the given
+    /// stream. Once exceeded, the submission blocks until the oldest tasks complete.
+    ///
+    /// This is a no-op on backends that don't queue submissions.
+    fn set_max_in_flight(&mut self, _max_tasks: usize, _stream_id: StreamId) {}
+
+    /// Bound the number of compiled pipelines kept by the server, evicting the least recently
+    /// used ones past it.
+    ///
+    /// This is a no-op on backends that don't support evicting pipelines.
+    fn set_pipeline_cache_size(&mut self, _size: usize) {}
+
+    /// The number of launches that found their pipeline cached, and the number that didn't.
+    fn pipeline_cache_stats(&mut self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    /// Whether the kernel is compiled, so that launching it doesn't compile it first.
+    ///
+    /// Servers that don't keep track of their compiled kernels report them as not compiled.
+    fn is_compiled(&mut self, _kernel_id: &KernelId) -> bool {
+        false
+    }
+
+    /// The free and total memory of the device in bytes, as reported by the driver.
+    ///
+    /// Unlike [`memory_usage`](Self::memory_usage), this accounts for memory used outside of
+    /// the server's pools, e.g. by other processes. Returns `None` when the backend can't
+    /// report it.
+    fn device_memory(&mut self) -> Option<(u64, u64)> {
+        None
+    }
+
     /// Enable collecting timestamps.
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError>;
 
@@ -544,9 +1030,18 @@ pub struct MemoryLayoutDescriptor {
     pub shape: Shape,
     /// Size of each element in the tensor (used for conversion of shape to bytes)
     pub elem_size: usize,
+    /// How the tensor is expected to be accessed, used to bias where its memory is placed.
+    #[new(default)]
+    pub access_hint: Option<AccessHint>,
 }
 
 impl MemoryLayoutDescriptor {
+    /// Set the [access hint](AccessHint) of the allocation.
+    pub fn with_access_hint(mut self, hint: AccessHint) -> Self {
+        self.access_hint = Some(hint);
+        self
+    }
+
     /// Create an optimized allocation descriptor
     pub fn optimized(shape: Shape, elem_size: usize) -> Self {
         MemoryLayoutDescriptor::new(MemoryLayoutStrategy::Optimized, shape, elem_size)
@@ -710,6 +1205,58 @@ pub enum IoError {
         backtrace: BackTrace,
     },
 
+    /// The fill pattern doesn't evenly divide the buffer
+    #[error(
+        "can't fill a buffer of size {size} with a pattern of {pattern_len} bytes\n{backtrace}"
+    )]
+    InvalidFillPattern {
+        /// The size of the buffer in bytes.
+        size: u64,
+        /// The length of the pattern in bytes.
+        pattern_len: usize,
+        /// The backtrace.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
+    /// The requested alignment isn't a power of two.
+    #[error(
+        "can't align an allocation to {alignment} bytes, which isn't a power of two\n{backtrace}"
+    )]
+    InvalidAlignment {
+        /// The requested alignment in bytes.
+        alignment: usize,
+        /// The backtrace.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
+    /// The memory can't be allocated without exceeding a limit of the device
+    #[error("can't allocate {size} bytes: {reason}\n{backtrace}")]
+    OutOfMemory {
+        /// The size of the allocation in bytes.
+        size: u64,
+        /// The limit that would be exceeded.
+        reason: String,
+        /// The backtrace.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
+    /// The written or read range doesn't fit in the buffer
+    #[error("can't access {len} bytes at offset {offset} in a buffer of size {size}\n{backtrace}")]
+    OutOfBounds {
+        /// The offset of the access in bytes.
+        offset: u64,
+        /// The length of the access in bytes.
+        len: u64,
+        /// The size of the buffer in bytes.
+        size: u64,
+        /// The backtrace.
+        #[cfg_attr(std_io, serde(skip))]
+        backtrace: BackTrace,
+    },
+
     /// The current IO operation is not supported
     #[error("The current IO operation is not supported\n{backtrace}")]
     UnsupportedIoOperation {
@@ -730,15 +1277,37 @@ impl core::fmt::Debug for IoError {
 }
 
 /// Arguments to execute a kernel.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct KernelArguments {
     /// Buffer bindings
     pub buffers: Vec<Binding>,
+    /// Uniform buffer bindings, bound after the storage [buffers](Self::buffers).
+    ///
+    /// They are read-only and limited in size by
+    /// [`max_uniform_buffer_size`](cubecl_ir::HardwareProperties::max_uniform_buffer_size), but
+    /// backends that distinguish them can serve them from faster constant memory.
+    pub uniforms: Vec<Binding>,
     /// Packed scalars and metadata. First scalars sorted by type, then static metadata,
     /// then dynamic metadata.
     pub info: MetadataBindingInfo,
     /// Tensor map bindings
     pub tensor_maps: Vec<TensorMapBinding>,
+    /// Values of the specialization constants of the kernel, indexed by their id.
+    ///
+    /// Unlike scalars, they are baked into the pipeline, so each distinct set of values creates
+    /// a new pipeline from the same compiled kernel.
+    pub spec_constants: Vec<ConstantValue>,
+    /// The [shape bucket](ShapeBucket) of the launch.
+    ///
+    /// When the client has constants [registered](crate::client::ComputeClient::register_shape_bucket)
+    /// for the kernel in this bucket, they replace the [specialization constants](Self::spec_constants).
+    pub shape_bucket: Option<ShapeBucket>,
+    /// Bytes of dynamic shared memory to allocate for the launch, on top of the shared memory
+    /// of the compiled kernel.
+    ///
+    /// Limited by [`max_dynamic_shared_mem`](crate::client::ComputeClient::max_dynamic_shared_mem),
+    /// and ignored by backends without dynamic shared memory.
+    pub dynamic_shared_mem: usize,
 }
 
 impl core::fmt::Display for KernelArguments {
@@ -747,6 +1316,9 @@ impl core::fmt::Display for KernelArguments {
         for b in self.buffers.iter() {
             f.write_fmt(format_args!("\n - buffer: {b:?}\n"))?;
         }
+        for b in self.uniforms.iter() {
+            f.write_fmt(format_args!("\n - uniform: {b:?}\n"))?;
+        }
 
         Ok(())
     }
@@ -770,24 +1342,65 @@ impl KernelArguments {
         self
     }
 
+    /// Add a uniform buffer binding
+    pub fn with_uniform(mut self, binding: Binding) -> Self {
+        self.uniforms.push(binding);
+        self
+    }
+
+    /// Extend the uniform buffers with `bindings`
+    pub fn with_uniforms(mut self, bindings: Vec<Binding>) -> Self {
+        self.uniforms.extend(bindings);
+        self
+    }
+
     /// Set the info to `info`
     pub fn with_info(mut self, info: MetadataBindingInfo) -> Self {
         self.info = info;
         self
     }
 
+    /// Set the info to the packed `scalars`
+    pub fn with_scalars(self, scalars: ScalarArgs) -> Self {
+        self.with_info(scalars.into())
+    }
+
     /// Extend the tensor maps with `bindings`
     pub fn with_tensor_maps(mut self, bindings: Vec<TensorMapBinding>) -> Self {
         self.tensor_maps.extend(bindings);
         self
     }
+
+    /// Set the specialization constants to `values`
+    pub fn with_spec_constants(mut self, values: Vec<ConstantValue>) -> Self {
+        self.spec_constants = values;
+        self
+    }
+
+    /// Set the [shape bucket](ShapeBucket) of the launch to `bucket`
+    pub fn with_shape_bucket(mut self, bucket: ShapeBucket) -> Self {
+        self.shape_bucket = Some(bucket);
+        self
+    }
+
+    /// Set the bytes of dynamic shared memory of the launch to `bytes`
+    pub fn with_dynamic_shared_mem(mut self, bytes: usize) -> Self {
+        self.dynamic_shared_mem = bytes;
+        self
+    }
+
+    /// Insert the buffers of the `group` before the current buffers.
+    pub fn with_bind_group(mut self, group: &BindGroup) -> Self {
+        self.buffers.splice(0..0, group.bindings());
+        self
+    }
 }
 
 /// Binding of a set of scalars of the same type to execute a kernel.
 ///
 /// The [`ComputeServer`] is responsible to convert those info into actual [`Binding`] when launching
 /// kernels.
-#[derive(new, Debug, Default)]
+#[derive(new, Debug, Default, Clone)]
 pub struct MetadataBindingInfo {
     /// Scalar and metadata values
     pub data: Vec<u64>,
@@ -816,7 +1429,7 @@ pub struct CopyDescriptor {
 }
 
 /// A tensor map used with TMA ops
-#[derive(new, Debug)]
+#[derive(new, Debug, Clone)]
 pub struct TensorMapBinding {
     /// The binding for the backing tensor
     pub binding: Binding,
@@ -0,0 +1,140 @@
+use super::{Binding, Handle};
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Unique identifier of a [`BindGroup`].
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, PartialOrd, Ord)]
+pub struct BindGroupId {
+    value: u64,
+}
+
+impl BindGroupId {
+    fn new() -> Self {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            value: COUNTER.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+/// A set of buffers bound once and reused across many kernel launches.
+///
+/// Servers resolve the buffers of a group when it is created and cache the result under its
+/// [id](Self::id), so launching with the group doesn't resolve them again. The group keeps its
+/// buffers alive; the cached state is discarded once every clone of the group is dropped.
+#[derive(Debug, Clone)]
+pub struct BindGroup {
+    id: BindGroupId,
+    handles: Arc<Vec<Handle>>,
+}
+
+impl BindGroup {
+    /// Create a new bind group from the given handles, in binding order.
+    pub fn new(handles: Vec<Handle>) -> Self {
+        Self {
+            id: BindGroupId::new(),
+            handles: Arc::new(handles),
+        }
+    }
+
+    /// The id of the group.
+    pub fn id(&self) -> BindGroupId {
+        self.id
+    }
+
+    /// The bindings of the buffers of the group, in binding order.
+    pub fn bindings(&self) -> impl Iterator<Item = Binding> + '_ {
+        self.handles.iter().map(|handle| handle.clone().binding())
+    }
+
+    /// The number of buffers in the group.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Whether the group has no buffers.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+/// Server-side state of the [bind groups](BindGroup) created on a server, such as resolved
+/// resources or backend descriptor objects.
+#[derive(Debug)]
+pub struct BindGroupCache<T> {
+    entries: HashMap<BindGroupId, (Weak<Vec<Handle>>, T)>,
+}
+
+impl<T> Default for BindGroupCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<T> BindGroupCache<T> {
+    /// Cache the `state` of the `group`, discarding the state of groups that were dropped.
+    pub fn insert(&mut self, group: &BindGroup, state: T) {
+        self.entries
+            .retain(|_, (handles, _)| handles.strong_count() > 0);
+        self.entries
+            .insert(group.id, (Arc::downgrade(&group.handles), state));
+    }
+
+    /// The cached state of the group with the given `id`.
+    pub fn get(&self, id: BindGroupId) -> Option<&T> {
+        self.entries.get(&id).map(|(_, state)| state)
+    }
+
+    /// The cached state of the group with the given `id`.
+    pub fn get_mut(&mut self, id: BindGroupId) -> Option<&mut T> {
+        self.entries.get_mut(&id).map(|(_, state)| state)
+    }
+
+    /// The cached states of every group, including dropped groups that weren't discarded yet.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.values_mut().map(|(_, state)| state)
+    }
+
+    /// The number of groups with a cached state, including dropped groups that weren't
+    /// discarded yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no group has a cached state.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use cubecl_common::stream_id::StreamId;
+
+    #[test]
+    fn dropped_groups_are_discarded_on_insert() {
+        let stream_id = StreamId::current();
+        let mut cache = BindGroupCache::default();
+
+        let dropped = BindGroup::new(vec![Handle::new(stream_id, 4)]);
+        let kept = BindGroup::new(vec![Handle::new(stream_id, 4)]);
+        cache.insert(&dropped, 0);
+        cache.insert(&kept.clone(), 1);
+        core::mem::drop(dropped);
+
+        let other = BindGroup::new(vec![Handle::new(stream_id, 4)]);
+        cache.insert(&other, 2);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(kept.id()), Some(&1));
+        assert_eq!(cache.get(other.id()), Some(&2));
+    }
+}
@@ -4,6 +4,7 @@ use cubecl_zspace::{Shape, Strides};
 use crate::{
     memory_management::{ManagedMemoryBinding, ManagedMemoryHandle},
     server::CopyDescriptor,
+    transient::TransientExpiry,
 };
 
 /// Server handle containing the [memory handle](crate::server::Handle).
@@ -18,6 +19,9 @@ pub struct Handle {
     pub stream: StreamId,
     /// Length of the underlying buffer ignoring offsets
     pub(crate) size: u64,
+    /// Set for [transient](crate::client::ComputeClient::empty_transient) handles and the handles
+    /// allocated in a [generation](crate::client::ComputeClient::begin_generation).
+    pub(crate) transient: Option<TransientExpiry>,
 }
 
 impl core::fmt::Debug for Handle {
@@ -40,6 +44,7 @@ impl Clone for Handle {
             offset_end: self.offset_end,
             stream: self.stream,
             size: self.size,
+            transient: self.transient.clone(),
         }
     }
 }
@@ -53,6 +58,7 @@ impl Handle {
             offset_end: None,
             stream,
             size,
+            transient: None,
         }
     }
     /// Creates a new handle of the given size.
@@ -63,6 +69,7 @@ impl Handle {
             offset_end: None,
             stream,
             size,
+            transient: None,
         }
     }
     /// Checks whether the handle can be mutated in-place without affecting other computation.
@@ -71,7 +78,21 @@ impl Handle {
     }
 
     /// Returns the [`Binding`] corresponding to the current handle.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the handle is [transient](crate::client::ComputeClient::empty_transient)
+    /// or part of a [generation](crate::client::ComputeClient::begin_generation), and its memory
+    /// was already released.
     pub fn binding(self) -> Binding {
+        #[cfg(debug_assertions)]
+        if let Some(transient) = &self.transient {
+            assert!(
+                !transient.is_expired(),
+                "Transient handle used after the sync, flush or generation free that released its memory"
+            );
+        }
+
         Binding {
             memory: self.memory.binding(),
             offset_start: self.offset_start,
@@ -124,6 +145,29 @@ impl Handle {
     pub fn size(&self) -> u64 {
         self.size
     }
+
+    /// Get the size of the region physically reserved for the handle, in bytes.
+    ///
+    /// This is the [logical size](Self::size_in_used) padded to the given memory `alignment`,
+    /// without going past the end of the underlying buffer.
+    pub fn size_physical(&self, alignment: u64) -> u64 {
+        let offset_start = self.offset_start.unwrap_or(0);
+
+        self.size_in_used()
+            .next_multiple_of(alignment.max(1))
+            .min(self.size - offset_start)
+    }
+
+    /// Extend the handle to cover its [physical size](Self::size_physical), including the
+    /// trailing padding.
+    pub fn physical(mut self, alignment: u64) -> Self {
+        let size_physical = self.size_physical(alignment);
+        let offset_start = self.offset_start.unwrap_or(0);
+        let offset_end = self.size - offset_start - size_physical;
+
+        self.offset_end = (offset_end > 0).then_some(offset_end);
+        self
+    }
 }
 
 /// A binding represents a [Handle] that is bound to managed memory.
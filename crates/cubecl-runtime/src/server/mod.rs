@@ -1,5 +1,11 @@
 mod base;
+mod bind_group;
 mod handle;
+mod pipeline_cache;
+mod scalars;
 
 pub use base::*;
+pub use bind_group::*;
 pub use handle::*;
+pub use pipeline_cache::*;
+pub use scalars::*;
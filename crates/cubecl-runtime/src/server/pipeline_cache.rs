@@ -0,0 +1,143 @@
+use crate::id::KernelId;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Compiled pipelines by kernel id, evicting the least recently used ones once the number of
+/// pipelines exceeds its [capacity](Self::set_capacity).
+///
+/// Evicted pipelines are compiled again on their next launch.
+#[derive(Debug)]
+pub struct PipelineCache<T> {
+    entries: HashMap<KernelId, (u64, T)>,
+    capacity: usize,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl<T> Default for PipelineCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: usize::MAX,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<T> PipelineCache<T> {
+    /// The pipeline of the kernel, marking it as recently used.
+    ///
+    /// Counts as a hit when the pipeline is cached, as a miss otherwise.
+    pub fn get(&mut self, id: &KernelId) -> Option<&T> {
+        self.clock += 1;
+
+        match self.entries.get_mut(id) {
+            Some((last_used, pipeline)) => {
+                self.hits += 1;
+                *last_used = self.clock;
+                Some(pipeline)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Whether the pipeline of the kernel is cached, without marking it as used.
+    pub fn contains(&self, id: &KernelId) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    /// Cache the pipeline of the kernel, evicting the least recently used pipelines if the cache
+    /// is full.
+    ///
+    /// Returns the ids of the evicted pipelines, so that the state kept for them can be dropped.
+    pub fn insert(&mut self, id: KernelId, pipeline: T) -> Vec<KernelId> {
+        self.clock += 1;
+        self.entries.insert(id, (self.clock, pipeline));
+        self.evict()
+    }
+
+    /// Set the maximum number of cached pipelines, evicting the least recently used pipelines
+    /// past it.
+    ///
+    /// Returns the ids of the evicted pipelines.
+    pub fn set_capacity(&mut self, capacity: usize) -> Vec<KernelId> {
+        self.capacity = capacity;
+        self.evict()
+    }
+
+    /// The ids of the cached pipelines.
+    pub fn ids(&self) -> impl Iterator<Item = &KernelId> {
+        self.entries.keys()
+    }
+
+    /// The number of lookups that found a cached pipeline, and the number that didn't.
+    pub fn stats(&self) -> (usize, usize) {
+        (self.hits, self.misses)
+    }
+
+    /// The number of cached pipelines.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no pipeline is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn evict(&mut self) -> Vec<KernelId> {
+        let mut evicted = Vec::new();
+
+        while self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(id, _)| id.clone());
+
+            match oldest {
+                Some(id) => {
+                    self.entries.remove(&id);
+                    evicted.push(id);
+                }
+                None => break,
+            };
+        }
+
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct First;
+    struct Second;
+    struct Third;
+
+    #[test]
+    fn least_recently_used_pipelines_are_evicted() {
+        let mut cache = PipelineCache::default();
+        cache.set_capacity(2);
+
+        cache.insert(KernelId::new::<First>(), 0);
+        cache.insert(KernelId::new::<Second>(), 1);
+        assert_eq!(cache.get(&KernelId::new::<First>()), Some(&0));
+
+        let evicted = cache.insert(KernelId::new::<Third>(), 2);
+
+        assert_eq!(evicted, [KernelId::new::<Second>()]);
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&KernelId::new::<Second>()));
+        assert_eq!(cache.get(&KernelId::new::<Second>()), None);
+        assert_eq!(cache.get(&KernelId::new::<First>()), Some(&0));
+        assert_eq!(cache.stats(), (2, 1));
+    }
+}
@@ -0,0 +1,180 @@
+use super::MetadataBindingInfo;
+use alloc::vec::Vec;
+
+/// The alignment rules used to pack [scalar arguments](ScalarArgs).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScalarLayout {
+    /// The layout of uniform buffers: arrays are aligned to 16 bytes with a stride rounded up to
+    /// 16 bytes, and the total size is rounded up to 16 bytes.
+    Std140,
+    /// The layout of storage buffers and push constants: scalars and arrays are aligned to
+    /// their element size, and arrays are tightly packed.
+    #[default]
+    Std430,
+}
+
+/// The alignment of arrays and of the total size in the [std140](ScalarLayout::Std140) layout.
+const STD140_ALIGNMENT: usize = 16;
+
+/// Packs the scalar arguments of a kernel into a byte buffer, following the alignment rules of a
+/// [layout](ScalarLayout).
+///
+/// The packed scalars are passed as the [info](super::KernelArguments::with_scalars) of the
+/// kernel, or [as bytes](Self::into_bytes) to fill a uniform buffer.
+#[derive(Debug, Clone, Default)]
+pub struct ScalarArgs {
+    layout: ScalarLayout,
+    bytes: Vec<u8>,
+    /// The largest alignment of the packed scalars.
+    alignment: usize,
+}
+
+impl ScalarArgs {
+    /// Create empty scalar arguments packed with the given `layout`.
+    pub fn new(layout: ScalarLayout) -> Self {
+        Self {
+            layout,
+            bytes: Vec::new(),
+            alignment: 1,
+        }
+    }
+
+    /// Push a `u32` scalar.
+    pub fn push_u32(self, val: u32) -> Self {
+        self.push_scalar(&val.to_ne_bytes())
+    }
+
+    /// Push an `i32` scalar.
+    pub fn push_i32(self, val: i32) -> Self {
+        self.push_scalar(&val.to_ne_bytes())
+    }
+
+    /// Push an `f32` scalar.
+    pub fn push_f32(self, val: f32) -> Self {
+        self.push_scalar(&val.to_ne_bytes())
+    }
+
+    /// Push an `f16` scalar.
+    pub fn push_f16(self, val: half::f16) -> Self {
+        self.push_scalar(&val.to_ne_bytes())
+    }
+
+    /// Push an array of `u32`.
+    pub fn push_u32_array(self, values: &[u32]) -> Self {
+        self.push_array(values.iter().map(|val| val.to_ne_bytes()))
+    }
+
+    /// Push an array of `i32`.
+    pub fn push_i32_array(self, values: &[i32]) -> Self {
+        self.push_array(values.iter().map(|val| val.to_ne_bytes()))
+    }
+
+    /// Push an array of `f32`.
+    pub fn push_f32_array(self, values: &[f32]) -> Self {
+        self.push_array(values.iter().map(|val| val.to_ne_bytes()))
+    }
+
+    /// The packed bytes, padded to the total size required by the layout.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        let alignment = match self.layout {
+            ScalarLayout::Std140 => STD140_ALIGNMENT,
+            ScalarLayout::Std430 => self.alignment,
+        };
+        self.pad_to(alignment);
+
+        self.bytes
+    }
+
+    fn push_scalar<const N: usize>(mut self, bytes: &[u8; N]) -> Self {
+        self.pad_to(N);
+        self.bytes.extend_from_slice(bytes);
+        self.alignment = self.alignment.max(N);
+
+        self
+    }
+
+    fn push_array<const N: usize>(mut self, values: impl Iterator<Item = [u8; N]>) -> Self {
+        let (alignment, stride) = match self.layout {
+            ScalarLayout::Std140 => (STD140_ALIGNMENT, N.next_multiple_of(STD140_ALIGNMENT)),
+            ScalarLayout::Std430 => (N, N),
+        };
+
+        self.pad_to(alignment);
+        self.alignment = self.alignment.max(alignment);
+
+        for bytes in values {
+            let start = self.bytes.len();
+            self.bytes.extend_from_slice(&bytes);
+            self.bytes.resize(start + stride, 0);
+        }
+
+        self
+    }
+
+    fn pad_to(&mut self, alignment: usize) {
+        let len = self.bytes.len().next_multiple_of(alignment);
+        self.bytes.resize(len, 0);
+    }
+}
+
+impl From<ScalarArgs> for MetadataBindingInfo {
+    fn from(value: ScalarArgs) -> Self {
+        let mut bytes = value.into_bytes();
+        bytes.resize(bytes.len().next_multiple_of(size_of::<u64>()), 0);
+
+        let data = bytes
+            .chunks_exact(size_of::<u64>())
+            .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        MetadataBindingInfo::custom(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn scalars_are_aligned_to_their_size() {
+        let bytes = ScalarArgs::new(ScalarLayout::Std430)
+            .push_f16(half::f16::ONE)
+            .push_u32(7)
+            .into_bytes();
+
+        let mut expected = half::f16::ONE.to_ne_bytes().to_vec();
+        expected.extend([0, 0]);
+        expected.extend(7u32.to_ne_bytes());
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn std140_arrays_have_a_16_byte_stride() {
+        let std140 = ScalarArgs::new(ScalarLayout::Std140)
+            .push_u32(1)
+            .push_u32_array(&[2, 3])
+            .into_bytes();
+        let std430 = ScalarArgs::new(ScalarLayout::Std430)
+            .push_u32(1)
+            .push_u32_array(&[2, 3])
+            .into_bytes();
+
+        let word = |val: u32| val.to_ne_bytes().to_vec();
+        let zeros = |len: usize| vec![0u8; len];
+        assert_eq!(
+            std140,
+            [word(1), zeros(12), word(2), zeros(12), word(3), zeros(12)].concat()
+        );
+        assert_eq!(std430, [word(1), word(2), word(3)].concat());
+    }
+
+    #[test]
+    fn std140_size_is_rounded_up_to_16_bytes() {
+        let bytes = ScalarArgs::new(ScalarLayout::Std140)
+            .push_f32(1.0)
+            .into_bytes();
+
+        assert_eq!(bytes.len(), 16);
+    }
+}
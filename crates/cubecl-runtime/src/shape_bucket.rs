@@ -0,0 +1,60 @@
+use crate::id::KernelId;
+use alloc::vec::Vec;
+use cubecl_ir::ConstantValue;
+use hashbrown::HashMap;
+
+/// A key grouping the problem shapes that share a specialized pipeline of a kernel, such as
+/// small, medium and large matrices.
+///
+/// The key is chosen by the caller, typically an autotuner bucketing the shapes it tuned.
+pub type ShapeBucket = u64;
+
+/// The specialization constants of the pipelines of each kernel, by [shape bucket](ShapeBucket).
+///
+/// Launches [tagged](crate::server::KernelArguments::with_shape_bucket) with a registered bucket
+/// use its constants, so they reuse the pipeline specialized for it instead of a recompiled
+/// kernel.
+#[derive(Debug, Default)]
+pub struct ShapeBuckets {
+    table: spin::Mutex<HashMap<(KernelId, ShapeBucket), Vec<ConstantValue>>>,
+}
+
+impl ShapeBuckets {
+    /// Use the specialization `constants` for the launches of the kernel in the `bucket`.
+    pub(crate) fn register(
+        &self,
+        kernel: KernelId,
+        bucket: ShapeBucket,
+        constants: Vec<ConstantValue>,
+    ) {
+        self.table.lock().insert((kernel, bucket), constants);
+    }
+
+    /// The specialization constants registered for the kernel in the `bucket`, if any.
+    pub(crate) fn get(&self, kernel: KernelId, bucket: ShapeBucket) -> Option<Vec<ConstantValue>> {
+        self.table.lock().get(&(kernel, bucket)).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    struct Matmul;
+    struct Reduce;
+
+    #[test]
+    fn constants_are_registered_per_kernel_and_bucket() {
+        let buckets = ShapeBuckets::default();
+        buckets.register(KernelId::new::<Matmul>(), 0, vec![ConstantValue::UInt(16)]);
+        buckets.register(KernelId::new::<Matmul>(), 1, vec![ConstantValue::UInt(64)]);
+
+        assert_eq!(
+            buckets.get(KernelId::new::<Matmul>(), 1),
+            Some(vec![ConstantValue::UInt(64)])
+        );
+        assert_eq!(buckets.get(KernelId::new::<Matmul>(), 2), None);
+        assert_eq!(buckets.get(KernelId::new::<Reduce>(), 0), None);
+    }
+}
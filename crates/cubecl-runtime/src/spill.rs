@@ -0,0 +1,79 @@
+use crate::memory_management::{
+    ManagedMemoryHandle, ManagedMemoryId, memory_pool::ManagedMemoryDescriptor,
+};
+use alloc::sync::Weak;
+use core::sync::atomic::{AtomicBool, Ordering};
+use hashbrown::HashMap;
+
+/// What happens to the allocations that don't fit in the memory of the device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpillMode {
+    /// The allocation fails.
+    #[default]
+    Disabled,
+    /// The allocation is made in host memory mapped into the address space of the device, which
+    /// kernels access in place over the bus. This is much slower than device memory, but lets
+    /// workloads degrade gracefully instead of running out of memory.
+    ///
+    /// Backends without mappable host memory ignore this mode.
+    Host,
+}
+
+/// The [`SpillMode`] of a device, and the memory spilled to host memory.
+#[derive(Debug, Default)]
+pub struct SpillPolicy {
+    enabled: AtomicBool,
+    spilled: spin::Mutex<HashMap<ManagedMemoryId, Weak<ManagedMemoryDescriptor>>>,
+}
+
+impl SpillPolicy {
+    pub(crate) fn set_mode(&self, mode: SpillMode) {
+        self.enabled
+            .store(matches!(mode, SpillMode::Host), Ordering::Relaxed);
+    }
+
+    /// Whether the allocations that don't fit in the device memory should be spilled to host
+    /// memory.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Mark the `memory` as spilled to host memory.
+    pub fn record(&self, memory: &ManagedMemoryHandle) {
+        let binding = memory.clone().binding();
+        let mut spilled = self.spilled.lock();
+        spilled.retain(|_, descriptor| descriptor.strong_count() > 0);
+        spilled.insert(binding.descriptor().id, binding.downgrade());
+    }
+
+    /// Whether the `memory` was spilled to host memory.
+    pub(crate) fn is_spilled(&self, memory: &ManagedMemoryHandle) -> bool {
+        self.spilled.lock().contains_key(&memory.descriptor().id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn only_recorded_memory_is_spilled() {
+        let policy = SpillPolicy::default();
+        let spilled = ManagedMemoryHandle::new();
+        let resident = ManagedMemoryHandle::new();
+
+        policy.record(&spilled);
+
+        assert!(policy.is_spilled(&spilled));
+        assert!(!policy.is_spilled(&resident));
+    }
+
+    #[test_log::test]
+    fn dropped_memory_is_forgotten() {
+        let policy = SpillPolicy::default();
+        policy.record(&ManagedMemoryHandle::new());
+        policy.record(&ManagedMemoryHandle::new());
+
+        assert_eq!(policy.spilled.lock().len(), 1);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::server::{Handle, IoError};
+use alloc::{
+    sync::{Arc, Weak},
+    vec,
+    vec::Vec,
+};
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use cubecl_common::backtrace::BackTrace;
+
+/// Offsets and sizes of flushed ranges are aligned to 4 bytes, as required by WGPU for buffer
+/// writes.
+const FLUSH_ALIGNMENT: usize = 4;
+
+/// Writes into the host copy of a buffer created with
+/// [`create_staged`](crate::client::ComputeClient::create_staged).
+///
+/// The buffer isn't mapped: writes land in a host copy kept next to it, and only the range
+/// written since the last flush is uploaded, implicitly, before the next launch or read on the
+/// device. The buffer can therefore be rewritten every frame without allocating a new buffer or
+/// uploading it whole, but each flush still goes through a regular write.
+pub struct StagedWriter {
+    region: Arc<StagedBuffer>,
+    pending: Arc<AtomicBool>,
+}
+
+impl core::fmt::Debug for StagedWriter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StagedWriter")
+            .field("size", &self.len())
+            .finish()
+    }
+}
+
+impl StagedWriter {
+    /// Write `data` at `offset` bytes in the buffer.
+    ///
+    /// Fails when the written range doesn't fit in the buffer.
+    pub fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), IoError> {
+        let mut state = self.region.state.lock();
+        let size = state.data.len();
+
+        let end = match offset.checked_add(data.len()) {
+            Some(end) if end <= size => end,
+            _ => {
+                return Err(IoError::OutOfBounds {
+                    offset: offset as u64,
+                    len: data.len() as u64,
+                    size: size as u64,
+                    backtrace: BackTrace::capture(),
+                });
+            }
+        };
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        state.data[offset..end].copy_from_slice(data);
+        state.dirty = Some(match state.dirty.take() {
+            Some(dirty) => dirty.start.min(offset)..dirty.end.max(end),
+            None => offset..end,
+        });
+        self.pending.store(true, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// The size of the buffer in bytes.
+    pub fn len(&self) -> usize {
+        self.region.state.lock().data.len()
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+struct StagedBuffer {
+    handle: Handle,
+    state: spin::Mutex<StagedState>,
+}
+
+struct StagedState {
+    data: Vec<u8>,
+    dirty: Option<Range<usize>>,
+}
+
+/// A range of a staged buffer written since the last flush.
+pub(crate) struct DirtyRange {
+    /// The handle over the written range.
+    pub handle: Handle,
+    /// The content of the range.
+    pub data: Vec<u8>,
+}
+
+/// The [staged buffers](StagedWriter) created on a device.
+#[derive(Default)]
+pub struct StagedBuffers {
+    regions: spin::Mutex<Vec<Weak<StagedBuffer>>>,
+    pending: Arc<AtomicBool>,
+}
+
+impl core::fmt::Debug for StagedBuffers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StagedBuffers")
+            .field("regions", &self.regions.lock().len())
+            .finish()
+    }
+}
+
+impl StagedBuffers {
+    /// Register the zero-initialized `handle` as a staged buffer.
+    pub(crate) fn register(&self, handle: Handle) -> StagedWriter {
+        let size = handle.size_in_used() as usize;
+        let region = Arc::new(StagedBuffer {
+            handle,
+            state: spin::Mutex::new(StagedState {
+                data: vec![0; size],
+                dirty: None,
+            }),
+        });
+
+        let mut regions = self.regions.lock();
+        regions.retain(|region| region.strong_count() > 0);
+        regions.push(Arc::downgrade(&region));
+
+        StagedWriter {
+            region,
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Whether ranges were written since the last flush.
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// Take the ranges written since the last flush.
+    pub(crate) fn take_dirty(&self) -> Vec<DirtyRange> {
+        if !self.pending.swap(false, Ordering::Acquire) {
+            return Vec::new();
+        }
+
+        let regions = self.regions.lock();
+        regions
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|region| {
+                let mut state = region.state.lock();
+                let dirty = state.dirty.take()?;
+                let size = state.data.len();
+                let start = dirty.start - dirty.start % FLUSH_ALIGNMENT;
+                let end = dirty.end.next_multiple_of(FLUSH_ALIGNMENT).min(size);
+
+                Some(DirtyRange {
+                    handle: region
+                        .handle
+                        .clone()
+                        .offset_start(start as u64)
+                        .offset_end((size - end) as u64),
+                    data: state.data[start..end].to_vec(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_common::stream_id::StreamId;
+
+    #[test]
+    fn dirty_ranges_are_merged_and_aligned() {
+        let regions = StagedBuffers::default();
+        let mut writer = regions.register(Handle::new(StreamId::current(), 32));
+
+        writer.write(5, &[1, 2]).unwrap();
+        writer.write(13, &[3]).unwrap();
+
+        let dirty = regions.take_dirty();
+        assert_eq!(dirty.len(), 1);
+        assert_eq!(dirty[0].handle.offset_start, Some(4));
+        assert_eq!(dirty[0].handle.offset_end, Some(16));
+        assert_eq!(dirty[0].data, [0, 1, 2, 0, 0, 0, 0, 0, 0, 3, 0, 0]);
+        assert!(regions.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn out_of_bounds_writes_fail() {
+        let regions = StagedBuffers::default();
+        let mut writer = regions.register(Handle::new(StreamId::current(), 8));
+
+        assert!(matches!(
+            writer.write(6, &[0; 4]),
+            Err(IoError::OutOfBounds { .. })
+        ));
+        assert!(regions.take_dirty().is_empty());
+    }
+}
@@ -44,7 +44,17 @@ impl StorageHandle {
     }
 
     /// Increase the current offset with the given value in bytes.
+    ///
+    /// The offset can't go past the end of the handle: an offset larger than the size results
+    /// in an empty handle, which is considered a bug in debug builds.
     pub fn offset_start(&self, offset_bytes: u64) -> Self {
+        debug_assert!(
+            offset_bytes <= self.size(),
+            "Offset of {offset_bytes} bytes is out of bounds for a handle of {} bytes",
+            self.size()
+        );
+        let offset_bytes = offset_bytes.min(self.size());
+
         let utilization = StorageUtilization {
             offset: self.offset() + offset_bytes,
             size: self.size() - offset_bytes,
@@ -56,11 +66,20 @@ impl StorageHandle {
         }
     }
 
-    /// Reduce the size of the memory handle..
+    /// Reduce the size of the memory handle.
+    ///
+    /// The size saturates to zero: an offset larger than the size results in an empty handle,
+    /// which is considered a bug in debug builds.
     pub fn offset_end(&self, offset_bytes: u64) -> Self {
+        debug_assert!(
+            offset_bytes <= self.size(),
+            "Offset of {offset_bytes} bytes is out of bounds for a handle of {} bytes",
+            self.size()
+        );
+
         let utilization = StorageUtilization {
             offset: self.offset(),
-            size: self.size() - offset_bytes,
+            size: self.size().saturating_sub(offset_bytes),
         };
 
         Self {
@@ -118,3 +137,78 @@ impl<Resource: Send> ManagedResource<Resource> {
         &self.resource
     }
 }
+
+/// Resources that can expose the handle of the backend they are allocated with, to share them
+/// with other libraries.
+#[cfg(feature = "interop")]
+pub trait InteropResource {
+    /// The backend-specific handle of the resource.
+    type Raw;
+
+    /// Returns the backend-specific handle of the resource.
+    fn as_raw(&self) -> Self::Raw;
+}
+
+#[cfg(feature = "interop")]
+impl<Resource: InteropResource + Send> ManagedResource<Resource> {
+    /// Returns the backend-specific handle of the resource, e.g. to hand it to another library.
+    ///
+    /// # Notes
+    ///
+    /// `CubeCL` doesn't track accesses made through the raw handle. The caller must synchronize
+    /// with the stream the resource is used on (e.g. with
+    /// [`sync`](crate::client::ComputeClient::sync)) before accessing it, and must keep this
+    /// managed resource alive for as long as the raw handle is used.
+    pub fn as_raw(&self) -> Resource::Raw {
+        self.resource.as_raw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle(offset: u64, size: u64) -> StorageHandle {
+        StorageHandle::new(StorageId::new(), StorageUtilization { offset, size })
+    }
+
+    #[test]
+    fn offsets_within_bounds_shrink_the_handle() {
+        let handle = handle(16, 64).offset_start(8).offset_end(8);
+
+        assert_eq!(handle.offset(), 24);
+        assert_eq!(handle.size(), 48);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn offset_start_is_clamped_to_the_size() {
+        let handle = handle(16, 64).offset_start(100);
+
+        assert_eq!(handle.offset(), 80);
+        assert_eq!(handle.size(), 0);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn offset_end_saturates_to_zero() {
+        let handle = handle(16, 64).offset_end(100);
+
+        assert_eq!(handle.offset(), 16);
+        assert_eq!(handle.size(), 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "out of bounds")]
+    fn offset_start_out_of_bounds_panics_in_debug() {
+        handle(16, 64).offset_start(100);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "out of bounds")]
+    fn offset_end_out_of_bounds_panics_in_debug() {
+        handle(16, 64).offset_end(100);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::server::IoError;
 
-use super::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use super::{ComputeStorage, ManagedResource, StorageHandle, StorageId, StorageUtilization};
 use alloc::alloc::{Layout, alloc_zeroed, dealloc};
+use alloc::vec::Vec;
 use cubecl_common::backtrace::BackTrace;
 use hashbrown::HashMap;
 
@@ -78,6 +79,47 @@ impl BytesResource {
     }
 }
 
+impl ManagedResource<BytesResource> {
+    /// The bytes of the region of the resource, borrowed for as long as the resource keeps the
+    /// allocation alive.
+    pub fn as_slice(&self) -> &[u8] {
+        self.resource().read()
+    }
+}
+
+impl BytesStorage {
+    /// Takes ownership of the allocation of `data` instead of copying it.
+    ///
+    /// Gives `data` back when its allocation is empty or isn't aligned to the
+    /// [alignment](ComputeStorage::alignment).
+    pub fn adopt(&mut self, mut data: Vec<u8>) -> Result<StorageHandle, Vec<u8>> {
+        if data.capacity() == 0 || !(data.as_ptr() as usize).is_multiple_of(self.alignment()) {
+            return Err(data);
+        }
+
+        // The spare capacity is handed out when the memory is reused, so it has to be initialized.
+        data.resize(data.capacity(), 0);
+
+        let mut data = core::mem::ManuallyDrop::new(data);
+        let size = data.len();
+        let memory = AllocatedBytes {
+            ptr: data.as_mut_ptr(),
+            layout: Layout::array::<u8>(size).unwrap(),
+        };
+
+        let id = StorageId::new();
+        self.memory.insert(id, memory);
+
+        Ok(StorageHandle {
+            id,
+            utilization: StorageUtilization {
+                offset: 0,
+                size: size as u64,
+            },
+        })
+    }
+}
+
 impl ComputeStorage for BytesStorage {
     type Resource = BytesResource;
 
@@ -208,6 +250,21 @@ mod tests {
         storage.dealloc(handle.id);
     }
 
+    #[test_log::test]
+    fn test_adopt_takes_the_allocation() {
+        let mut storage = BytesStorage::default();
+        let mut data = alloc::vec![0u8; 64];
+        data.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        let ptr = data.as_ptr();
+
+        let handle = storage.adopt(data).unwrap();
+        let resource = storage.get(&handle);
+
+        assert_eq!(resource.get_write_ptr_and_length(), (ptr as *mut u8, 64));
+        assert_eq!(resource.read()[63], 63);
+        storage.dealloc(handle.id);
+    }
+
     #[test_log::test]
     fn test_alloc_dealloc_realloc() {
         let mut storage = BytesStorage::default();
@@ -0,0 +1,186 @@
+use crate::server::IoError;
+
+use super::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use alloc::vec::Vec;
+use cubecl_common::backtrace::BackTrace;
+use hashbrown::HashMap;
+
+/// A storage that doesn't own any memory, used to drive memory management into specific
+/// failure states in tests.
+///
+/// The storage has a fixed capacity, can be told to fail allocations after a given number
+/// of calls with [`force_oom_after`](Self::force_oom_after), and can simulate fragmentation
+/// by rounding every allocation up to a granularity, wasting the remainder.
+#[derive(Debug)]
+pub struct MockStorage {
+    capacity: u64,
+    alignment: usize,
+    granularity: u64,
+    allocated: u64,
+    alloc_calls: usize,
+    oom_after: Option<usize>,
+    memory: HashMap<StorageId, u64>,
+    pending_deallocs: Vec<StorageId>,
+}
+
+/// The resource of a [`MockStorage`], only describing which part of the storage is used.
+#[derive(Debug, Clone)]
+pub struct MockResource {
+    /// The storage id the resource points to.
+    pub id: StorageId,
+    /// How the storage is used.
+    pub utilization: StorageUtilization,
+}
+
+impl MockStorage {
+    /// Creates a new mock storage that can hold at most `capacity` bytes.
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            alignment: 4,
+            granularity: 1,
+            allocated: 0,
+            alloc_calls: 0,
+            oom_after: None,
+            memory: HashMap::new(),
+            pending_deallocs: Vec::new(),
+        }
+    }
+
+    /// Sets the alignment reported by the storage.
+    pub fn with_alignment(mut self, alignment: usize) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Rounds every allocation up to a multiple of `granularity` bytes of capacity, simulating
+    /// memory lost to fragmentation.
+    pub fn with_fragmentation(mut self, granularity: u64) -> Self {
+        self.granularity = granularity.max(1);
+        self
+    }
+
+    /// Makes every allocation fail once `n` more calls to `alloc` have been made.
+    pub fn force_oom_after(&mut self, n: usize) {
+        self.oom_after = Some(self.alloc_calls + n);
+    }
+
+    /// Lets allocations succeed again as long as there is enough capacity.
+    pub fn clear_forced_oom(&mut self) {
+        self.oom_after = None;
+    }
+
+    /// The number of bytes of capacity currently consumed, including fragmentation.
+    pub fn allocated(&self) -> u64 {
+        self.allocated
+    }
+
+    /// The total capacity of the storage in bytes.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// The number of calls made to `alloc`, including the failed ones.
+    pub fn alloc_calls(&self) -> usize {
+        self.alloc_calls
+    }
+
+    /// The number of allocations currently alive.
+    pub fn num_allocations(&self) -> usize {
+        self.memory.len()
+    }
+
+    fn footprint(&self, size: u64) -> u64 {
+        size.div_ceil(self.granularity) * self.granularity
+    }
+}
+
+impl ComputeStorage for MockStorage {
+    type Resource = MockResource;
+
+    fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+        assert!(
+            self.memory.contains_key(&handle.id),
+            "No allocation found for {}",
+            handle.id
+        );
+
+        MockResource {
+            id: handle.id,
+            utilization: handle.utilization.clone(),
+        }
+    }
+
+    fn alloc(&mut self, size: u64) -> Result<StorageHandle, IoError> {
+        self.alloc_calls += 1;
+
+        let footprint = self.footprint(size);
+        let forced = self.oom_after.is_some_and(|n| self.alloc_calls > n);
+
+        if forced || self.allocated + footprint > self.capacity {
+            return Err(IoError::BufferTooBig {
+                size,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
+        let id = StorageId::new();
+        self.allocated += footprint;
+        self.memory.insert(id, footprint);
+
+        Ok(StorageHandle::new(
+            id,
+            StorageUtilization { offset: 0, size },
+        ))
+    }
+
+    fn dealloc(&mut self, id: StorageId) {
+        self.pending_deallocs.push(id);
+    }
+
+    fn flush(&mut self) {
+        for id in self.pending_deallocs.drain(..) {
+            if let Some(footprint) = self.memory.remove(&id) {
+                self.allocated -= footprint;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn test_forced_oom_after_calls() {
+        let mut storage = MockStorage::new(1024);
+        storage.force_oom_after(2);
+
+        assert!(storage.alloc(16).is_ok());
+        assert!(storage.alloc(16).is_ok());
+        assert!(storage.alloc(16).is_err());
+
+        storage.clear_forced_oom();
+        assert!(storage.alloc(16).is_ok());
+    }
+
+    #[test_log::test]
+    fn test_capacity_is_released_on_flush() {
+        let mut storage = MockStorage::new(128).with_fragmentation(64);
+        let handle = storage.alloc(10).unwrap();
+        storage.alloc(10).unwrap();
+
+        assert_eq!(storage.allocated(), 128);
+        assert!(storage.alloc(1).is_err());
+
+        storage.dealloc(handle.id);
+        assert!(storage.alloc(1).is_err());
+
+        storage.flush();
+        assert!(storage.alloc(1).is_ok());
+    }
+}
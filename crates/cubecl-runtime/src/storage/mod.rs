@@ -6,3 +6,8 @@ pub use base::*;
 mod bytes_cpu;
 #[cfg(feature = "storage-bytes")]
 pub use bytes_cpu::*;
+
+#[cfg(feature = "test-util")]
+mod mock;
+#[cfg(feature = "test-util")]
+pub use mock::*;
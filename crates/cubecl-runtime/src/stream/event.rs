@@ -1,7 +1,7 @@
 use crate::{
     config::streaming::StreamingLogLevel,
     logging::ServerLogger,
-    memory_management::ManagedMemoryId,
+    memory_management::{ManagedMemoryId, memory_pool::ManagedMemoryDescriptor},
     server::{Binding, ServerError},
     stream::{StreamFactory, StreamPool},
 };
@@ -11,7 +11,7 @@ use hashbrown::HashMap;
 use std::{
     boxed::Box,
     format,
-    sync::{Arc, mpsc::SyncSender},
+    sync::{Arc, Weak, mpsc::SyncSender},
     vec::Vec,
 };
 
@@ -53,7 +53,55 @@ pub struct MultiStream<B: EventStreamBackend> {
     pub logger: Arc<ServerLogger>,
     max_streams: usize,
     gc: GcThread<B>,
-    shared_bindings_pool: Vec<(ManagedMemoryId, StreamId, u64)>,
+    shared_bindings_pool: Vec<(ManagedMemoryId, usize, u64)>,
+    bindings_pool: Vec<(ManagedMemoryId, Weak<ManagedMemoryDescriptor>)>,
+    last_used: LastUsed,
+}
+
+/// The stream index and cursor of the last task using each memory slice, whatever the stream
+/// the memory was created on.
+///
+/// A task can write to memory created on another stream, so the stream that created the memory
+/// isn't enough to know which stream a new task has to wait on.
+#[derive(Debug)]
+struct LastUsed {
+    entries: HashMap<ManagedMemoryId, (Weak<ManagedMemoryDescriptor>, usize, u64)>,
+    prune_threshold: usize,
+}
+
+impl LastUsed {
+    const MIN_PRUNE_THRESHOLD: usize = 64;
+
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            prune_threshold: Self::MIN_PRUNE_THRESHOLD,
+        }
+    }
+
+    fn get(&self, id: &ManagedMemoryId) -> Option<(usize, u64)> {
+        self.entries
+            .get(id)
+            .map(|(_, stream, cursor)| (*stream, *cursor))
+    }
+
+    fn register(
+        &mut self,
+        id: ManagedMemoryId,
+        memory: Weak<ManagedMemoryDescriptor>,
+        stream: usize,
+        cursor: u64,
+    ) {
+        self.entries.insert(id, (memory, stream, cursor));
+
+        // Memory ids are never reused, so the entries of freed memory are pruned once the map
+        // doubled in size since the last pruning.
+        if self.entries.len() >= self.prune_threshold {
+            self.entries
+                .retain(|_, (memory, _, _)| memory.strong_count() > 0);
+            self.prune_threshold = usize::max(Self::MIN_PRUNE_THRESHOLD, self.entries.len() * 2);
+        }
+    }
 }
 
 /// A wrapper around a backend stream that includes synchronization metadata.
@@ -196,6 +244,8 @@ impl<B: EventStreamBackend> MultiStream<B> {
             max_streams: max_streams as usize,
             gc: GcThread::new(),
             shared_bindings_pool: Vec::new(),
+            bindings_pool: Vec::new(),
+            last_used: LastUsed::new(),
         }
     }
 
@@ -220,6 +270,11 @@ impl<B: EventStreamBackend> MultiStream<B> {
         let stream = self.streams.get_mut(&stream_id);
         stream.cursor += 1;
 
+        let index = stream_index(&stream_id, self.max_streams);
+        for (id, memory) in self.bindings_pool.drain(..) {
+            self.last_used.register(id, memory, index, stream.cursor);
+        }
+
         if enforce_healthy && !B::is_healthy(&stream.stream) {
             return Err(ServerError::Generic {
                 reason: "Can't resolve the stream since it is currently in an error state".into(),
@@ -236,6 +291,49 @@ impl<B: EventStreamBackend> MultiStream<B> {
         })
     }
 
+    /// Makes the stream wait for the work submitted so far on the `dependencies`, even when they
+    /// share no binding with it.
+    ///
+    /// Dependencies on the same stream are already ordered, and the ones the stream already
+    /// synced with since their last task are skipped.
+    pub fn wait_streams(&mut self, stream_id: StreamId, dependencies: &[StreamId]) {
+        let current_index = stream_index(&stream_id, self.max_streams);
+        let mut events = Vec::with_capacity(dependencies.len());
+
+        for dependency in dependencies {
+            let index = stream_index(dependency, self.max_streams);
+            if index == current_index || events.iter().any(|(origin, _, _)| *origin == index) {
+                continue;
+            }
+
+            let cursor = self.streams.get_mut(dependency).cursor;
+            let current = self.streams.get_mut(&stream_id);
+            if current
+                .last_synced
+                .get(&index)
+                .is_some_and(|synced| *synced >= cursor)
+            {
+                continue;
+            }
+
+            let origin = self.streams.get_mut(dependency);
+            events.push((index, cursor, B::flush(&mut origin.stream)));
+        }
+
+        let stream = self.streams.get_mut(&stream_id);
+
+        for (origin, cursor, event) in events {
+            stream.last_synced.insert(origin, cursor);
+
+            self.logger.log_streaming(
+                |level| !matches!(level, StreamingLogLevel::Disabled),
+                || format!("Waiting on {origin} from {stream_id} as an explicit dependency"),
+            );
+
+            B::wait_event(&mut stream.stream, event);
+        }
+    }
+
     /// Aligns the target stream with other streams based on shared bindings.
     ///
     /// This initializes the stream if it doesn't exist, analyzes which originating streams need flushing
@@ -261,8 +359,12 @@ impl<B: EventStreamBackend> MultiStream<B> {
     ) -> SharedBindingAnalysis {
         // We reset the memory pool for the info.
         self.shared_bindings_pool.clear();
+        self.bindings_pool.clear();
+
+        let current_index = stream_index(&stream_id, self.max_streams);
 
         for handle in handles {
+            let id = handle.memory.descriptor().id;
             let index = stream_index(&handle.stream, self.max_streams);
             let stream = unsafe { self.streams.get_mut_index(index) };
             let cursor_handle = B::handle_cursor(&stream.stream, handle);
@@ -270,44 +372,47 @@ impl<B: EventStreamBackend> MultiStream<B> {
             // We only add the info to be consider if the handle stream is different from the current
             // stream.
             if handle.stream != stream_id {
-                self.shared_bindings_pool.push((
-                    handle.memory.descriptor().id,
-                    handle.stream,
-                    cursor_handle,
-                ));
+                self.shared_bindings_pool.push((id, index, cursor_handle));
             }
+
+            // The memory may also have been used by a task on another stream since it was created.
+            if let Some((index, cursor)) = self.last_used.get(&id)
+                && index != current_index
+            {
+                self.shared_bindings_pool.push((id, index, cursor));
+            }
+
+            self.bindings_pool.push((id, handle.memory.downgrade()));
         }
 
         let mut analysis = SharedBindingAnalysis::default();
         let current = self.streams.get_mut(&stream_id);
 
-        for (handle_id, stream, cursor) in self.shared_bindings_pool.iter() {
-            let index = stream_index(stream, self.max_streams);
-
-            if let Some(last_synced) = current.last_synced.get(&index) {
+        for (handle_id, index, cursor) in self.shared_bindings_pool.iter() {
+            if let Some(last_synced) = current.last_synced.get(index) {
                 if last_synced < cursor {
                     self.logger.log_streaming(
                         |level| matches!(level, StreamingLogLevel::Full),
                         || {
                             format!(
-                                "Binding on {} is shared on {} since it's not sync {} < {}",
-                                stream, stream_id, last_synced, cursor
+                                "Binding on stream {} is shared on {} since it's not sync {} < {}",
+                                index, stream_id, last_synced, cursor
                             )
                         },
                     );
-                    analysis.shared(*handle_id, index);
+                    analysis.shared(*handle_id, *index);
                 }
             } else {
                 self.logger.log_streaming(
                     |level| matches!(level, StreamingLogLevel::Full),
                     || {
                         format!(
-                            "Binding on {} is shared on {} since it was never synced.",
-                            stream, stream_id,
+                            "Binding on stream {} is shared on {} since it was never synced.",
+                            index, stream_id,
                         )
                     },
                 );
-                analysis.shared(*handle_id, index);
+                analysis.shared(*handle_id, *index);
             }
         }
 
@@ -488,6 +593,73 @@ mod tests {
         assert_eq!(stream2.cursor, 1);
     }
 
+    #[test_log::test]
+    fn test_analysis_shared_by_last_use() {
+        let logger = Arc::new(ServerLogger::default());
+        let stream_1 = StreamId { value: 1 };
+        let stream_2 = StreamId { value: 2 };
+
+        let binding = handle(stream_1);
+
+        let mut ms = MultiStream::new(logger, TestBackend, MAX_STREAMS);
+        ms.resolve(stream_1, [].into_iter(), false).unwrap();
+        // A task on stream 2 uses the memory created on stream 1.
+        ms.resolve(stream_2, [&binding].into_iter(), false).unwrap();
+
+        let analysis = ms.update_shared_bindings(stream_1, [&binding].into_iter());
+
+        let mut expected = SharedBindingAnalysis::default();
+        expected.shared(
+            binding.memory.descriptor().id,
+            ms.streams.stream_index(&stream_2),
+        );
+
+        assert_eq!(analysis, expected);
+    }
+
+    #[test_log::test]
+    fn test_analysis_last_use_synced() {
+        let logger = Arc::new(ServerLogger::default());
+        let stream_1 = StreamId { value: 1 };
+        let stream_2 = StreamId { value: 2 };
+
+        let binding = handle(stream_1);
+
+        let mut ms = MultiStream::new(logger, TestBackend, MAX_STREAMS);
+        ms.resolve(stream_1, [].into_iter(), false).unwrap();
+        ms.resolve(stream_2, [&binding].into_iter(), false).unwrap();
+        ms.resolve(stream_1, [&binding].into_iter(), false).unwrap();
+
+        let analysis = ms.update_shared_bindings(stream_1, [&binding].into_iter());
+
+        assert_eq!(analysis, SharedBindingAnalysis::default());
+    }
+
+    #[test_log::test]
+    fn test_wait_streams_syncs_once_per_task() {
+        let logger = Arc::new(ServerLogger::default());
+        let stream_1 = StreamId { value: 1 };
+        let stream_2 = StreamId { value: 2 };
+
+        let mut ms = MultiStream::new(logger, TestBackend, MAX_STREAMS);
+        ms.resolve(stream_1, [].into_iter(), false).unwrap();
+        ms.resolve(stream_2, [].into_iter(), false).unwrap();
+
+        ms.wait_streams(stream_1, &[stream_1, stream_2, stream_2]);
+
+        let index_1 = stream_index(&stream_1, MAX_STREAMS as usize);
+        let index_2 = stream_index(&stream_2, MAX_STREAMS as usize);
+        let stream1 = ms.streams.get_mut(&stream_1);
+        assert_eq!(stream1.last_synced.get(&index_2), Some(&1));
+        assert_eq!(stream1.last_synced.get(&index_1), None);
+
+        ms.resolve(stream_2, [].into_iter(), false).unwrap();
+        ms.wait_streams(stream_1, &[stream_2]);
+
+        let stream1 = ms.streams.get_mut(&stream_1);
+        assert_eq!(stream1.last_synced.get(&index_2), Some(&2));
+    }
+
     fn handle(stream: StreamId) -> Binding {
         Handle::new(stream, 10).binding()
     }
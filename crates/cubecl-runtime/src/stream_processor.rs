@@ -0,0 +1,73 @@
+use crate::{
+    client::ComputeClient,
+    runtime::Runtime,
+    server::{Handle, ServerError},
+};
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+use core::{future::Future, pin::Pin};
+use cubecl_common::bytes::Bytes;
+
+/// The read of the result of a tile.
+type TileRead<'a> = Pin<Box<dyn Future<Output = Result<Vec<Bytes>, ServerError>> + Send + 'a>>;
+
+/// Processes host data larger than the device memory one tile at a time.
+///
+/// Each tile is uploaded, computed by a per-tile kernel and read back, with up to a
+/// [depth](Self::with_depth) of tiles in flight, so that the transfers of a tile overlap with the
+/// compute of the others. The memory of a tile is released to the pools once its result is read,
+/// where the next tiles reuse it.
+pub struct StreamProcessor<R: Runtime> {
+    client: ComputeClient<R>,
+    tile_size: usize,
+    depth: usize,
+}
+
+impl<R: Runtime> StreamProcessor<R> {
+    /// Create a processor splitting the data into tiles of `tile_size` bytes, double buffered.
+    pub fn new(client: ComputeClient<R>, tile_size: usize) -> Self {
+        Self {
+            client,
+            tile_size: tile_size.max(1),
+            depth: 2,
+        }
+    }
+
+    /// Set the number of tiles in flight at once, bounding the device memory used by the tiles.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Run the `kernel` over every tile of the `data`, passing the result of each tile to the
+    /// `sink` with the index of the tile, in order.
+    ///
+    /// The `kernel` gets the handle of the uploaded tile, which is shorter than the tile size for
+    /// the last tile, and returns the handle of its result. Fails at the first tile whose result
+    /// can't be read.
+    pub fn run<K, S>(&self, data: &[u8], mut kernel: K, mut sink: S) -> Result<(), ServerError>
+    where
+        K: FnMut(&ComputeClient<R>, Handle) -> Handle,
+        S: FnMut(usize, Bytes),
+    {
+        let mut pending: VecDeque<(usize, TileRead<'_>)> = VecDeque::with_capacity(self.depth);
+        let mut complete = |(index, read): (usize, TileRead<'_>)| -> Result<(), ServerError> {
+            let mut result = cubecl_common::reader::read_sync(read)?;
+            sink(index, result.remove(0));
+            Ok(())
+        };
+
+        for (index, tile) in data.chunks(self.tile_size).enumerate() {
+            if pending.len() == self.depth
+                && let Some(oldest) = pending.pop_front()
+            {
+                complete(oldest)?;
+            }
+
+            let input = self.client.create_from_slice(tile);
+            let output = kernel(&self.client, input);
+            pending.push_back((index, Box::pin(self.client.read_async(vec![output]))));
+        }
+
+        pending.into_iter().try_for_each(complete)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::server::{ServerError, SubmissionId};
+use alloc::collections::VecDeque;
+use cubecl_common::{future::DynFut, stream_id::StreamId};
+use hashbrown::HashMap;
+
+/// How many fences are kept per stream, the oldest being dropped first.
+///
+/// Waiting on a submission whose fence was dropped waits on the next one instead, so this only
+/// trades precision for memory.
+const MAX_PENDING_FENCES: usize = 64;
+
+/// The [fences](crate::server::ComputeServer::fence) of the [submissions](SubmissionId) of each
+/// stream, for the host to wait on a submission without waiting on the work after it.
+#[derive(Default)]
+pub struct SubmissionFences {
+    streams: spin::Mutex<HashMap<StreamId, StreamFences>>,
+}
+
+#[derive(Default)]
+struct StreamFences {
+    next: u64,
+    pending: VecDeque<(u64, DynFut<Result<(), ServerError>>)>,
+}
+
+impl SubmissionFences {
+    /// Reserve the index of the next submission on the `stream`.
+    pub(crate) fn next_index(&self, stream: StreamId) -> u64 {
+        let mut streams = self.streams.lock();
+        let fences = streams.entry(stream).or_default();
+        let index = fences.next;
+        fences.next += 1;
+        index
+    }
+
+    /// Register the `fence` of the submission at `index` on the `stream`.
+    pub(crate) fn insert(
+        &self,
+        stream: StreamId,
+        index: u64,
+        fence: DynFut<Result<(), ServerError>>,
+    ) {
+        let mut streams = self.streams.lock();
+        let fences = streams.entry(stream).or_default();
+        fences.pending.push_back((index, fence));
+
+        if fences.pending.len() > MAX_PENDING_FENCES {
+            fences.pending.pop_front();
+        }
+    }
+
+    /// The fence completing with the `submission`, or with the first later one still tracked.
+    ///
+    /// The fences of the submissions before it are dropped, since work on a stream completes in
+    /// order. Returns `None` when no fence is left for the submission.
+    pub(crate) fn take(&self, submission: SubmissionId) -> Option<DynFut<Result<(), ServerError>>> {
+        let mut streams = self.streams.lock();
+        let fences = streams.get_mut(&submission.stream)?;
+
+        while let Some((index, fence)) = fences.pending.pop_front() {
+            if index >= submission.index {
+                return Some(fence);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    fn fence() -> DynFut<Result<(), ServerError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn submission(stream: StreamId, index: u64) -> SubmissionId {
+        SubmissionId { stream, index }
+    }
+
+    #[test_log::test]
+    fn waiting_on_a_submission_drops_the_earlier_fences() {
+        let fences = SubmissionFences::default();
+        let stream = StreamId { value: 0 };
+
+        for _ in 0..3 {
+            let index = fences.next_index(stream);
+            fences.insert(stream, index, fence());
+        }
+
+        assert!(fences.take(submission(stream, 1)).is_some());
+        assert_eq!(fences.streams.lock()[&stream].pending.len(), 1);
+        assert!(fences.take(submission(stream, 1)).is_some());
+        assert!(fences.take(submission(stream, 2)).is_none());
+    }
+
+    #[test_log::test]
+    fn dropped_fences_fall_back_to_a_later_one() {
+        let fences = SubmissionFences::default();
+        let stream = StreamId { value: 0 };
+
+        for _ in 0..MAX_PENDING_FENCES + 1 {
+            let index = fences.next_index(stream);
+            fences.insert(stream, index, fence());
+        }
+
+        assert!(fences.take(submission(stream, 0)).is_some());
+        assert_eq!(
+            fences.streams.lock()[&stream].pending.len(),
+            MAX_PENDING_FENCES - 1
+        );
+    }
+}
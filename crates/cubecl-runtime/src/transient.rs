@@ -0,0 +1,122 @@
+use crate::server::Binding;
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU64, Ordering};
+use cubecl_common::stream_id::StreamId;
+use hashbrown::HashMap;
+
+/// The [transient allocations](TransientAllocations) of each stream.
+///
+/// A [sync](crate::client::ComputeClient::sync) or [flush](crate::client::ComputeClient::flush)
+/// only releases the allocations of its own stream, since the work of the other streams may still
+/// use theirs.
+#[derive(Debug, Default)]
+pub struct StreamTransients {
+    streams: spin::Mutex<HashMap<StreamId, TransientAllocations>>,
+}
+
+impl StreamTransients {
+    /// Register the binding of a transient allocation made on the `stream`, returning the expiry
+    /// of its handle.
+    pub(crate) fn register(&self, stream: StreamId, binding: Binding) -> TransientExpiry {
+        self.streams
+            .lock()
+            .entry(stream)
+            .or_default()
+            .register(binding)
+    }
+
+    /// Whether no allocation of the `stream` is waiting to be released.
+    pub(crate) fn is_empty(&self, stream: StreamId) -> bool {
+        self.streams
+            .lock()
+            .get(&stream)
+            .is_none_or(|allocations| allocations.is_empty())
+    }
+
+    /// Take the bindings to release on the `stream` and expire its handles created so far.
+    pub(crate) fn take(&self, stream: StreamId) -> Vec<Binding> {
+        match self.streams.lock().remove(&stream) {
+            Some(allocations) => allocations.take(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// The allocations made with
+/// [`empty_transient`](crate::client::ComputeClient::empty_transient) that haven't been released
+/// yet.
+///
+/// Every [sync](crate::client::ComputeClient::sync) or
+/// [flush](crate::client::ComputeClient::flush) takes the pending allocations and starts a new
+/// epoch, expiring the handles created before it.
+#[derive(Debug, Default)]
+pub struct TransientAllocations {
+    pending: spin::Mutex<Vec<Binding>>,
+    epoch: Arc<AtomicU64>,
+}
+
+impl TransientAllocations {
+    /// Register the binding of a transient allocation, returning the expiry of its handle.
+    pub(crate) fn register(&self, binding: Binding) -> TransientExpiry {
+        let mut pending = self.pending.lock();
+        pending.push(binding);
+
+        TransientExpiry {
+            epoch: self.epoch.clone(),
+            created: self.epoch.load(Ordering::Acquire),
+        }
+    }
+
+    /// Whether no allocation is waiting to be released.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.lock().is_empty()
+    }
+
+    /// Take the bindings to release and expire the handles created so far.
+    pub(crate) fn take(&self) -> Vec<Binding> {
+        let mut pending = self.pending.lock();
+        self.epoch.fetch_add(1, Ordering::Release);
+
+        core::mem::take(&mut *pending)
+    }
+}
+
+/// Tracks whether the memory of a transient [handle](crate::server::Handle) was released.
+#[derive(Clone, Debug)]
+pub(crate) struct TransientExpiry {
+    epoch: Arc<AtomicU64>,
+    created: u64,
+}
+
+impl TransientExpiry {
+    /// Whether the boundary that released the memory was crossed.
+    pub(crate) fn is_expired(&self) -> bool {
+        self.epoch.load(Ordering::Acquire) != self.created
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::Handle;
+
+    fn binding(stream: StreamId) -> Binding {
+        Handle::new(stream, 16).binding()
+    }
+
+    #[test_log::test]
+    fn streams_only_release_their_own_allocations() {
+        let (first, second) = (StreamId { value: 0 }, StreamId { value: 1 });
+        let transients = StreamTransients::default();
+        let expiry_first = transients.register(first, binding(first));
+        let expiry_second = transients.register(second, binding(second));
+
+        assert_eq!(transients.take(first).len(), 1);
+        assert!(expiry_first.is_expired());
+        assert!(!expiry_second.is_expired());
+        assert!(transients.is_empty(first));
+        assert!(!transients.is_empty(second));
+        assert_eq!(transients.take(second).len(), 1);
+        assert!(expiry_second.is_expired());
+    }
+}
@@ -1,9 +1,13 @@
-use cubecl_common::backtrace::BackTrace;
+use alloc::format;
+use core::sync::atomic::{AtomicBool, Ordering};
+use cubecl_common::{backtrace::BackTrace, bytes::Bytes};
 use cubecl_ir::DeviceProperties;
 
 use crate::{
     id::KernelId,
-    server::{CubeDim, LaunchError, ResourceLimitError},
+    kernel::Visibility,
+    memory_management::ManagedMemoryId,
+    server::{Binding, CubeDim, LaunchError, ResourceLimitError, ServerError},
 };
 
 /// Validate the cube dim of a kernel fits within the hardware limits
@@ -25,6 +29,102 @@ pub fn validate_cube_dim(
     }
 }
 
+/// Validate the size of every buffer is a multiple of the size of the elements the kernel
+/// expects for it.
+///
+/// Element sizes of zero are considered unknown and aren't validated.
+pub fn validate_buffer_sizes(
+    elem_sizes: &[usize],
+    sizes: impl IntoIterator<Item = u64>,
+) -> Result<(), LaunchError> {
+    for (index, (elem_size, size)) in elem_sizes.iter().zip(sizes).enumerate() {
+        if *elem_size != 0 && !size.is_multiple_of(*elem_size as u64) {
+            return Err(LaunchError::BindingSize {
+                index,
+                size,
+                elem_size: *elem_size,
+                backtrace: BackTrace::capture(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the two bindings refer to overlapping bytes of the same memory.
+fn overlaps(lhs: &Binding, rhs: &Binding) -> bool {
+    if lhs.memory.descriptor().id != rhs.memory.descriptor().id {
+        return false;
+    }
+
+    let start = |binding: &Binding| binding.offset_start.unwrap_or(0);
+    let end = |binding: &Binding| binding.size - binding.offset_end.unwrap_or(0);
+
+    start(lhs) < end(rhs) && start(rhs) < end(lhs)
+}
+
+/// Validate the buffers bound to overlapping memory, such as the same handle passed twice for an
+/// in-place operation, against the `access` of the kernel to each buffer.
+///
+/// Buffers that are only read can always overlap. Two written buffers can't, since the writes
+/// would race, and a buffer that is read can only overlap a written one when the backend
+/// supports `read_write_aliasing`, i.e. doesn't bind read buffers as read-only.
+pub fn validate_aliasing(
+    access: &[Visibility],
+    buffers: &[Binding],
+    read_write_aliasing: bool,
+) -> Result<(), LaunchError> {
+    for (first, (first_access, lhs)) in access.iter().zip(buffers).enumerate() {
+        for (second, (second_access, rhs)) in access.iter().zip(buffers).enumerate().skip(first + 1)
+        {
+            if !overlaps(lhs, rhs) {
+                continue;
+            }
+
+            let reason = match (first_access, second_access) {
+                (Visibility::Read, Visibility::Read) => continue,
+                (Visibility::ReadWrite, Visibility::ReadWrite) => {
+                    "the kernel writes to both of them".into()
+                }
+                _ if read_write_aliasing => continue,
+                _ => "the backend binds the read buffer as read-only while the other one is \
+                      written; pass the handle once or copy it first"
+                    .into(),
+            };
+
+            return Err(LaunchError::AliasedBindings {
+                first,
+                second,
+                reason,
+                backtrace: BackTrace::capture(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate every uniform buffer fits within the maximum uniform binding size of the device.
+pub fn validate_uniform_sizes(
+    properties: &DeviceProperties,
+    sizes: impl IntoIterator<Item = u64>,
+) -> Result<(), LaunchError> {
+    let max = properties.hardware.max_uniform_buffer_size;
+    for (index, requested) in sizes.into_iter().enumerate() {
+        if requested > max {
+            return Err(ResourceLimitError::UniformSize {
+                index,
+                requested,
+                max,
+                backtrace: BackTrace::capture(),
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate the total units of a kernel fits within the hardware limits
 pub fn validate_units(
     properties: &DeviceProperties,
@@ -43,3 +143,152 @@ pub fn validate_units(
         Ok(())
     }
 }
+
+/// The byte [`empty`](crate::client::ComputeClient::empty) buffers are filled with when the
+/// `uninit-checks` feature is enabled, to detect reads of memory that was never written.
+pub const UNINIT_SENTINEL: u8 = 0xDE;
+
+/// Whether the `bytes` are all the [sentinel](UNINIT_SENTINEL), meaning the buffer they were read
+/// from was likely never written since it was reserved.
+pub fn is_uninitialized(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && bytes.iter().all(|byte| *byte == UNINIT_SENTINEL)
+}
+
+/// What happens when a read returns a buffer that was never written, with the `uninit-checks`
+/// feature enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UninitReadMode {
+    /// The read succeeds and a warning is logged.
+    #[default]
+    Warn,
+    /// The read fails with an error naming the buffer.
+    Error,
+}
+
+/// The [`UninitReadMode`] of a device.
+#[derive(Debug, Default)]
+pub struct UninitReadPolicy {
+    error: AtomicBool,
+}
+
+impl UninitReadPolicy {
+    pub(crate) fn set_mode(&self, mode: UninitReadMode) {
+        self.error
+            .store(matches!(mode, UninitReadMode::Error), Ordering::Relaxed);
+    }
+
+    pub(crate) fn mode(&self) -> UninitReadMode {
+        match self.error.load(Ordering::Relaxed) {
+            true => UninitReadMode::Error,
+            false => UninitReadMode::Warn,
+        }
+    }
+}
+
+/// Warn about, or fail on, the reads that returned memory still filled with the
+/// [sentinel](crate::validation::UNINIT_SENTINEL) of uninitialized buffers, depending on the
+/// [mode](UninitReadMode).
+pub(crate) fn check_uninitialized_reads(
+    bytes: &[Bytes],
+    memories: &[ManagedMemoryId],
+    mode: UninitReadMode,
+) -> Result<(), ServerError> {
+    for (index, (data, memory)) in bytes.iter().zip(memories).enumerate() {
+        if !is_uninitialized(data) {
+            continue;
+        }
+
+        let reason = format!(
+            "Read {index} of memory {} returned a buffer that was never written since it was \
+             reserved",
+            memory.value
+        );
+        match mode {
+            UninitReadMode::Warn => log::warn!("{reason}"),
+            UninitReadMode::Error => {
+                return Err(ServerError::Generic {
+                    reason,
+                    backtrace: BackTrace::capture(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::Handle;
+    use alloc::vec;
+    use cubecl_common::stream_id::StreamId;
+
+    #[test_log::test]
+    fn only_sentinel_bytes_are_uninitialized() {
+        assert!(is_uninitialized(&[UNINIT_SENTINEL; 8]));
+        assert!(!is_uninitialized(&[UNINIT_SENTINEL, 0, UNINIT_SENTINEL]));
+        assert!(!is_uninitialized(&[]));
+    }
+
+    #[test_log::test]
+    fn unwritten_reads_only_fail_in_error_mode() {
+        let bytes = [
+            Bytes::from_bytes_vec(vec![1, 2]),
+            Bytes::from_bytes_vec(vec![UNINIT_SENTINEL; 4]),
+        ];
+        let memories = [ManagedMemoryId { value: 0 }, ManagedMemoryId { value: 7 }];
+
+        assert!(check_uninitialized_reads(&bytes, &memories, UninitReadMode::Warn).is_ok());
+
+        let error = check_uninitialized_reads(&bytes, &memories, UninitReadMode::Error);
+        assert!(matches!(
+            error,
+            Err(ServerError::Generic { reason, .. }) if reason.contains("Read 1 of memory 7")
+        ));
+    }
+
+    #[test_log::test]
+    fn only_written_buffers_cant_overlap() {
+        let handle = Handle::new(StreamId { value: 0 }, 16);
+        let whole = handle.clone().binding();
+        let mut head = handle.clone().binding();
+        head.offset_end = Some(8);
+        let mut tail = handle.binding();
+        tail.offset_start = Some(8);
+
+        let read = Visibility::Read;
+        let write = Visibility::ReadWrite;
+
+        assert!(validate_aliasing(&[read, read], &[whole.clone(), head.clone()], false).is_ok());
+        assert!(validate_aliasing(&[write, write], &[head.clone(), tail.clone()], false).is_ok());
+        assert!(validate_aliasing(&[read, write], &[whole.clone(), head.clone()], true).is_ok());
+
+        let err = validate_aliasing(&[read, write], &[whole.clone(), tail], false).unwrap_err();
+        assert!(matches!(
+            err,
+            LaunchError::AliasedBindings {
+                first: 0,
+                second: 1,
+                ..
+            }
+        ));
+        assert!(validate_aliasing(&[write, write], &[whole, head], true).is_err());
+    }
+
+    #[test_log::test]
+    fn buffer_sizes_must_be_multiple_of_elem_size() {
+        assert!(validate_buffer_sizes(&[4, 2, 0], [16, 6, 3]).is_ok());
+
+        let err = validate_buffer_sizes(&[4, 2], [16, 5]).unwrap_err();
+        assert!(matches!(
+            err,
+            LaunchError::BindingSize {
+                index: 1,
+                size: 5,
+                elem_size: 2,
+                ..
+            }
+        ));
+    }
+}
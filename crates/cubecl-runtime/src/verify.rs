@@ -0,0 +1,164 @@
+use crate::{
+    memory_management::{ManagedMemoryId, memory_pool::ManagedMemoryDescriptor},
+    server::{Binding, CopyDescriptor, ServerError},
+};
+use alloc::{format, sync::Weak, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+use cubecl_common::{backtrace::BackTrace, bytes::Bytes};
+use hashbrown::HashMap;
+
+/// How kernels are verified against their [CPU reference](crate::kernel::CpuReference).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// Kernels only run on the device.
+    #[default]
+    Disabled,
+    /// Kernels with a CPU reference also run on the host into shadow buffers, and reading a
+    /// buffer written by such a kernel fails at the first element differing from its shadow.
+    ///
+    /// The buffers of every verified launch are read back before the launch, so this is only
+    /// meant for debugging.
+    Enabled,
+}
+
+/// The bytes a kernel with a CPU reference is expected to leave in a buffer.
+struct Shadow {
+    kernel: &'static str,
+    bytes: Vec<u8>,
+    memory: Weak<ManagedMemoryDescriptor>,
+}
+
+type ShadowKey = (ManagedMemoryId, Option<u64>, Option<u64>);
+
+fn shadow_key(binding: &Binding) -> ShadowKey {
+    (
+        binding.memory.descriptor().id,
+        binding.offset_start,
+        binding.offset_end,
+    )
+}
+
+/// Shadow buffers of the kernels [verified](VerifyMode) on a device.
+#[derive(Default)]
+pub struct Verifier {
+    enabled: AtomicBool,
+    shadows: spin::Mutex<HashMap<ShadowKey, Shadow>>,
+}
+
+impl core::fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Verifier")
+            .field("enabled", &self.is_enabled())
+            .field("shadows", &self.shadows.lock().len())
+            .finish()
+    }
+}
+
+impl Verifier {
+    pub(crate) fn set_mode(&self, mode: VerifyMode) {
+        self.enabled
+            .store(matches!(mode, VerifyMode::Enabled), Ordering::Relaxed);
+
+        if matches!(mode, VerifyMode::Disabled) {
+            self.shadows.lock().clear();
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Register the buffers computed by the CPU reference of `kernel` as the shadows of the
+    /// `bindings`.
+    pub(crate) fn insert(&self, kernel: &'static str, bindings: &[Binding], buffers: Vec<Vec<u8>>) {
+        let mut shadows = self.shadows.lock();
+        shadows.retain(|_, shadow| shadow.memory.strong_count() > 0);
+
+        for (binding, bytes) in bindings.iter().zip(buffers) {
+            shadows.insert(
+                shadow_key(binding),
+                Shadow {
+                    kernel,
+                    bytes,
+                    memory: binding.memory.downgrade(),
+                },
+            );
+        }
+    }
+
+    /// Discard the shadows of the `bindings`, since a kernel without a CPU reference may write
+    /// to them.
+    pub(crate) fn invalidate(&self, bindings: &[Binding]) {
+        let mut shadows = self.shadows.lock();
+        if shadows.is_empty() {
+            return;
+        }
+
+        for binding in bindings {
+            let id = binding.memory.descriptor().id;
+            shadows.retain(|(shadow_id, _, _), _| *shadow_id != id);
+        }
+    }
+
+    /// Returns a future comparing the result of the read of the `descriptors` with their
+    /// shadows, which are consumed.
+    pub(crate) fn take(&self, descriptors: &[CopyDescriptor]) -> ShadowCheck {
+        let mut shadows = self.shadows.lock();
+
+        ShadowCheck {
+            shadows: descriptors
+                .iter()
+                .map(|desc| {
+                    shadows
+                        .remove(&shadow_key(&desc.handle))
+                        .map(|shadow| (shadow, desc.elem_size))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The shadows of the buffers of a read, to compare with the bytes read from the device.
+pub(crate) struct ShadowCheck {
+    shadows: Vec<Option<(Shadow, usize)>>,
+}
+
+impl ShadowCheck {
+    /// Compare the bytes read from the device with the shadows, failing at the first element
+    /// that differs.
+    ///
+    /// Reads that don't cover the whole shadow, such as strided reads, aren't compared.
+    pub(crate) fn check(self, buffers: &[Bytes]) -> Result<(), ServerError> {
+        for (bytes, shadow) in buffers.iter().zip(self.shadows) {
+            let Some((shadow, elem_size)) = shadow else {
+                continue;
+            };
+            let bytes = &bytes[..];
+            if bytes.len() != shadow.bytes.len() {
+                continue;
+            }
+
+            let elem_size = elem_size.max(1);
+            let divergent = bytes
+                .chunks(elem_size)
+                .zip(shadow.bytes.chunks(elem_size))
+                .position(|(device, reference)| device != reference);
+
+            if let Some(index) = divergent {
+                let range = index * elem_size..((index + 1) * elem_size).min(bytes.len());
+                return Err(ServerError::Generic {
+                    reason: format!(
+                        "Kernel {} diverges from its CPU reference at element {index}: the device \
+                         has {:?}, the reference {:?}",
+                        shadow.kernel,
+                        &bytes[range.clone()],
+                        &shadow.bytes[range],
+                    ),
+                    backtrace: BackTrace::capture(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -8,7 +8,9 @@ use cubecl_runtime::{
     client::ComputeClient,
     compiler::{CompilationError, Compiler},
     logging::ServerLogger,
-    memory_management::{MemoryConfiguration, MemoryManagement, MemoryManagementOptions},
+    memory_management::{
+        MemoryConfiguration, MemoryManagement, MemoryManagementOptions, MemoryUsageSnapshot,
+    },
     runtime::Runtime,
     server::ExecutionMode,
     storage::BytesStorage,
@@ -53,14 +55,16 @@ fn init_server() -> DummyServer {
         alignment: 32,
     };
 
+    let memory_usage = Arc::new(MemoryUsageSnapshot::default());
+
     let memory_management = MemoryManagement::from_configuration(
         storage,
         &mem_properties,
         MemoryConfiguration::default(),
         Arc::new(ServerLogger::default()),
-        MemoryManagementOptions::new("Main CPU Memory"),
+        MemoryManagementOptions::new("Main CPU Memory").usage_snapshot(memory_usage.clone()),
     );
-    DummyServer::new(memory_management, mem_properties)
+    DummyServer::new(memory_management, mem_properties, memory_usage)
 }
 
 pub fn test_client(device: &DummyDevice) -> DummyClient {
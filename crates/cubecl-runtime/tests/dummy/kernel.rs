@@ -1,4 +1,4 @@
-use cubecl_runtime::{id::KernelId, storage::BytesResource};
+use cubecl_runtime::{id::KernelId, kernel::CpuReference, storage::BytesResource};
 
 /// The `DummyKernel` trait should be implemented for every supported operation
 pub trait DummyKernel: Sync + Send + 'static + core::fmt::Debug {
@@ -9,6 +9,10 @@ pub trait DummyKernel: Sync + Send + 'static + core::fmt::Debug {
     fn name(&self) -> &'static str {
         core::any::type_name::<Self>()
     }
+
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        None
+    }
 }
 
 /// Contains the algorithm for element-wise addition
@@ -34,3 +38,53 @@ impl DummyKernel for DummyElementwiseAddition {
         KernelId::new::<Self>()
     }
 }
+
+/// Host implementation of an element-wise addition.
+#[derive(Debug)]
+pub struct ElementwiseAdditionReference;
+
+impl CpuReference for ElementwiseAdditionReference {
+    fn execute(&self, _cube_count: [u32; 3], buffers: &mut [Vec<u8>], _info: &[u64]) {
+        let (inputs, out) = buffers.split_at_mut(2);
+
+        for (i, out) in out[0].iter_mut().enumerate() {
+            *out = inputs[0][i] + inputs[1][i];
+        }
+    }
+}
+
+/// Runs a kernel, verified against the CPU reference of an element-wise addition.
+#[derive(Debug)]
+pub struct WithAdditionReference<K>(pub K);
+
+impl<K: DummyKernel> DummyKernel for WithAdditionReference<K> {
+    fn compute(&self, resources: &mut [&mut BytesResource]) {
+        self.0.compute(resources)
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>()
+    }
+
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        Some(&ElementwiseAdditionReference)
+    }
+}
+
+/// Marks the byte of the output at the x offset of the tile written in the first buffer.
+#[derive(Debug)]
+pub struct DummyMarkTileOffset;
+
+impl DummyKernel for DummyMarkTileOffset {
+    fn compute(&self, inputs: &mut [&mut BytesResource]) {
+        let offset = inputs[0].read();
+        let x = u32::from_le_bytes([offset[0], offset[1], offset[2], offset[3]]);
+        let out = &mut inputs[1].write();
+
+        out[x as usize] += 1;
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>()
+    }
+}
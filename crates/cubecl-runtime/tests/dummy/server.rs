@@ -1,6 +1,9 @@
 use super::DummyKernel;
 use crate::dummy::DummyCompiler;
-use cubecl_common::{bytes::Bytes, future::DynFut, profile::ProfileDuration, stream_id::StreamId};
+use cubecl_common::{
+    backtrace::BackTrace, bytes::Bytes, future::DynFut, profile::ProfileDuration,
+    stream_id::StreamId,
+};
 use cubecl_ir::{
     DeviceProperties, ElemType, HardwareProperties, MemoryDeviceProperties, StorageType, UIntKind,
     VectorSize, features::Features,
@@ -9,19 +12,26 @@ use cubecl_runtime::{
     allocator::ContiguousMemoryLayoutPolicy,
     compiler::{CompilationError, CubeTask},
     id::KernelId,
-    kernel::{CompiledKernel, KernelMetadata},
+    kernel::{CompiledKernel, CpuReference, KernelBinary, KernelMetadata},
     logging::ServerLogger,
-    memory_management::{ManagedMemoryHandle, MemoryAllocationMode, MemoryManagement, MemoryUsage},
+    memory_management::{
+        AccessHint, ChunkInfo, ManagedMemoryHandle, MemoryAllocationMode, MemoryManagement,
+        MemoryUsage, MemoryUsageSnapshot,
+    },
+    read_path::ReadPath,
     server::{
-        Binding, ComputeServer, CopyDescriptor, CubeCount, CubeDim, ExecutionMode, Handle,
-        KernelArguments, ProfileError, ProfilingToken, ServerCommunication, ServerError,
-        ServerUtilities,
+        Binding, ComputeServer, CopyDescriptor, CubeCount, CubeDim, ExecutionMode, Handle, IoError,
+        KernelArguments, LaunchError, ProfileError, ProfilingToken, ServerCommunication,
+        ServerError, ServerUtilities,
     },
     storage::{BytesResource, BytesStorage, ComputeStorage, ManagedResource},
     timestamp_profiler::TimestampProfiler,
 };
 use cubecl_zspace::{Shape, Strides};
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 /// The dummy server is used to test the cubecl-runtime infrastructure.
 /// It uses simple memory management with a bytes storage on CPU, without asynchronous tasks.
@@ -30,6 +40,12 @@ pub struct DummyServer {
     memory_management: MemoryManagement<BytesStorage>,
     timestamps: TimestampProfiler,
     utilities: Arc<ServerUtilities<Self>>,
+    /// The precompiled kernels that were loaded.
+    precompiled: HashMap<u128, KernelBinary>,
+    /// The ids of the kernels launched so far, with their execution mode.
+    launched: HashSet<KernelId>,
+    /// The streams synchronized so far.
+    synced: HashSet<StreamId>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +65,10 @@ impl KernelMetadata for KernelTask {
     fn address_type(&self) -> cubecl_ir::StorageType {
         ElemType::UInt(UIntKind::U32).into()
     }
+
+    fn cpu_reference(&self) -> Option<&dyn CpuReference> {
+        self.kernel.cpu_reference()
+    }
 }
 
 impl core::fmt::Display for KernelTask {
@@ -71,6 +91,7 @@ impl CubeTask<DummyCompiler> for KernelTask {
             source: String::new(),
             repr: Some(self.clone()),
             cube_dim: CubeDim::new_single(),
+            buffer_elem_sizes: Vec::new(),
             debug_info: None,
         })
     }
@@ -106,11 +127,36 @@ impl ComputeServer for DummyServer {
         self.utilities.clone()
     }
 
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, _stream_id: StreamId) {
-        let reserved = self.memory_management.reserve(size).unwrap();
-        self.memory_management
-            .bind(reserved, memory.clone(), 0)
-            .unwrap();
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        _stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let reserved = self.memory_management.reserve(size)?;
+        self.memory_management.bind(reserved, memory.clone(), 0)
+    }
+
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        _stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let reserved = self.memory_management.reserve_aligned(size, alignment)?;
+        self.memory_management.bind(reserved, memory.clone(), 0)
+    }
+
+    fn initialize_memory_with_hint(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        hint: AccessHint,
+        _stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let reserved = self.memory_management.reserve_with_hint(size, Some(hint))?;
+        self.memory_management.bind(reserved, memory.clone(), 0)
     }
 
     fn read(
@@ -118,6 +164,16 @@ impl ComputeServer for DummyServer {
         descriptors: Vec<CopyDescriptor>,
         _stream_id: StreamId,
     ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        // Like WGPU, only contiguous reads are supported.
+        if descriptors.iter().any(|desc| desc.shape.len() > 1) {
+            return Box::pin(async {
+                Err(IoError::UnsupportedStrides {
+                    backtrace: BackTrace::capture(),
+                }
+                .into())
+            });
+        }
+
         let bytes: Vec<_> = descriptors
             .into_iter()
             .map(|b| {
@@ -136,7 +192,9 @@ impl ComputeServer for DummyServer {
             })
             .collect();
 
+        let utilities = self.utilities.clone();
         Box::pin(async move {
+            utilities.read_path.record(ReadPath::Mapped);
             Ok(bytes
                 .into_iter()
                 .map(|(b, size, _binding)| {
@@ -148,16 +206,20 @@ impl ComputeServer for DummyServer {
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor, Bytes)>, _stream_id: StreamId) {
         for (descriptor, data) in descriptors {
-            let storage_h = self
+            let mut storage_h = self
                 .memory_management
                 .get_storage(descriptor.handle.memory)
                 .unwrap();
+            if let Some(offset) = descriptor.handle.offset_start {
+                storage_h = storage_h.offset_start(offset);
+            }
             let mut bytes = self.memory_management.storage().get(&storage_h);
             bytes.write()[..data.len()].copy_from_slice(&data);
         }
     }
 
-    fn sync(&mut self, _stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+    fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        self.synced.insert(stream_id);
         Box::pin(async move { Ok(()) })
     }
 
@@ -178,11 +240,28 @@ impl ComputeServer for DummyServer {
     unsafe fn launch(
         &mut self,
         kernel: Self::Kernel,
-        _count: CubeCount,
+        count: CubeCount,
         bindings: KernelArguments,
         mode: ExecutionMode,
         stream_id: StreamId,
     ) {
+        let count = match count {
+            CubeCount::Static(x, y, z) => [x, y, z],
+            CubeCount::Dynamic(binding) => {
+                let resource = self
+                    .memory_management
+                    .get_resource(binding.memory, binding.offset_start, binding.offset_end)
+                    .unwrap();
+                let count: &[u32] = bytemuck::cast_slice(&resource.read()[0..12]);
+                [count[0], count[1], count[2]]
+            }
+        };
+
+        // Empty dispatches are skipped like on real backends.
+        if count.contains(&0) {
+            return;
+        }
+
         let mut resources: Vec<_> = bindings
             .buffers
             .into_iter()
@@ -206,6 +285,10 @@ impl ComputeServer for DummyServer {
                 .unwrap()
         });
 
+        let mut launched = kernel.id();
+        launched.mode(mode);
+        self.launched.insert(launched);
+
         let mut resources: Vec<_> = resources.iter_mut().collect();
         let kernel = kernel
             .compile(&mut DummyCompiler, &(), mode, kernel.address_type())
@@ -213,6 +296,45 @@ impl ComputeServer for DummyServer {
         kernel.repr.unwrap().compute(resources.as_mut_slice());
     }
 
+    fn is_compiled(&mut self, kernel_id: &KernelId) -> bool {
+        self.launched.contains(kernel_id)
+    }
+
+    fn compile_binaries(
+        &mut self,
+        kernels: Vec<Self::Kernel>,
+        modes: Vec<ExecutionMode>,
+    ) -> Result<Vec<KernelBinary>, LaunchError> {
+        // Dummy kernels aren't compiled, so their binaries are empty.
+        let mut binaries = Vec::new();
+        for kernel in kernels {
+            for mode in &modes {
+                let mut kernel_id = kernel.id();
+                kernel_id.mode(*mode);
+                let id = kernel_id.stable_hash();
+
+                binaries.push(match self.precompiled.get(&id) {
+                    Some(binary) => binary.clone(),
+                    None => KernelBinary {
+                        id,
+                        entrypoint_name: kernel.name().into(),
+                        shared_mem_bytes: 0,
+                        buffer_elem_sizes: Vec::new(),
+                        binary: Vec::new(),
+                    },
+                });
+            }
+        }
+
+        Ok(binaries)
+    }
+
+    fn load_compiled_kernels(&mut self, kernels: Vec<KernelBinary>) {
+        for kernel in kernels {
+            self.precompiled.insert(kernel.id, kernel);
+        }
+    }
+
     fn flush(&mut self, _stream_id: StreamId) -> Result<(), ServerError> {
         // Nothing to do with dummy backend.
         Ok(())
@@ -222,10 +344,25 @@ impl ComputeServer for DummyServer {
         Ok(self.memory_management.memory_usage())
     }
 
+    fn chunk_map(&mut self, _stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError> {
+        Ok(self.memory_management.chunk_map())
+    }
+
+    fn has_pending_work(&mut self, _stream_id: StreamId) -> bool {
+        // Tasks are executed as they are submitted.
+        false
+    }
+
     fn memory_cleanup(&mut self, _stream_id: StreamId) {
         self.memory_management.cleanup(true);
     }
 
+    fn release(&mut self, bindings: Vec<Binding>, _stream_id: StreamId) {
+        for binding in bindings {
+            self.memory_management.release(binding.memory).unwrap();
+        }
+    }
+
     fn start_profile(&mut self, _stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
         Ok(self.timestamps.start())
     }
@@ -247,6 +384,7 @@ impl DummyServer {
     pub fn new(
         memory_management: MemoryManagement<BytesStorage>,
         mem_props: MemoryDeviceProperties,
+        memory_usage: Arc<MemoryUsageSnapshot>,
     ) -> Self {
         let hardware = HardwareProperties {
             load_width: 128,
@@ -254,6 +392,7 @@ impl DummyServer {
             plane_size_max: 32,
             max_bindings: 32,
             max_shared_memory_size: 48000,
+            max_uniform_buffer_size: 65536,
             max_cube_count: (u16::MAX as u32, u16::MAX as u32, u16::MAX as u32),
             max_units_per_cube: 1024,
             max_cube_dim: (1024, 1024, 64),
@@ -268,26 +407,33 @@ impl DummyServer {
         let props = DeviceProperties::new(features, mem_props, hardware, timing_method);
         let logger = Arc::new(ServerLogger::default());
 
-        let utilities = Arc::new(ServerUtilities::new(
-            props,
-            logger,
-            (),
-            ContiguousMemoryLayoutPolicy::new(4),
-        ));
+        let mut utilities =
+            ServerUtilities::new(props, logger, (), ContiguousMemoryLayoutPolicy::new(4));
+        utilities.memory_usage = memory_usage;
+        let utilities = Arc::new(utilities);
 
         Self {
             memory_management,
             utilities,
             timestamps: TimestampProfiler::default(),
+            precompiled: HashMap::new(),
+            launched: HashSet::new(),
+            synced: HashSet::new(),
         }
     }
 
+    /// Whether the stream was synchronized.
+    pub fn was_synced(&self, stream_id: StreamId) -> bool {
+        self.synced.contains(&stream_id)
+    }
+
     /// Utility to create a new buffer and immediately copy contiguous data into it
     fn bind_with_data(&mut self, data: &[u8], handle: Handle, stream_id: StreamId) {
         let strides: Strides = [1].into();
         let shape: Shape = [data.len()].into();
 
-        self.initialize_memory(handle.memory.clone(), handle.size(), stream_id);
+        self.initialize_memory(handle.memory.clone(), handle.size(), stream_id)
+            .unwrap();
         self.write(
             vec![(
                 CopyDescriptor::new(handle.binding(), shape, strides, 1),
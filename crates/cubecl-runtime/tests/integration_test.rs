@@ -2,10 +2,28 @@ mod dummy;
 
 use crate::dummy::{DummyDevice, DummyElementwiseAddition, test_client};
 
+use core::pin::Pin;
+use cubecl_common::bytes::Bytes;
+use cubecl_common::future::block_on;
+use cubecl_common::stream_id::StreamId;
+use cubecl_runtime::compiler::CubeTask;
+use cubecl_runtime::handle_pool::HandlePool;
+use cubecl_runtime::id::KernelId;
+use cubecl_runtime::kernel::KernelMetadata;
+use cubecl_runtime::read_path::ReadPath;
 use cubecl_runtime::server::CubeCount;
+use cubecl_runtime::server::ExecutionMode;
+use cubecl_runtime::server::IoError;
 use cubecl_runtime::server::KernelArguments;
+use cubecl_runtime::server::ServerError;
+use cubecl_runtime::server::SyncTimeout;
+use cubecl_runtime::storage::BytesResource;
+use cubecl_runtime::stream_processor::StreamProcessor;
+use cubecl_runtime::verify::VerifyMode;
 use cubecl_runtime::{local_tuner, tune::LocalTuner};
 use dummy::*;
+use futures_core::Stream;
+use std::time::Duration;
 
 #[test_log::test]
 fn created_resource_is_the_same_when_read() {
@@ -18,6 +36,294 @@ fn created_resource_is_the_same_when_read() {
     assert_eq!(resource, obtained_resource)
 }
 
+#[test_log::test]
+fn cancelled_read_resolves_to_an_error() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(&[0, 1, 2]);
+
+    let (read_handle, read) = client.read_cancellable(handle.clone());
+    read_handle.cancel();
+    assert!(matches!(block_on(read), Err(ServerError::Cancelled { .. })));
+
+    let (_read_handle, read) = client.read_cancellable(handle);
+    assert_eq!(block_on(read).unwrap().to_vec(), [0, 1, 2]);
+}
+
+#[test_log::test]
+fn read_physical_includes_alignment_padding() {
+    let client = test_client(&DummyDevice);
+    let resource = client.create_from_slice(&[0, 1, 2]);
+
+    let logical = client.read_one(resource.clone()).unwrap();
+    let physical = client.read_physical(resource).unwrap();
+
+    assert_eq!(logical.len(), 3);
+    assert_eq!(physical.len(), 4);
+    assert_eq!(&physical[..3], &logical[..]);
+}
+
+#[test_log::test]
+fn fill_repeats_pattern() {
+    let client = test_client(&DummyDevice);
+    let handle = client.empty(6);
+
+    client.fill(handle.clone(), &[1, 2]).unwrap();
+    let obtained_resource = client.read_one(handle.clone()).unwrap().to_vec();
+
+    assert_eq!(obtained_resource, Vec::from([1, 2, 1, 2, 1, 2]));
+    assert!(client.fill(handle.clone(), &[1, 2, 3, 4]).is_err());
+    assert!(client.fill(handle, &[]).is_err());
+}
+
+#[test_log::test]
+fn create_staged_flushes_writes_before_reads() {
+    let client = test_client(&DummyDevice);
+    let (handle, mut writer) = client.create_staged(8);
+
+    writer.write(1, &[1, 2]).unwrap();
+    writer.write(6, &[3]).unwrap();
+    assert_eq!(
+        client.read_one(handle.clone()).unwrap().to_vec(),
+        [0, 1, 2, 0, 0, 0, 3, 0]
+    );
+
+    writer.write(4, &[4]).unwrap();
+    assert!(writer.write(7, &[0, 0]).is_err());
+    assert_eq!(
+        client.read_one(handle).unwrap().to_vec(),
+        [0, 1, 2, 0, 4, 0, 3, 0]
+    );
+}
+
+#[test_log::test]
+fn fetch_add_u32_returns_the_previous_value() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(bytemuck::cast_slice(&[1u32, 10]));
+
+    let first = block_on(client.fetch_add_u32(handle.clone().binding(), 4, 5));
+    let second = block_on(client.fetch_add_u32(handle.clone().binding(), 4, 5));
+
+    assert_eq!(first.unwrap(), 10);
+    assert_eq!(second.unwrap(), 15);
+    assert!(block_on(client.fetch_add_u32(handle.clone().binding(), 6, 1)).is_err());
+
+    let obtained = client.read_one(handle).unwrap();
+    assert_eq!(bytemuck::cast_slice::<u8, u32>(&obtained), [1, 20]);
+}
+
+#[test_log::test]
+fn write_u32_only_writes_the_value() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(bytemuck::cast_slice(&[1u32, 2, 3]));
+
+    client.write_u32(handle.clone().binding(), 4, 42).unwrap();
+
+    assert!(client.write_u32(handle.clone().binding(), 10, 1).is_err());
+    let obtained = client.read_one(handle).unwrap();
+    assert_eq!(bytemuck::cast_slice::<u8, u32>(&obtained), [1, 42, 3]);
+}
+
+#[test_log::test]
+fn handle_pool_recycles_dropped_handles() {
+    let client = test_client(&DummyDevice);
+    let pool = HandlePool::new(client.clone(), 8, 1);
+
+    let first = pool.acquire();
+    let second = pool.acquire();
+    assert_eq!(first.size(), 8);
+    assert_eq!(pool.available(), 0);
+
+    core::mem::drop(first);
+    core::mem::drop(second);
+    assert_eq!(pool.available(), 1);
+
+    let handle = pool.acquire().into_handle();
+    assert_eq!(pool.available(), 0);
+    assert_eq!(client.read_one(handle).unwrap().len(), 8);
+}
+
+#[test_log::test]
+fn handle_pool_recycles_handles_after_launch() {
+    let client = test_client(&DummyDevice);
+    let pool = HandlePool::new(client.clone(), 3, 1);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+
+    let out = pool.acquire();
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        KernelArguments::new().with_buffers(vec![
+            lhs.binding(),
+            rhs.binding(),
+            out.clone().binding(),
+        ]),
+    );
+    assert_eq!(client.read_one(out.clone()).unwrap().to_vec(), [4, 5, 6]);
+
+    core::mem::drop(out);
+    assert_eq!(pool.available(), 1);
+}
+
+#[test_log::test]
+fn launch_if_runs_with_a_non_empty_cube_count() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.create_from_slice(&[0, 0, 0]);
+    let condition = client.create_from_slice(bytemuck::cast_slice(&[1u32, 1, 1]));
+
+    client.launch_if(
+        condition,
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        KernelArguments::new().with_buffers(vec![
+            lhs.binding(),
+            rhs.binding(),
+            out.clone().binding(),
+        ]),
+    );
+
+    assert_eq!(client.read_one(out).unwrap().to_vec(), [4, 5, 6]);
+}
+
+#[test_log::test]
+fn last_dynamic_count_reads_the_resolved_cube_count() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.create_from_slice(&[0, 0, 0]);
+    let condition = client.create_from_slice(bytemuck::cast_slice(&[1u32, 2, 3]));
+
+    client.launch_if(
+        condition,
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        KernelArguments::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.binding()]),
+    );
+
+    assert_eq!(block_on(client.last_dynamic_count()).unwrap(), [1, 2, 3]);
+    assert!(block_on(client.last_dynamic_count()).is_err());
+}
+
+#[test_log::test]
+fn launch_if_skips_an_empty_cube_count() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.create_from_slice(&[0, 0, 0]);
+    let condition = client.create_from_slice(bytemuck::cast_slice(&[0u32, 1, 1]));
+
+    client.launch_if(
+        condition,
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        KernelArguments::new().with_buffers(vec![
+            lhs.binding(),
+            rhs.binding(),
+            out.clone().binding(),
+        ]),
+    );
+
+    assert_eq!(client.read_one(out).unwrap().to_vec(), [0, 0, 0]);
+}
+
+#[test_log::test]
+fn sync_timeout_completes_when_the_device_is_idle() {
+    let client = test_client(&DummyDevice);
+    client.create_from_slice(&[0, 1, 2]);
+
+    let report = block_on(client.sync_timeout(Duration::from_secs(10))).unwrap();
+
+    assert!(report.elapsed < Duration::from_secs(10));
+}
+
+#[test_log::test]
+fn sync_timeout_syncs_the_stream_of_the_caller() {
+    let client = test_client(&DummyDevice);
+    let stream_id = StreamId::current();
+
+    block_on(client.sync_timeout(Duration::from_secs(10))).unwrap();
+
+    assert_eq!(
+        client.downcast_server(move |server: &mut DummyServer| server.was_synced(stream_id)),
+        Some(true)
+    );
+}
+
+#[test_log::test]
+fn staged_writes_are_pending_work() {
+    let client = test_client(&DummyDevice);
+    let (_handle, mut writer) = client.create_staged(4);
+
+    writer.write(0, &[1, 2, 3, 4]).unwrap();
+
+    assert!(client.has_pending_work());
+}
+
+#[test_log::test]
+fn sync_timeout_fails_when_the_device_is_busy() {
+    let client = test_client(&DummyDevice);
+    let size = 200;
+    let lhs = client.create_from_slice(&vec![1; size]);
+    let rhs = client.create_from_slice(&vec![1; size]);
+    let out = client.empty(size);
+
+    // Sleeps a millisecond per element.
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAdditionSlowWrong)),
+        CubeCount::Static(1, 1, 1),
+        KernelArguments::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.binding()]),
+    );
+    let result = block_on(client.sync_timeout(Duration::from_millis(1)));
+
+    assert!(matches!(result, Err(SyncTimeout::Elapsed { .. })));
+}
+
+#[test_log::test]
+fn clone_onto_submits_to_the_given_stream() {
+    let client = test_client(&DummyDevice);
+    let stream_id = StreamId { value: 4242 };
+    let client_stream = unsafe { client.clone_onto(stream_id) };
+
+    let handle = client_stream.create_from_slice(&[0, 1, 2]);
+
+    assert_eq!(handle.stream, stream_id);
+    assert_ne!(client.empty(4).stream, stream_id);
+    assert_eq!(client.read_one(handle).unwrap().to_vec(), [0, 1, 2]);
+}
+
+#[test_log::test]
+fn verify_mode_compares_reads_with_the_cpu_reference() {
+    let client = test_client(&DummyDevice);
+    let launch = |kernel: Box<dyn CubeTask<DummyCompiler>>| {
+        let lhs = client.create_from_slice(&[0, 1, 2]);
+        let rhs = client.create_from_slice(&[0, 4, 4]);
+        let out = client.empty(3);
+        client.launch(
+            kernel,
+            CubeCount::Static(1, 1, 1),
+            KernelArguments::new().with_buffers(vec![
+                lhs.binding(),
+                rhs.binding(),
+                out.clone().binding(),
+            ]),
+        );
+        client.read_one(out)
+    };
+    client.set_verify_mode(VerifyMode::Enabled);
+
+    let matching = launch(Box::new(KernelTask::new(WithAdditionReference(
+        DummyElementwiseAddition,
+    ))));
+    // Copies the lhs instead of adding, so only the first element matches the reference.
+    let divergent = launch(Box::new(KernelTask::new(WithAdditionReference(
+        DummyElementwiseAdditionSlowWrong,
+    ))));
+    client.set_verify_mode(VerifyMode::Disabled);
+
+    assert_eq!(matching.unwrap().to_vec(), [0, 5, 6]);
+    let err = divergent.unwrap_err().to_string();
+    assert!(err.contains("at element 1"), "{err}");
+}
+
 #[test_log::test]
 fn empty_allocates_memory() {
     let client = test_client(&DummyDevice);
@@ -28,6 +334,237 @@ fn empty_allocates_memory() {
     assert_eq!(empty_resource.len(), 4);
 }
 
+#[test_log::test]
+fn memory_usage_is_published_on_allocation() {
+    let client = test_client(&DummyDevice);
+    let _handle = client.create_from_slice(&[0, 1, 2, 3]);
+
+    let usage = client.memory_usage();
+
+    assert!(usage.number_allocs >= 1);
+    assert!(usage.bytes_reserved >= usage.bytes_in_use);
+}
+
+#[test_log::test]
+fn memory_usage_settled_includes_pending_allocations() {
+    let client = test_client(&DummyDevice);
+    let _handle = client.empty(1024 * 1024);
+
+    let usage = block_on(client.memory_usage_settled()).unwrap();
+
+    assert!(usage.bytes_in_use >= 1024 * 1024);
+    assert!(usage.bytes_reserved >= usage.bytes_in_use);
+}
+
+#[test_log::test]
+fn bytes_in_flight_counts_pending_reads() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(&[0, 1, 2, 3]);
+
+    let read = client.read_async(vec![handle]);
+    assert_eq!(client.bytes_in_flight(), 4);
+
+    block_on(read).unwrap();
+    assert_eq!(client.bytes_in_flight(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Transient handle used after")]
+fn transient_handle_panics_after_sync() {
+    let client = test_client(&DummyDevice);
+    let transient = client.empty_transient(16);
+
+    block_on(client.sync()).unwrap();
+    let _ = client.read_one(transient);
+}
+
+#[test_log::test]
+#[cfg(debug_assertions)]
+fn transient_handle_survives_the_sync_of_another_stream() {
+    let client = test_client(&DummyDevice);
+    // SAFETY: The dummy server orders nothing across streams.
+    let other = unsafe { client.clone_onto(StreamId { value: 1_000 }) };
+    let transient = client.empty_transient(4);
+
+    block_on(other.sync()).unwrap();
+    assert!(client.read_one(transient).is_ok());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Transient handle used after")]
+fn generation_handle_panics_after_free() {
+    let client = test_client(&DummyDevice);
+    let generation = client.begin_generation();
+    let handle = client.create_from_slice(&[0, 1, 2]);
+    client.free_generation(generation);
+
+    let _ = client.read_one(handle);
+}
+
+#[test_log::test]
+fn read_sync_gives_the_bytes_of_the_handle() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        KernelArguments::new().with_buffers(vec![
+            lhs.binding(),
+            rhs.binding(),
+            out.clone().binding(),
+        ]),
+    );
+
+    assert_eq!(client.read_sync(out).unwrap().as_slice(), &[4, 5, 6]);
+}
+
+#[test_log::test]
+fn read_sync_respects_the_offsets_of_the_handle() {
+    let client = test_client(&DummyDevice);
+    let handle = client
+        .create_from_slice(&[0, 1, 2, 3, 4, 5, 6, 7])
+        .offset_start(2)
+        .offset_end(2);
+
+    assert_eq!(client.read_sync(handle).unwrap().as_slice(), &[2, 3, 4, 5]);
+}
+
+#[test_log::test]
+fn handles_outlive_the_sync_while_their_generation_is_current() {
+    let client = test_client(&DummyDevice);
+    let generation = client.begin_generation();
+    let handle = client.create_from_slice(&[0, 1, 2]);
+
+    block_on(client.sync()).unwrap();
+    let data = client.read_one(handle.clone()).unwrap();
+    client.free_generation(generation);
+
+    assert_eq!(data.to_vec(), vec![0, 1, 2]);
+}
+
+#[test_log::test]
+fn execute_elementwise_addition_with_bind_group() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let group = client.create_bind_group(vec![lhs]);
+
+    for (rhs, expected) in [([4, 4, 4], [4, 5, 6]), ([1, 2, 3], [1, 3, 5])] {
+        let rhs = client.create_from_slice(&rhs);
+        let out = client.empty(3);
+
+        client.launch_with_group(
+            Box::new(KernelTask::new(DummyElementwiseAddition)),
+            CubeCount::Static(1, 1, 1),
+            &group,
+            KernelArguments::new().with_buffers(vec![rhs.binding(), out.clone().binding()]),
+        );
+
+        let obtained_resource = client.read_one(out).unwrap().to_vec();
+        assert_eq!(obtained_resource, expected);
+    }
+}
+
+#[test_log::test]
+fn execute_elementwise_addition_with_bind_group_unchecked() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+    let group = client.create_bind_group(vec![lhs, rhs]);
+
+    // SAFETY: The dummy kernel stays in bounds.
+    unsafe {
+        client.launch_with_group_unchecked(
+            Box::new(KernelTask::new(DummyElementwiseAddition)),
+            CubeCount::Static(1, 1, 1),
+            &group,
+            KernelArguments::new().with_buffers(vec![out.clone().binding()]),
+        )
+    };
+
+    assert_eq!(client.read_one(out).unwrap().to_vec(), [4, 5, 6]);
+}
+
+#[test_log::test]
+fn suggest_cube_size_falls_back_to_hardware_heuristic() {
+    let client = test_client(&DummyDevice);
+    let hardware = &client.properties().hardware;
+
+    let cube_size = client.suggest_cube_size(Box::new(KernelTask::new(DummyElementwiseAddition)));
+
+    assert!(cube_size > 0);
+    assert!(cube_size <= hardware.max_units_per_cube);
+    assert_eq!(cube_size % hardware.plane_size_max, 0);
+}
+
+#[test_log::test]
+fn compiled_blob_round_trips() {
+    let client = test_client(&DummyDevice);
+    let kernel = || Box::new(KernelTask::new(DummyElementwiseAddition));
+    let mut id = kernel().id();
+    id.mode(ExecutionMode::Checked);
+    let id = id.stable_hash();
+
+    // Kernels that were never launched are compiled too.
+    let mut blob = client.compile_all(vec![kernel()]).unwrap();
+    let mut precompiled = blob.kernels[0].clone();
+    assert_eq!(precompiled.id, id);
+
+    precompiled.entrypoint_name = "precompiled".into();
+    blob.kernels = vec![precompiled];
+    client.load_blob(&blob).unwrap();
+    let compiled = client.compile_all(vec![kernel()]).unwrap();
+    assert_eq!(compiled.kernels[0].entrypoint_name, "precompiled");
+
+    blob.properties_hash = blob.properties_hash.wrapping_add(1);
+    assert!(client.load_blob(&blob).is_err());
+}
+
+#[test_log::test]
+fn cooperative_launch_fails_when_unsupported() {
+    let client = test_client(&DummyDevice);
+    let out = client.empty(3);
+
+    let result = client.launch_cooperative(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        KernelArguments::new().with_buffers(vec![out.binding()]),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test_log::test]
+#[cfg(feature = "std")]
+fn exported_resources_are_the_same_when_imported() {
+    let client = test_client(&DummyDevice);
+    let resources = [Vec::from([0, 1, 2]), Vec::from([3, 4, 5, 6, 7])];
+    let handles = resources
+        .iter()
+        .map(|data| client.create_from_slice(data))
+        .collect();
+
+    let mut exported = Vec::new();
+    client.export(handles, &mut exported).unwrap();
+    let imported = client.import(exported.as_slice()).unwrap();
+
+    assert_eq!(imported.len(), 2);
+    for (resource, handle) in resources.iter().zip(imported) {
+        assert_eq!(resource, &client.read_one(handle).unwrap().to_vec());
+    }
+
+    let truncated = client.import(&exported[..exported.len() - 1]).unwrap_err();
+    assert_eq!(truncated.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let invalid = client.import([0u8; 16].as_slice()).unwrap_err();
+    assert_eq!(invalid.kind(), std::io::ErrorKind::InvalidData);
+}
+
 #[test_log::test]
 fn execute_elementwise_addition() {
     let client = test_client(&DummyDevice);
@@ -50,6 +587,141 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test_log::test]
+fn launch_profiled_records_the_time_under_the_label() {
+    use cubecl_runtime::profiler::Profiler;
+
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+    let profiler = Profiler::new();
+
+    for _ in 0..2 {
+        client
+            .launch_profiled(
+                &profiler,
+                "addition",
+                Box::new(KernelTask::new(DummyElementwiseAddition)),
+                CubeCount::Static(1, 1, 1),
+                KernelArguments::new().with_buffers(vec![
+                    lhs.clone().binding(),
+                    rhs.clone().binding(),
+                    out.clone().binding(),
+                ]),
+            )
+            .unwrap();
+    }
+
+    assert_eq!(profiler.stats("addition").unwrap().count, 2);
+    assert!(profiler.report().contains("addition"));
+}
+
+#[test_log::test]
+fn benchmark_returns_a_sample_per_iteration() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let samples = client
+        .benchmark(
+            || Box::new(KernelTask::new(DummyElementwiseAddition)),
+            CubeCount::Static(1, 1, 1),
+            KernelArguments::new().with_buffers(vec![
+                lhs.binding(),
+                rhs.binding(),
+                out.clone().binding(),
+            ]),
+            5,
+        )
+        .unwrap();
+
+    assert_eq!(samples.len(), 5);
+    assert_eq!(client.read_one(out).unwrap().to_vec(), [4, 5, 6]);
+}
+
+#[test_log::test]
+fn duplicate_copies_the_binding_into_a_new_handle() {
+    let client = test_client(&DummyDevice);
+    let src = client.create_from_slice(&[3, 1, 4, 1, 5]);
+
+    let copy = client.duplicate(src.clone().binding());
+    client.fill(src.clone(), &[0]).unwrap();
+
+    assert_eq!(client.read_one(copy).unwrap().to_vec(), [3, 1, 4, 1, 5]);
+    assert_eq!(client.read_one(src).unwrap().to_vec(), [0, 0, 0, 0, 0]);
+}
+
+#[test_log::test]
+fn copy_clamped_copies_the_smallest_size() {
+    let client = test_client(&DummyDevice);
+    let small = client.create_from_slice(&[3, 1, 4]);
+    let large = client.create_from_slice(&[9, 9, 9, 9, 9]);
+
+    assert_eq!(
+        client.copy_clamped(small.clone().binding(), large.clone().binding()),
+        3
+    );
+    assert_eq!(
+        client.read_one(large.clone()).unwrap().to_vec(),
+        [3, 1, 4, 9, 9]
+    );
+
+    let grown = client.create_from_slice(&[5, 9, 2, 6, 5]);
+    assert_eq!(
+        client.copy_clamped(grown.binding(), small.clone().binding()),
+        3
+    );
+    assert_eq!(client.read_one(small).unwrap().to_vec(), [5, 9, 2]);
+}
+
+#[test_log::test]
+fn create_owned_holds_the_data() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_owned(vec![9, 8, 7, 6]);
+
+    assert_eq!(client.read_one(handle).unwrap().to_vec(), [9, 8, 7, 6]);
+}
+
+#[test_log::test]
+fn read_counter_reads_the_first_u32() {
+    let client = test_client(&DummyDevice);
+    let counter = client.empty_counter();
+    assert_eq!(block_on(client.read_counter(counter.binding())).unwrap(), 0);
+
+    let handle = client.create_from_slice(&[7, 0, 0, 0, 1, 2]);
+    assert_eq!(block_on(client.read_counter(handle.binding())).unwrap(), 7);
+
+    let small = client.create_from_slice(&[1, 2]);
+    assert!(block_on(client.read_counter(small.binding())).is_err());
+}
+
+#[test_log::test]
+fn execute_elementwise_addition_raw() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    // SAFETY: The dummy kernel only accesses the first 3 elements of each buffer.
+    unsafe {
+        client.launch_raw(
+            Box::new(KernelTask::new(DummyElementwiseAddition)),
+            CubeCount::Static(1, 1, 1),
+            KernelArguments::new().with_buffers(vec![
+                lhs.binding(),
+                rhs.binding(),
+                out.clone().binding(),
+            ]),
+        )
+    };
+
+    let obtained_resource = client.read_one(out).unwrap().to_vec();
+
+    assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
+}
+
 #[test_log::test]
 #[cfg(feature = "std")]
 fn autotune_basic_addition_execution() {
@@ -100,3 +772,298 @@ fn autotune_basic_multiplication_execution() {
     // If slow kernel was selected it would output [0, 1, 2]
     assert_eq!(obtained_resource, Vec::from([0, 4, 8]));
 }
+
+#[test_log::test]
+fn execute_tiled_launches_a_kernel_per_tile() {
+    let client = test_client(&DummyDevice);
+    let offset = client.empty(12);
+    let out = client.empty(5);
+    client.fill(out.clone(), &[0]).unwrap();
+
+    client
+        .execute_tiled(
+            || Box::new(KernelTask::new(DummyMarkTileOffset)),
+            [5, 1, 1],
+            [2, 1, 1],
+            offset.clone().binding(),
+            KernelArguments::new()
+                .with_buffers(vec![offset.clone().binding(), out.clone().binding()]),
+        )
+        .unwrap();
+
+    assert_eq!(
+        client.read_one(out.clone()).unwrap().to_vec(),
+        [1, 0, 1, 0, 1]
+    );
+
+    let oversized = client.execute_tiled(
+        || Box::new(KernelTask::new(DummyMarkTileOffset)),
+        [5, 1, 1],
+        [u32::MAX, 1, 1],
+        offset.binding(),
+        KernelArguments::new().with_buffers(vec![out.binding()]),
+    );
+    assert!(oversized.is_err());
+}
+
+#[test_log::test]
+fn create_aligned_holds_the_data() {
+    let client = test_client(&DummyDevice);
+    let handle = client
+        .create_aligned(Bytes::from_bytes_vec(vec![1, 2, 3]), 128)
+        .unwrap();
+
+    assert_eq!(client.read_one(handle).unwrap().to_vec(), [1, 2, 3]);
+    assert!(client.empty_aligned(8, 96).is_err());
+}
+
+#[test_log::test]
+fn downcast_server_runs_on_the_concrete_server_type() {
+    let client = test_client(&DummyDevice);
+
+    assert_eq!(client.downcast_server(|_: &mut DummyServer| 1), Some(1));
+}
+
+#[test_log::test]
+fn read_gather_reads_each_range_in_order() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]);
+
+    let ranges = block_on(client.read_gather(handle.clone().binding(), &[(6, 2), (1, 3)])).unwrap();
+    assert_eq!(ranges[0].to_vec(), [6, 7]);
+    assert_eq!(ranges[1].to_vec(), [1, 2, 3]);
+
+    assert!(block_on(client.read_gather(handle.binding(), &[(6, 3)])).is_err());
+}
+
+#[test_log::test]
+fn warm_memory_reserves_the_memory() {
+    let client = test_client(&DummyDevice);
+    client.warm_memory(3 * 1024 * 1024);
+
+    let usage = block_on(client.memory_usage_settled()).unwrap();
+
+    assert!(usage.bytes_reserved >= 3 * 1024 * 1024);
+}
+
+#[test_log::test]
+fn try_empty_fails_past_the_max_alloc_size() {
+    let client = test_client(&DummyDevice);
+
+    let result = client.try_empty(client.max_alloc_size() + 1);
+
+    assert!(matches!(result, Err(IoError::BufferTooBig { .. })));
+}
+
+#[test_log::test]
+fn try_read_returns_the_created_data() {
+    let client = test_client(&DummyDevice);
+    let handle = client
+        .try_create(Bytes::from_bytes_vec(vec![4, 5, 6]))
+        .unwrap();
+
+    let data = client.try_read(vec![handle]).unwrap();
+
+    assert_eq!(data[0].to_vec(), vec![4, 5, 6]);
+}
+
+#[test_log::test]
+fn arena_rollback_frees_the_allocations_after_the_mark() {
+    let client = test_client(&DummyDevice);
+    let arena = client.empty_arena(1024);
+
+    let kept = arena.alloc(20).unwrap();
+    let mark = arena.mark();
+    let used = arena.used();
+    let speculative = arena.alloc(100).unwrap();
+    arena.alloc(200).unwrap();
+
+    arena.rollback(mark);
+
+    assert_eq!(arena.used(), used);
+    assert_eq!(kept.size_in_used(), 20);
+    let reused = arena.alloc(100).unwrap();
+    assert_eq!(reused.offset_start, speculative.offset_start);
+    assert!(arena.alloc(1024).is_err());
+}
+
+#[test_log::test]
+fn last_read_path_is_recorded_after_a_read() {
+    let client = test_client(&DummyDevice);
+    let handle = client.create_from_slice(&[1, 2, 3]);
+
+    client.read_one(handle).unwrap();
+
+    assert_eq!(client.last_read_path(), Some(ReadPath::Mapped));
+}
+
+#[test_log::test]
+fn empty_many_returns_a_handle_per_size_in_order() {
+    let client = test_client(&DummyDevice);
+    let sizes = [12, 0, 300, 4];
+
+    let handles = client.empty_many(&sizes);
+
+    let obtained = handles
+        .iter()
+        .map(|handle| handle.size_in_used() as usize)
+        .collect::<Vec<_>>();
+    assert_eq!(obtained, sizes);
+}
+
+#[cfg(feature = "uninit-checks")]
+#[test_log::test]
+fn empty_buffers_are_filled_with_the_uninit_sentinel() {
+    use cubecl_runtime::validation::is_uninitialized;
+
+    let client = test_client(&DummyDevice);
+    let handle = client.empty(64);
+
+    let data = client.read_one(handle).unwrap();
+
+    assert!(is_uninitialized(&data));
+}
+
+#[test_log::test]
+fn stream_processor_runs_the_kernel_on_every_tile_in_order() {
+    let client = test_client(&DummyDevice);
+    let data = (0..10).collect::<Vec<u8>>();
+    let mut tiles = Vec::new();
+
+    StreamProcessor::new(client, 4)
+        .run(
+            &data,
+            |client, input| client.duplicate(input.binding()),
+            |index, bytes| tiles.push((index, bytes.to_vec())),
+        )
+        .unwrap();
+
+    assert_eq!(
+        tiles,
+        vec![
+            (0, vec![0, 1, 2, 3]),
+            (1, vec![4, 5, 6, 7]),
+            (2, vec![8, 9]),
+        ]
+    );
+}
+
+#[test_log::test]
+fn read_stream_yields_the_chunks_in_order() {
+    let client = test_client(&DummyDevice);
+    let data = (0..10).collect::<Vec<u8>>();
+    let handle = client.create_from_slice(&data);
+
+    let mut stream = client
+        .read_stream(handle.clone().binding(), 4)
+        .with_depth(2);
+    assert_eq!(stream.size_hint(), (3, Some(3)));
+    // The chunks hold the contents of the buffer as of the creation of the stream.
+    client.fill(handle, &[0xff]).unwrap();
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut stream).poll_next(cx)
+    })) {
+        chunks.push(chunk.unwrap().to_vec());
+    }
+
+    assert_eq!(chunks, [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+}
+
+#[test_log::test]
+fn read_strided_packs_the_rows() {
+    let client = test_client(&DummyDevice);
+    let data = (0..12).collect::<Vec<u8>>();
+    let handle = client.create_from_slice(&data);
+
+    let rows = block_on(client.read_strided(handle.binding(), 3, 2, 4)).unwrap();
+
+    assert_eq!(rows.to_vec(), vec![0, 1, 4, 5, 8, 9]);
+}
+
+#[test_log::test]
+fn launch_after_returns_the_submission_of_its_stream() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let bindings = KernelArguments::new().with_buffers(vec![
+        lhs.binding(),
+        rhs.binding(),
+        out.clone().binding(),
+    ]);
+    let submission = client.launch_after(
+        &[client.submission()],
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        bindings,
+    );
+
+    assert_eq!(submission.stream, client.submission().stream);
+    block_on(client.sync_until(submission)).unwrap();
+    assert_eq!(client.read_one(out).unwrap().to_vec(), vec![4, 5, 6]);
+}
+
+#[test_log::test]
+fn sync_until_waits_on_a_submission() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let bindings = KernelArguments::new().with_buffers(vec![
+        lhs.clone().binding(),
+        rhs.clone().binding(),
+        out.clone().binding(),
+    ]);
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        bindings,
+    );
+    let first = client.submission();
+    let second = client.submission();
+    assert!(second.index > first.index);
+
+    block_on(client.sync_until(first)).unwrap();
+    block_on(client.sync_until(second)).unwrap();
+    // Already waited on, which falls back to a full synchronization.
+    block_on(client.sync_until(first)).unwrap();
+    assert_eq!(client.read_one(out).unwrap().to_vec(), vec![4, 5, 6]);
+}
+
+#[test_log::test]
+fn is_compiled_reports_the_launched_kernels() {
+    #[derive(Debug)]
+    struct NeverLaunched;
+
+    impl DummyKernel for NeverLaunched {
+        fn compute(&self, _resources: &mut [&mut BytesResource]) {}
+
+        fn id(&self) -> KernelId {
+            KernelId::new::<Self>()
+        }
+    }
+
+    let client = test_client(&DummyDevice);
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let bindings =
+        KernelArguments::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.binding()]);
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        bindings,
+    );
+
+    let launched: Box<dyn CubeTask<DummyCompiler>> =
+        Box::new(KernelTask::new(DummyElementwiseAddition));
+    let never_launched: Box<dyn CubeTask<DummyCompiler>> = Box::new(KernelTask::new(NeverLaunched));
+
+    assert!(client.is_compiled(&launched));
+    assert!(!client.is_compiled(&never_launched));
+}
@@ -166,6 +166,13 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
             });
         }
 
+        if !value.uniforms.is_empty() {
+            return Err(CompilationError::UnsupportedInstruction {
+                reason: "Uniform buffers are not supported by the SPIR-V compiler".into(),
+                backtrace: BackTrace::capture(),
+            });
+        }
+
         let bindings = value.buffers.clone();
         let mut ext_meta_pos = Vec::new();
         let mut num_ext = 0;
@@ -204,6 +211,12 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
             module: Some(Arc::new(module)),
             optimizer: Some(Arc::new(optimizer)),
             bindings: bindings.iter().map(|it| it.visibility).collect(),
+            spec_constants: self
+                .state
+                .spec_constants
+                .iter()
+                .map(|(_, elem)| *elem)
+                .collect(),
             shared_size,
             uniform_info,
         })
@@ -5,9 +5,16 @@ use std::{
     sync::Arc,
 };
 
-use cubecl_core::prelude::Visibility;
+use cubecl_core::{ir::ConstantValue, prelude::Visibility};
 use cubecl_opt::Optimizer;
-use rspirv::{binary::Disassemble, dr::Module};
+use hashbrown::HashMap;
+use rspirv::{
+    binary::{Assemble, Disassemble},
+    dr::{Module, Operand},
+    grammar::CoreInstructionTable,
+    spirv::{Decoration, Op},
+};
+use variable::ConstVal;
 
 mod arithmetic;
 mod atomic;
@@ -41,6 +48,9 @@ pub struct SpirvKernel {
 
     pub assembled_module: Vec<u32>,
     pub bindings: Vec<Visibility>,
+    /// The elements of the specialization constants, indexed by their id.
+    #[serde(default)]
+    spec_constants: Vec<item::Elem>,
     pub shared_size: usize,
     pub uniform_info: bool,
 }
@@ -56,14 +66,79 @@ impl PartialEq for SpirvKernel {
 pub struct SpirvCacheEntry {
     pub entrypoint_name: String,
     pub kernel: SpirvKernel,
+    /// Missing from entries written by older versions, whose bindings then aren't validated.
+    #[serde(default)]
+    pub buffer_elem_sizes: Vec<usize>,
 }
 
 impl SpirvCacheEntry {
-    pub fn new(entrypoint_name: String, kernel: SpirvKernel) -> Self {
+    pub fn new(
+        entrypoint_name: String,
+        kernel: SpirvKernel,
+        buffer_elem_sizes: Vec<usize>,
+    ) -> Self {
         SpirvCacheEntry {
             entrypoint_name,
             kernel,
+            buffer_elem_sizes,
+        }
+    }
+}
+
+impl SpirvKernel {
+    /// The assembled module with its specialization constants set to `values`, indexed by their
+    /// id.
+    ///
+    /// Passthrough modules can't be specialized with pipeline constants, so the values are
+    /// written as the defaults of the `OpSpecConstant`s instead.
+    pub fn specialize(&self, values: &[ConstantValue]) -> Vec<u32> {
+        if self.spec_constants.is_empty() {
+            return self.assembled_module.clone();
         }
+
+        let mut module =
+            rspirv::dr::load_words(&self.assembled_module).expect("Compiled modules should load");
+        let spec_ids = module
+            .annotations
+            .iter()
+            .filter_map(|inst| match (inst.class.opcode, inst.operands.as_slice()) {
+                (
+                    Op::Decorate,
+                    [
+                        Operand::IdRef(target),
+                        Operand::Decoration(Decoration::SpecId),
+                        Operand::LiteralBit32(spec_id),
+                    ],
+                ) => Some((*target, *spec_id as usize)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for inst in module.types_global_values.iter_mut() {
+            let Some(spec_id) = inst.result_id.and_then(|id| spec_ids.get(&id)) else {
+                continue;
+            };
+            let (value, elem) = (values[*spec_id], self.spec_constants[*spec_id]);
+
+            match elem {
+                item::Elem::Bool => {
+                    let opcode = match value.as_bool() {
+                        true => Op::SpecConstantTrue,
+                        false => Op::SpecConstantFalse,
+                    };
+                    inst.class = CoreInstructionTable::get(opcode);
+                }
+                elem => {
+                    let value = ConstVal::from((value, item::Item::Scalar(elem)));
+                    inst.operands = vec![match value {
+                        ConstVal::Bit32(value) => Operand::LiteralBit32(value),
+                        ConstVal::Bit64(value) => Operand::LiteralBit64(value),
+                    }];
+                }
+            }
+        }
+
+        module.assemble()
     }
 }
 
@@ -38,6 +38,8 @@ pub struct LookupTables {
     pub used_builtins: HashMap<BuiltIn, (Word, Item)>,
 
     pub scalars: HashMap<(Id, ir::StorageType), Word>,
+    /// The `OpSpecConstant` of each specialization constant, indexed by its id.
+    pub spec_constants: Vec<(Word, Elem)>,
     pub array_types: HashSet<Word>,
     pub constants: HashMap<(ConstVal, Item), Word>,
     pub bindings: HashMap<Id, Word>,
@@ -160,6 +162,14 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             .map(|(i, arg)| (arg.ty, i as u32))
             .collect();
 
+        let spec_constants = kernel
+            .spec_constants
+            .into_iter()
+            .enumerate()
+            .map(|(spec_id, ty)| self.declare_spec_constant(spec_id as u32, ty))
+            .collect();
+        self.state.spec_constants = spec_constants;
+
         let cube_dims = [kernel.cube_dim.x, kernel.cube_dim.y, kernel.cube_dim.z];
         self.state.cube_dims = cube_dims.iter().map(|dim| self.const_u32(*dim)).collect();
         self.state.cube_size = self.const_u32(cube_dims.iter().product());
@@ -377,6 +387,25 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
         }
     }
 
+    /// Declare a specialization constant, decorated with its id so that
+    /// [`SpirvKernel::specialize`](crate::SpirvKernel::specialize) can set its value.
+    fn declare_spec_constant(&mut self, spec_id: u32, ty: ir::StorageType) -> (Word, Elem) {
+        let elem = self.compile_type(ir::Type::new(ty)).elem();
+        let ty_id = elem.id(self);
+        let id = match elem {
+            Elem::Bool => self.spec_constant_false(ty_id),
+            _ if elem.size() == 8 => self.spec_constant_bit64(ty_id, 0),
+            _ => self.spec_constant_bit32(ty_id, 0),
+        };
+        self.decorate(
+            id,
+            spirv::Decoration::SpecId,
+            [dr::Operand::LiteralBit32(spec_id)],
+        );
+
+        (id, elem)
+    }
+
     pub fn register_const_array(&mut self, arr: ConstArray) {
         let var = ir::Variable::new(
             VariableKind::ConstantArray {
@@ -389,6 +389,12 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                     Variable::CoopMatrix(id, elem)
                 }
             }
+            ir::VariableKind::SpecConstant(id) => {
+                // Uniform and read-only like the scalars of the info buffer.
+                let (id, elem) = self.state.spec_constants[id as usize];
+                Variable::GlobalScalar(id, elem)
+            }
+            ir::VariableKind::UniformArray { .. } => panic!("Uniform buffers not supported."),
             ir::VariableKind::Pipeline { .. } => panic!("Pipeline not supported."),
             ir::VariableKind::BarrierToken { .. } => {
                 panic!("Barrier not supported.")
@@ -0,0 +1,154 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_core::calculate_cube_count_elemwise;
+use cubecl_runtime::server::{Binding, ServerError};
+
+const SEED_XOR: u32 = 0x9e37_79b9;
+const SEED_ADD: u32 = 0x7f4a_7c15;
+
+/// The finalizer of murmur3, spreading every bit of the input over the whole output.
+#[cube]
+fn fmix32(value: u32) -> u32 {
+    let mut hash = value;
+    hash ^= hash >> 16u32;
+    hash *= 0x85eb_ca6bu32;
+    hash ^= hash >> 13u32;
+    hash *= 0xc2b2_ae35u32;
+    hash ^= hash >> 16u32;
+    hash
+}
+
+/// Hashes every word with its position, and combines the hashes with operations that don't
+/// depend on the order of the units, so the words are hashed in parallel.
+#[cube(launch)]
+fn checksum_kernel(input: &Array<u32>, output: &mut Array<Atomic<u32>>) {
+    if ABSOLUTE_POS < input.len() {
+        let word = input[ABSOLUTE_POS];
+        let position = u32::cast_from(ABSOLUTE_POS);
+
+        output[0].fetch_xor(fmix32(word ^ fmix32(position ^ SEED_XOR)));
+        output[1].fetch_add(fmix32(word ^ fmix32(position ^ SEED_ADD)));
+    }
+}
+
+fn fmix32_host(value: u32) -> u32 {
+    let mut hash = value;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+    hash
+}
+
+fn fmix64_host(value: u64) -> u64 {
+    let mut hash = value;
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^= hash >> 33;
+    hash
+}
+
+/// The two lanes accumulated by the words of a buffer.
+#[derive(Default)]
+struct Lanes {
+    xor: u32,
+    add: u32,
+}
+
+impl Lanes {
+    fn push(&mut self, position: u32, word: u32) {
+        self.xor ^= fmix32_host(word ^ fmix32_host(position ^ SEED_XOR));
+        self.add = self
+            .add
+            .wrapping_add(fmix32_host(word ^ fmix32_host(position ^ SEED_ADD)));
+    }
+
+    /// Fold the last bytes of a buffer that don't fill a word, and its size, into the checksum.
+    fn finish(mut self, words: usize, tail: &[u8], size: u64) -> u64 {
+        if !tail.is_empty() {
+            let mut word = [0; 4];
+            word[..tail.len()].copy_from_slice(tail);
+            self.push(words as u32, u32::from_le_bytes(word));
+        }
+
+        fmix64_host((((self.add as u64) << 32) | self.xor as u64) ^ size)
+    }
+}
+
+/// Computes the checksum of the bytes of the `binding` on the device, to detect silent
+/// corruption of a buffer.
+///
+/// Only the two words of the result are read back, along with the last bytes of the buffer when
+/// its size isn't a multiple of 4. The checksum is the same as the one of
+/// [`checksum_of`] on the same bytes.
+pub fn checksum<R: Runtime>(
+    client: &ComputeClient<R>,
+    binding: Binding,
+) -> impl Future<Output = Result<u64, ServerError>> + Send + 'static {
+    let size = binding.size_in_used();
+    let words = (size / 4) as usize;
+    let tail = (size % 4) as usize;
+
+    let output = client.create_from_slice(u32::as_bytes(&[0, 0]));
+
+    if words > 0 {
+        let mut input = binding.clone();
+        input.offset_end = Some(input.offset_end.unwrap_or(0) + tail as u64);
+
+        let cube_dim = CubeDim::new_1d(256);
+        checksum_kernel::launch::<R>(
+            client,
+            calculate_cube_count_elemwise(client, words, cube_dim),
+            cube_dim,
+            unsafe { ArrayArg::from_raw_parts_binding(input, words) },
+            unsafe { ArrayArg::from_raw_parts(output.clone(), 2) },
+        );
+    }
+
+    let lanes = client.read_gather(output.binding(), &[(0, 8)]);
+    let tail = (tail > 0).then(|| client.read_gather(binding, &[(words * 4, tail)]));
+
+    async move {
+        let lanes = lanes.await?;
+        let lanes = u32::from_bytes(&lanes[0]);
+        let tail = match tail {
+            Some(tail) => tail.await?.remove(0).to_vec(),
+            None => Vec::new(),
+        };
+
+        let lanes = Lanes {
+            xor: lanes[0],
+            add: lanes[1],
+        };
+        Ok(lanes.finish(words, &tail, size))
+    }
+}
+
+/// Computes the [checksum] of `bytes` on the host, such as the data uploaded to a buffer.
+pub fn checksum_of(bytes: &[u8]) -> u64 {
+    let words = bytes.len() / 4;
+    let mut lanes = Lanes::default();
+
+    for (position, word) in bytes.chunks_exact(4).enumerate() {
+        lanes.push(
+            position as u32,
+            u32::from_le_bytes(word.try_into().unwrap()),
+        );
+    }
+
+    lanes.finish(words, &bytes[words * 4..], bytes.len() as u64)
+}
+
+/// Whether the [checksum] of the bytes of the `binding` is still `expected`.
+pub fn verify<R: Runtime>(
+    client: &ComputeClient<R>,
+    binding: Binding,
+    expected: u64,
+) -> impl Future<Output = Result<bool, ServerError>> + Send + 'static {
+    let checksum = checksum(client, binding);
+
+    async move { Ok(checksum.await? == expected) }
+}
@@ -19,5 +19,13 @@ pub mod tensor;
 /// Event utilities.
 pub mod event;
 
+/// Checksums of device buffers, to detect silent corruption.
+#[cfg(feature = "checksum")]
+pub mod checksum;
+
+/// Reductions of device buffers to a single value, read back instead of the whole buffer.
+#[cfg(feature = "reduce")]
+pub mod reduce;
+
 #[cfg(feature = "export_tests")]
 pub mod tests;
@@ -0,0 +1,167 @@
+use cubecl::prelude::*;
+use cubecl_common::backtrace::BackTrace;
+use cubecl_core as cubecl;
+use cubecl_core::ir::{ElemType, FloatKind, IntKind};
+use cubecl_runtime::server::{Binding, Handle, ServerError};
+
+/// The number of units of the cubes reducing the buffer.
+const CUBE_SIZE: u32 = 256;
+/// The number of partial results of the first pass at most, so that a single cube reduces them in
+/// the second pass.
+const MAX_CUBES: usize = CUBE_SIZE as usize;
+
+/// The aggregate computed by [`reduce`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ReduceOp {
+    /// The sum of the elements, wrapping on overflow for integers.
+    Sum,
+    /// The smallest element.
+    Min,
+    /// The largest element.
+    Max,
+    /// The mean of the elements, always a float.
+    Mean,
+}
+
+/// The scalar computed by [`reduce`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReduceValue {
+    /// A float.
+    F32(f32),
+    /// An integer.
+    I32(i32),
+}
+
+/// The result of a [`reduce`].
+pub type ReduceResult = Result<ReduceValue, ServerError>;
+
+#[cube]
+fn identity<N: Numeric>(#[comptime] op: ReduceOp) -> N {
+    match op {
+        ReduceOp::Sum | ReduceOp::Mean => N::from_int(0),
+        ReduceOp::Min => N::max_value(),
+        ReduceOp::Max => N::min_value(),
+    }
+}
+
+#[cube]
+fn combine<N: Numeric>(lhs: N, rhs: N, #[comptime] op: ReduceOp) -> N {
+    match op {
+        ReduceOp::Sum | ReduceOp::Mean => lhs + rhs,
+        ReduceOp::Min => min(lhs, rhs),
+        ReduceOp::Max => max(lhs, rhs),
+    }
+}
+
+/// Each unit combines the elements strided by the number of units launched, then the units of
+/// each cube combine their values in shared memory into one partial result per cube.
+#[cube(launch)]
+fn reduce_kernel<N: Numeric>(input: &Array<N>, output: &mut Array<N>, #[comptime] op: ReduceOp) {
+    let stride = CUBE_COUNT * CUBE_DIM as usize;
+    let mut value = identity::<N>(op);
+    let mut index = ABSOLUTE_POS;
+
+    while index < input.len() {
+        value = combine::<N>(value, input[index], op);
+        index += stride;
+    }
+
+    let unit = UNIT_POS as usize;
+    let mut shared = SharedMemory::<N>::new(CUBE_SIZE as usize);
+    shared[unit] = value;
+    sync_cube();
+
+    let mut offset = CUBE_SIZE as usize / 2;
+    while offset > 0 {
+        if unit < offset {
+            shared[unit] = combine::<N>(shared[unit], shared[unit + offset], op);
+        }
+        sync_cube();
+        offset /= 2;
+    }
+
+    if unit == 0 {
+        output[CUBE_POS] = shared[0];
+    }
+}
+
+/// Reduce the `len` elements of the `binding` into a buffer holding the single result.
+fn reduce_passes<N: Numeric, R: Runtime>(
+    client: &ComputeClient<R>,
+    binding: Binding,
+    len: usize,
+    op: ReduceOp,
+) -> Handle {
+    let cube_dim = CubeDim::new_1d(CUBE_SIZE);
+    let cubes = len.div_ceil(CUBE_SIZE as usize).clamp(1, MAX_CUBES);
+    let partials = client.empty(cubes * size_of::<N>());
+
+    reduce_kernel::launch::<N, R>(
+        client,
+        CubeCount::Static(cubes as u32, 1, 1),
+        cube_dim,
+        unsafe { ArrayArg::from_raw_parts_binding(binding, len) },
+        unsafe { ArrayArg::from_raw_parts(partials.clone(), cubes) },
+        op,
+    );
+
+    if cubes == 1 {
+        return partials;
+    }
+
+    let output = client.empty(size_of::<N>());
+    reduce_kernel::launch::<N, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        cube_dim,
+        unsafe { ArrayArg::from_raw_parts(partials, cubes) },
+        unsafe { ArrayArg::from_raw_parts(output.clone(), 1) },
+        op,
+    );
+
+    output
+}
+
+/// Reduces the elements of the `binding` to a single value on the device, reading back only
+/// the result instead of the whole buffer.
+///
+/// Supports `f32` and `i32` elements; the bytes of the binding past the last whole element are
+/// ignored. The [sum](ReduceOp::Sum) and [mean](ReduceOp::Mean) of an empty binding are zero and
+/// `NaN`, its [min](ReduceOp::Min) and [max](ReduceOp::Max) are the largest and smallest values
+/// of the type.
+pub fn reduce<R: Runtime>(
+    client: &ComputeClient<R>,
+    binding: Binding,
+    op: ReduceOp,
+    elem: ElemType,
+) -> impl Future<Output = ReduceResult> + Send + 'static {
+    let len = binding.size_in_used() as usize / 4;
+
+    let output = match elem {
+        ElemType::Float(FloatKind::F32) => Some(reduce_passes::<f32, R>(client, binding, len, op)),
+        ElemType::Int(IntKind::I32) => Some(reduce_passes::<i32, R>(client, binding, len, op)),
+        _ => None,
+    };
+    let read = output.map(|output| client.read_gather(output.binding(), &[(0, 4)]));
+
+    async move {
+        let Some(read) = read else {
+            return Err(ServerError::Generic {
+                reason: alloc::format!("Can't reduce elements of type {elem:?}"),
+                backtrace: BackTrace::capture(),
+            });
+        };
+        let bytes = read.await?.remove(0);
+
+        let value = match elem {
+            ElemType::Float(_) => ReduceValue::F32(f32::from_bytes(&bytes)[0]),
+            _ => ReduceValue::I32(i32::from_bytes(&bytes)[0]),
+        };
+
+        Ok(match (op, value) {
+            (ReduceOp::Mean, ReduceValue::F32(sum)) => ReduceValue::F32(sum / len as f32),
+            (ReduceOp::Mean, ReduceValue::I32(sum)) => ReduceValue::F32(sum as f32 / len as f32),
+            (_, value) => value,
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::checksum::*;
+
+pub fn test_checksum_matches_the_host<R: Runtime>(client: ComputeClient<R>) {
+    for size in [1, 4, 7, 1024, 1027] {
+        let data = (0..size).map(|i| (i * 31 % 251) as u8).collect::<Vec<_>>();
+        let handle = client.create_from_slice(&data);
+
+        let actual = cubecl_common::future::block_on(checksum(&client, handle.binding())).unwrap();
+
+        assert_eq!(actual, checksum_of(&data), "size {size}");
+    }
+}
+
+pub fn test_checksum_detects_a_bit_flip<R: Runtime>(client: ComputeClient<R>) {
+    let data = vec![7u8; 1027];
+    let handle = client.create_from_slice(&data);
+    let expected = checksum_of(&data);
+
+    assert!(cubecl_common::future::block_on(verify(&client, handle.binding(), expected)).unwrap());
+
+    // The first 1024 bytes are hashed by the kernel, the last 3 on the host.
+    for position in [512, 1026] {
+        let mut corrupted = data.clone();
+        corrupted[position] ^= 1;
+        let corrupted = client.create_from_slice(&corrupted);
+
+        assert!(
+            !cubecl_common::future::block_on(verify(&client, corrupted.binding(), expected))
+                .unwrap(),
+            "position {position}"
+        );
+    }
+}
+
+#[macro_export]
+macro_rules! testgen_checksum {
+    () => {
+        mod checksum {
+            use super::*;
+            use $crate::tests::checksum::*;
+
+            #[$crate::tests::test_log::test]
+            fn test_checksum_matches_the_host_on_unaligned_sizes() {
+                let client = TestRuntime::client(&Default::default());
+                test_checksum_matches_the_host::<TestRuntime>(client);
+            }
+
+            #[$crate::tests::test_log::test]
+            fn test_checksum_detects_a_bit_flip_on_the_device_and_in_the_tail() {
+                let client = TestRuntime::client(&Default::default());
+                test_checksum_detects_a_bit_flip::<TestRuntime>(client);
+            }
+        }
+    };
+}
@@ -1,7 +1,9 @@
 /// Re-export for testgen macros.
 pub use test_log;
 
+pub mod checksum;
 pub mod event;
+pub mod reduce;
 pub mod reinterpret_slice;
 pub mod tensor;
 pub mod trigonometry;
@@ -17,6 +19,8 @@ macro_rules! testgen {
             cubecl_std::testgen_reinterpret_slice!();
             cubecl_std::testgen_trigonometry!();
             cubecl_std::testgen_event!();
+            cubecl_std::testgen_checksum!();
+            cubecl_std::testgen_reduce!();
         }
     };
 }
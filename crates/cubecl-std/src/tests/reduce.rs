@@ -0,0 +1,88 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_core::ir::{ElemType, FloatKind, IntKind};
+use cubecl_runtime::server::Handle;
+
+use crate::reduce::*;
+
+fn reduce_sync<R: Runtime>(
+    client: &ComputeClient<R>,
+    handle: &Handle,
+    op: ReduceOp,
+    elem: ElemType,
+) -> ReduceValue {
+    cubecl_common::future::block_on(reduce(client, handle.clone().binding(), op, elem)).unwrap()
+}
+
+pub fn test_reduce_f32<R: Runtime>(client: ComputeClient<R>) {
+    let elem = ElemType::Float(FloatKind::F32);
+    // Larger than a single cube can reduce in one pass.
+    let data = (0..70_000)
+        .map(|i| (i % 100) as f32 - 20.0)
+        .collect::<Vec<_>>();
+    let handle = client.create_from_slice(f32::as_bytes(&data));
+
+    let sum = data.iter().sum::<f32>();
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Sum, elem),
+        ReduceValue::F32(sum)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Min, elem),
+        ReduceValue::F32(-20.0)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Max, elem),
+        ReduceValue::F32(79.0)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Mean, elem),
+        ReduceValue::F32(sum / data.len() as f32)
+    );
+}
+
+pub fn test_reduce_i32<R: Runtime>(client: ComputeClient<R>) {
+    let elem = ElemType::Int(IntKind::I32);
+    // Spans a few cubes, whose partial results fit in a single cube.
+    let data = (0..1000).map(|i| i - 500).collect::<Vec<i32>>();
+    let handle = client.create_from_slice(i32::as_bytes(&data));
+
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Sum, elem),
+        ReduceValue::I32(-500)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Min, elem),
+        ReduceValue::I32(-500)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Max, elem),
+        ReduceValue::I32(499)
+    );
+    assert_eq!(
+        reduce_sync(&client, &handle, ReduceOp::Mean, elem),
+        ReduceValue::F32(-0.5)
+    );
+}
+
+#[macro_export]
+macro_rules! testgen_reduce {
+    () => {
+        mod reduce {
+            use super::*;
+            use $crate::tests::reduce::*;
+
+            #[$crate::tests::test_log::test]
+            fn test_reduce_f32_over_multiple_cubes() {
+                let client = TestRuntime::client(&Default::default());
+                test_reduce_f32::<TestRuntime>(client);
+            }
+
+            #[$crate::tests::test_log::test]
+            fn test_reduce_i32_over_a_few_cubes() {
+                let client = TestRuntime::client(&Default::default());
+                test_reduce_i32::<TestRuntime>(client);
+            }
+        }
+    };
+}
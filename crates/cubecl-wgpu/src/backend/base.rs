@@ -5,13 +5,13 @@ use cubecl_core::MemoryConfiguration;
 use cubecl_core::{
     ExecutionMode, WgpuCompilationOptions, hash::StableHash, server::KernelArguments,
 };
-use cubecl_ir::DeviceProperties;
+use cubecl_ir::{ConstantValue, DeviceProperties};
 use cubecl_runtime::{compiler::CompilationError, id::KernelId};
 use std::{borrow::Cow, sync::Arc};
 use wgpu::{
     Adapter, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
-    ComputePipeline, Device, PipelineLayoutDescriptor, Queue, ShaderModule, ShaderModuleDescriptor,
-    ShaderStages,
+    ComputePipeline, Device, PipelineLayout, PipelineLayoutDescriptor, Queue, ShaderModule,
+    ShaderModuleDescriptor, ShaderStages,
 };
 
 #[cfg(feature = "spirv")]
@@ -32,7 +32,7 @@ impl WgpuServer {
     )]
     #[allow(unused_variables)]
     pub fn load_cached_pipeline(
-        &self,
+        &mut self,
         kernel_id: &KernelId,
         bindings: &KernelArguments,
         mode: ExecutionMode,
@@ -45,8 +45,19 @@ impl WgpuServer {
             if let Some(entry) = cache.get(&key) {
                 log::trace!("Using SPIR-V cache");
 
+                if matches!(mode, ExecutionMode::Checked) {
+                    self.buffer_elem_sizes
+                        .insert(kernel_id.clone(), entry.buffer_elem_sizes.clone());
+                }
+
                 let repr = AutoRepresentationRef::SpirV(&entry.kernel);
-                let module = self.create_module(&entry.entrypoint_name, Some(repr), "", mode)?;
+                let module = self.create_module(
+                    &entry.entrypoint_name,
+                    Some(repr),
+                    "",
+                    mode,
+                    &bindings.spec_constants,
+                )?;
                 let pipeline =
                     self.create_pipeline(&entry.entrypoint_name, Some(repr), module, bindings);
                 Ok(Some(Ok(pipeline)))
@@ -60,12 +71,18 @@ impl WgpuServer {
         res
     }
 
+    /// Create the module of a kernel.
+    ///
+    /// Passthrough modules can't be specialized by the pipeline, so the `spec_constants` are
+    /// written into them. WGSL modules ignore them, the pipeline overrides their constants.
+    #[allow(unused_variables)]
     pub fn create_module(
         &self,
         entrypoint_name: &str,
         repr: Option<AutoRepresentationRef<'_>>,
         source: &str,
         mode: ExecutionMode,
+        spec_constants: &[ConstantValue],
     ) -> Result<ShaderModule, CompilationError> {
         #[allow(unused_assignments)]
         #[cfg(not(target_family = "wasm"))]
@@ -77,7 +94,7 @@ impl WgpuServer {
                 Ok(self.device.create_shader_module_passthrough(
                     wgpu::ShaderModuleDescriptorPassthrough {
                         label: Some(entrypoint_name),
-                        spirv: Some(Cow::Borrowed(&repr.assembled_module)),
+                        spirv: Some(Cow::Owned(repr.specialize(spec_constants))),
                         ..Default::default()
                     },
                 ))
@@ -87,7 +104,7 @@ impl WgpuServer {
                 Ok(self.device.create_shader_module_passthrough(
                     wgpu::ShaderModuleDescriptorPassthrough {
                         label: Some(entrypoint_name),
-                        msl: Some(Cow::Borrowed(source)),
+                        msl: Some(Cow::Owned(metal::specialize_source(source, spec_constants))),
                         num_workgroups: (repr.cube_dim.x, repr.cube_dim.y, repr.cube_dim.z),
                         ..Default::default()
                     },
@@ -145,6 +162,22 @@ impl WgpuServer {
         module: ShaderModule,
         bindings: &KernelArguments,
     ) -> Arc<ComputePipeline> {
+        let layout = self.create_pipeline_layout(repr, bindings);
+        self.specialize_pipeline(
+            entrypoint_name,
+            layout.as_ref(),
+            &module,
+            &bindings.spec_constants,
+        )
+    }
+
+    /// Create the layout of the pipelines of a kernel, or `None` to let WGPU derive it from the
+    /// module.
+    pub fn create_pipeline_layout(
+        &self,
+        repr: Option<AutoRepresentationRef<'_>>,
+        bindings: &KernelArguments,
+    ) -> Option<PipelineLayout> {
         let bindings_info = match repr {
             Some(AutoRepresentationRef::Wgsl(repr)) => Some(wgsl::bindings(repr, bindings)),
             #[cfg(all(feature = "msl", target_os = "macos"))]
@@ -154,7 +187,8 @@ impl WgpuServer {
             _ => None,
         };
 
-        let layout = bindings_info.map(|bindings| {
+        let uniforms = bindings.uniforms.iter();
+        bindings_info.map(|bindings| {
             let (mut bindings, info, uniform_info) = bindings;
             // When slices are shared, it needs to be read-write if ANY of the slices is read-write,
             // and since we can't be sure, we'll assume everything is read-write.
@@ -172,6 +206,7 @@ impl WgpuServer {
                 .map(|visibility| BufferBindingType::Storage {
                     read_only: matches!(visibility, cubecl_runtime::kernel::Visibility::Read),
                 })
+                .chain(uniforms.map(|_| BufferBindingType::Uniform))
                 .chain(info)
                 .enumerate()
                 .map(|(i, ty)| BindGroupLayoutEntry {
@@ -197,18 +232,36 @@ impl WgpuServer {
                     bind_group_layouts: &[Some(&layout)],
                     immediate_size: 0,
                 })
-        });
+        })
+    }
+
+    /// Create a pipeline from the `module`, with its `spec_constants` overridden by id.
+    pub fn specialize_pipeline(
+        &self,
+        entrypoint_name: &str,
+        layout: Option<&PipelineLayout>,
+        module: &ShaderModule,
+        spec_constants: &[ConstantValue],
+    ) -> Arc<ComputePipeline> {
+        let names = (0..spec_constants.len())
+            .map(|id| format!("spec_{id}"))
+            .collect::<Vec<_>>();
+        let constants = names
+            .iter()
+            .zip(spec_constants)
+            .map(|(name, value)| (name.as_str(), value.as_f64()))
+            .collect::<Vec<_>>();
 
         let pipeline = self
             .device
             .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
                 label: Some(entrypoint_name),
-                layout: layout.as_ref(),
-                module: &module,
+                layout,
+                module,
                 entry_point: Some(entrypoint_name),
                 compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &constants,
                     zero_initialize_workgroup_memory: false,
-                    ..Default::default()
                 },
                 cache: None,
             });
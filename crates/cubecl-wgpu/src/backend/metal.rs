@@ -8,7 +8,7 @@ use cubecl_cpp::{
     shared::register_wmma_features,
 };
 use cubecl_ir::{
-    DeviceProperties, Type,
+    ConstantValue, DeviceProperties, Type,
     features::{AtomicUsage, Plane, TypeUsage},
 };
 use wgpu::{
@@ -129,3 +129,15 @@ fn register_cmma(props: &mut DeviceProperties) {
     let combinations = MslDialect::supported_wmma_combinations(&MetalArchitecture::Metal3);
     register_wmma_features(combinations, props);
 }
+
+/// The source of a kernel with the `SPEC_{id}` macros of its specialization constants defined to
+/// the values it's launched with, since passthrough modules can't be specialized by the pipeline.
+pub fn specialize_source(source: &str, spec_constants: &[ConstantValue]) -> String {
+    let defines = spec_constants
+        .iter()
+        .enumerate()
+        .map(|(id, value)| format!("#define SPEC_{id} {value}\n"))
+        .collect::<String>();
+
+    defines + source
+}
@@ -7,6 +7,8 @@ pub enum Variable {
     GlobalInputArray(Id, Item),
     GlobalOutputArray(Id, Item),
     GlobalScalar(Id, Elem),
+    SpecConstant(Id, Elem),
+    UniformArray(Id, Item, u32),
     Constant(ConstantValue, Item),
     LocalMut {
         id: Id,
@@ -88,6 +90,7 @@ impl Variable {
     pub fn is_always_scalar(&self) -> bool {
         match self {
             Variable::GlobalScalar(_, _) => true,
+            Variable::SpecConstant(_, _) => true,
             Variable::Constant(_, _) => true,
             Variable::LocalScalar { .. } => true,
             Variable::Id => true,
@@ -100,6 +103,7 @@ impl Variable {
             Variable::SharedArray(_, _, _) => false,
             Variable::SharedValue(_, _) => false,
             Variable::ConstantArray(_, _, _) => false,
+            Variable::UniformArray(_, _, _) => false,
             Variable::LocalArray(_, _, _) => false,
             Variable::LocalMut { .. } => false,
             Variable::LocalConst { .. } => false,
@@ -151,12 +155,14 @@ impl Variable {
             Self::SharedArray(_, e, _) => *e,
             Self::SharedValue(_, e) => *e,
             Self::ConstantArray(_, e, _) => *e,
+            Self::UniformArray(_, e, _) => *e,
             Self::LocalArray(_, e, _) => *e,
             Self::LocalMut { item, .. } => *item,
             Self::LocalConst { item, .. } => *item,
             Self::Named { item, .. } => *item,
             Self::Constant(_, item) => *item,
             Self::GlobalScalar(_, e) => Item::Scalar(*e),
+            Self::SpecConstant(_, e) => Item::Scalar(*e),
             Self::Id => Item::Scalar(Elem::U32),
             Self::LocalInvocationIndex => Item::Scalar(Elem::U32),
             Self::LocalInvocationIdX => Item::Scalar(Elem::U32),
@@ -349,6 +355,8 @@ impl Display for Variable {
             Variable::GlobalScalar(number, elem) => {
                 write!(f, "info.scalars_{elem}[{number}]")
             }
+            Variable::SpecConstant(number, _) => write!(f, "spec_{number}"),
+            Variable::UniformArray(number, _, _) => write!(f, "uniform_{number}_global"),
             Variable::Constant(val, item) => {
                 match (val, item.elem()) {
                     // naga can't seem to parse literals > i64::MAX or i64::MIN atm.
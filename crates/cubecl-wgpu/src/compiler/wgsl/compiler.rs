@@ -104,6 +104,18 @@ impl WgslCompiler {
             });
         }
 
+        // Arrays in the uniform address space must have a stride of 16 bytes.
+        if let Some(uniform) = value.uniforms.iter().find(|it| it.ty.size() % 16 != 0) {
+            return Err(CompilationError::Validation {
+                reason: format!(
+                    "Uniform array {} has items of {} bytes, which isn't a multiple of 16 bytes",
+                    uniform.id,
+                    uniform.ty.size()
+                ),
+                backtrace: BackTrace::capture(),
+            });
+        }
+
         self.strategy = mode;
         self.kernel_name = value.options.kernel_name.clone();
 
@@ -145,11 +157,24 @@ impl WgslCompiler {
                     self.compile_binding(it)
                 })
                 .collect(),
+            uniforms: value
+                .uniforms
+                .into_iter()
+                .map(|it| wgsl::KernelArg {
+                    location: wgsl::Location::Uniform,
+                    ..self.compile_binding(it)
+                })
+                .collect(),
             scalars: value
                 .scalars
                 .into_iter()
                 .map(|binding| (self.compile_storage_type(binding.ty), binding.count))
                 .collect(),
+            spec_constants: value
+                .spec_constants
+                .into_iter()
+                .map(|ty| self.compile_storage_type(ty))
+                .collect(),
             shared_arrays: self.shared_arrays.clone(),
             shared_values: self.shared_values.clone(),
             constant_arrays: self.const_arrays.clone(),
@@ -271,6 +296,9 @@ impl WgslCompiler {
             cube::VariableKind::GlobalScalar(id) => {
                 wgsl::Variable::GlobalScalar(id, self.compile_storage_type(item.storage_type()))
             }
+            cube::VariableKind::SpecConstant(id) => {
+                wgsl::Variable::SpecConstant(id, self.compile_storage_type(item.storage_type()))
+            }
             cube::VariableKind::LocalMut { id } | cube::VariableKind::Versioned { id, .. } => {
                 wgsl::Variable::LocalMut {
                     id,
@@ -315,6 +343,10 @@ impl WgslCompiler {
                 let item = self.compile_type(item);
                 wgsl::Variable::ConstantArray(id, item, length as u32)
             }
+            cube::VariableKind::UniformArray { id, length } => {
+                let item = self.compile_type(item);
+                wgsl::Variable::UniformArray(id, item, length as u32)
+            }
             cube::VariableKind::LocalArray {
                 id,
                 length,
@@ -891,6 +891,9 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                     Variable::ConstantArray(_, _, length) => {
                         writeln!(f, "{out} = {length}u;")
                     }
+                    Variable::UniformArray(_, _, length) => {
+                        writeln!(f, "{out} = {length}u;")
+                    }
                     Variable::LocalArray(_, _, length) => {
                         writeln!(f, "{out} = {length}u;")
                     }
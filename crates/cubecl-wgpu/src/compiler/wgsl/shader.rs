@@ -5,6 +5,7 @@ use std::fmt::Display;
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Location {
     Storage,
+    Uniform,
     Workgroup,
 }
 
@@ -79,7 +80,9 @@ impl LocalArray {
 #[derive(Debug, Clone)]
 pub struct ComputeShader {
     pub buffers: Vec<KernelArg>,
+    pub uniforms: Vec<KernelArg>,
     pub scalars: Vec<(Elem, usize)>,
+    pub spec_constants: Vec<Elem>,
     pub shared_arrays: Vec<SharedArray>,
     pub shared_values: Vec<SharedValue>,
     pub constant_arrays: Vec<ConstantArray>,
@@ -130,8 +133,9 @@ impl Display for ComputeShader {
         }
 
         Self::format_bindings(f, "buffer", &self.buffers, 0)?;
+        Self::format_bindings(f, "uniform", &self.uniforms, self.buffers.len())?;
 
-        let offset = self.buffers.len();
+        let offset = self.buffers.len() + self.uniforms.len();
 
         if self.info.has_info() {
             f.write_str("struct info_st {\n")?;
@@ -160,6 +164,11 @@ var<{location}, {visibility}> info: info_st;
             )?;
         }
 
+        // Pipeline-overridable constants, set by name when the pipeline is created.
+        for (id, elem) in self.spec_constants.iter().enumerate() {
+            writeln!(f, "override spec_{id}: {elem};")?;
+        }
+
         for array in self.shared_arrays.iter() {
             write!(
                 f,
@@ -312,6 +321,17 @@ impl ComputeShader {
             None => format!("array<{}>", binding.item),
         };
 
+        // Uniforms don't have an access mode, they are always read-only.
+        if binding.location == Location::Uniform {
+            return write!(
+                f,
+                "@group(0)
+@binding({num_entry})
+var<uniform> {name}: {ty};
+\n",
+            );
+        }
+
         let visibility = match binding.visibility {
             #[cfg(exclusive_memory_only)]
             Visibility::Read if !binding.item.elem().is_atomic() => "read",
@@ -335,6 +355,7 @@ impl Display for Location {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Location::Storage => f.write_str("storage"),
+            Location::Uniform => f.write_str("uniform"),
             Location::Workgroup => f.write_str("workgroup"),
         }
     }
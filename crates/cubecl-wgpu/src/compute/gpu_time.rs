@@ -0,0 +1,48 @@
+use cubecl_common::profile::{Duration, Instant};
+use std::sync::{Arc, Mutex};
+
+/// Accumulates the wall-clock time of the submissions of a stream, as they complete.
+///
+/// This doesn't use timestamp queries: a submission is timed from when it was submitted, or from
+/// when the previous submission completed if the GPU was still busy, until the queue reports it
+/// done. The queue only reports it when the device is polled, so the time also covers the wait
+/// until that poll. It is also not per stream, since a submission only completes once the work
+/// submitted before it on the queue, by any stream, completed. This never waits on the GPU, so it
+/// can be [polled](GpuTime::take) every frame.
+#[derive(Debug, Default, Clone)]
+pub struct GpuTime {
+    state: Arc<Mutex<GpuTimeState>>,
+}
+
+#[derive(Debug, Default)]
+struct GpuTimeState {
+    /// The time of the submissions completed since the last poll, if any completed.
+    completed: Option<Duration>,
+    last_done: Option<Instant>,
+}
+
+impl GpuTime {
+    /// Time the last submission made on the `queue`.
+    pub fn track(&self, queue: &wgpu::Queue) {
+        let submitted = Instant::now();
+        let state = self.state.clone();
+
+        queue.on_submitted_work_done(move || {
+            let done = Instant::now();
+            let mut state = state.lock().unwrap();
+            let start = match state.last_done {
+                Some(last_done) if last_done > submitted => last_done,
+                _ => submitted,
+            };
+
+            let time = state.completed.unwrap_or_default() + (done - start);
+            state.completed = Some(time);
+            state.last_done = Some(done);
+        });
+    }
+
+    /// The time of the submissions that completed since the last call, or `None` if none did.
+    pub fn take(&self) -> Option<Duration> {
+        self.state.lock().unwrap().completed.take()
+    }
+}
@@ -8,8 +8,8 @@ use cubecl_ir::MemoryDeviceProperties;
 use cubecl_runtime::{
     logging::ServerLogger,
     memory_management::{
-        ManagedMemoryBinding, ManagedMemoryHandle, MemoryAllocationMode, MemoryHandle,
-        MemoryManagement, MemoryManagementOptions,
+        AccessHint, ManagedMemoryBinding, ManagedMemoryHandle, MemoryAllocationMode, MemoryHandle,
+        MemoryManagement, MemoryManagementOptions, MemoryUsageSnapshot, SharedMemoryManagement,
     },
     storage::ComputeStorage,
 };
@@ -17,7 +17,7 @@ use wgpu::BufferUsages;
 
 #[derive(Debug)]
 pub(crate) struct WgpuMemManager {
-    memory_pool: MemoryManagement<WgpuStorage>,
+    memory_pool: SharedMemoryManagement<WgpuStorage>,
     memory_uniforms: MemoryManagement<WgpuStorage>,
     memory_pool_staging: MemoryManagement<WgpuStorage>,
     uniforms: Vec<ManagedMemoryHandle>,
@@ -29,24 +29,30 @@ impl WgpuMemManager {
         memory_properties: MemoryDeviceProperties,
         memory_config: MemoryConfiguration,
         logger: Arc<ServerLogger>,
+        memory_usage: Arc<MemoryUsageSnapshot>,
+        memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
     ) -> Self {
         // Allocate storage & memory management for the main memory buffers. Any calls
         // to empty() or create() with a small enough size will be allocated from this
         // main memory pool.
-        let memory_main = MemoryManagement::from_configuration(
-            WgpuStorage::new(
-                memory_properties.alignment as usize,
+        let memory_main = match memory_shared {
+            Some(memory_shared) => {
+                // The usage of the shared pools is reported by every server sharing them.
+                let mut memory = memory_shared.lock();
+                memory.set_logger(logger.clone());
+                memory.add_usage_snapshot(memory_usage);
+                core::mem::drop(memory);
+                memory_shared
+            }
+            None => Self::main_memory(
                 device.clone(),
-                BufferUsages::STORAGE
-                    | BufferUsages::COPY_SRC
-                    | BufferUsages::COPY_DST
-                    | BufferUsages::INDIRECT,
-            ),
-            &memory_properties,
-            memory_config,
-            logger.clone(),
-            MemoryManagementOptions::new("Main GPU Memory"),
-        );
+                &memory_properties,
+                memory_config,
+                logger.clone(),
+                MemoryManagementOptions::new("Main GPU Memory").usage_snapshot(memory_usage),
+            )
+            .into(),
+        };
 
         let memory_staging = MemoryManagement::from_configuration(
             WgpuStorage::new(
@@ -84,15 +90,54 @@ impl WgpuMemManager {
         }
     }
 
+    /// Create the memory management for the main memory buffers of the `device`.
+    pub(crate) fn main_memory(
+        device: wgpu::Device,
+        memory_properties: &MemoryDeviceProperties,
+        memory_config: MemoryConfiguration,
+        logger: Arc<ServerLogger>,
+        options: MemoryManagementOptions,
+    ) -> MemoryManagement<WgpuStorage> {
+        MemoryManagement::from_configuration(
+            WgpuStorage::new(
+                memory_properties.alignment as usize,
+                device,
+                // Uniform usage lets kernels bind these buffers as uniforms too.
+                BufferUsages::STORAGE
+                    | BufferUsages::UNIFORM
+                    | BufferUsages::COPY_SRC
+                    | BufferUsages::COPY_DST
+                    | BufferUsages::INDIRECT,
+            ),
+            memory_properties,
+            memory_config,
+            logger,
+            options,
+        )
+    }
+
     pub(crate) fn bind(&mut self, old: ManagedMemoryHandle, new: ManagedMemoryHandle) {
-        self.memory_pool.bind(old, new, 0).unwrap();
+        self.memory_pool.lock().bind(old, new, 0).unwrap();
     }
 
     pub(crate) fn reserve(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
-        match self.memory_pool.reserve(size) {
-            Ok(handle) => Ok(handle),
-            Err(err) => Err(err),
-        }
+        self.reserve_with_hint(size, None)
+    }
+
+    pub(crate) fn reserve_with_hint(
+        &mut self,
+        size: u64,
+        hint: Option<AccessHint>,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.memory_pool.lock().reserve_with_hint(size, hint)
+    }
+
+    pub(crate) fn reserve_aligned(
+        &mut self,
+        size: u64,
+        alignment: u64,
+    ) -> Result<ManagedMemoryHandle, IoError> {
+        self.memory_pool.lock().reserve_aligned(size, alignment)
     }
 
     pub(crate) fn reserve_staging(
@@ -110,8 +155,11 @@ impl WgpuMemManager {
     }
 
     pub(crate) fn get_resource(&mut self, binding: Binding) -> Result<WgpuResource, IoError> {
-        self.memory_pool
-            .get_resource(binding.memory, binding.offset_start, binding.offset_end)
+        self.memory_pool.lock().get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        )
     }
 
     pub(crate) fn reserve_uniform(&mut self, size: u64) -> WgpuResource {
@@ -129,15 +177,23 @@ impl WgpuMemManager {
     }
 
     pub(crate) fn memory_usage(&self) -> cubecl_runtime::memory_management::MemoryUsage {
-        self.memory_pool.memory_usage()
+        self.memory_pool.lock().memory_usage()
+    }
+
+    pub(crate) fn chunk_map(&self) -> Vec<cubecl_runtime::memory_management::ChunkInfo> {
+        self.memory_pool.lock().chunk_map()
+    }
+
+    pub(crate) fn release(&mut self, binding: Binding) -> Result<(), IoError> {
+        self.memory_pool.lock().release(binding.memory)
     }
 
     pub(crate) fn memory_cleanup(&mut self, explicit: bool) {
-        self.memory_pool.cleanup(explicit);
+        self.memory_pool.lock().cleanup(explicit);
     }
 
     pub(crate) fn mode(&mut self, mode: MemoryAllocationMode) {
-        self.memory_pool.mode(mode);
+        self.memory_pool.lock().mode(mode);
     }
 
     pub(crate) fn release_uniforms(&mut self) {
@@ -2,6 +2,7 @@ pub(crate) mod controller;
 
 mod storage;
 
+pub(super) mod gpu_time;
 pub(super) mod mem_manager;
 pub(super) mod poll;
 pub(super) mod schedule;
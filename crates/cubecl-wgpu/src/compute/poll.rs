@@ -1,33 +1,59 @@
 #[cfg(not(target_family = "wasm"))]
 mod _impl {
+    use crate::MapWait;
     use std::thread::JoinHandle;
 
     #[derive(Debug)]
     pub struct WgpuPoll {
         active_handle: std::sync::Arc<()>,
         cancel_sender: std::sync::mpsc::Sender<()>,
-        poll_thread: JoinHandle<()>,
+        /// No thread is spawned when the application polls the device itself.
+        poll_thread: Option<JoinHandle<()>>,
+        /// The device to poll when blocking, set when no thread polls it.
+        manual_device: Option<wgpu::Device>,
     }
 
     impl WgpuPoll {
-        pub fn new(device: wgpu::Device) -> Self {
+        pub fn new(device: wgpu::Device, map_wait: MapWait) -> Self {
             let active_handle = std::sync::Arc::new(());
             let thread_check = active_handle.clone();
 
             let (cancel_sender, cancel_receiver) = std::sync::mpsc::channel();
+            if map_wait == MapWait::Manual {
+                return Self {
+                    active_handle,
+                    cancel_sender,
+                    poll_thread: None,
+                    manual_device: Some(device),
+                };
+            }
+
             let poll_thread = std::thread::spawn(move || {
                 loop {
                     // Check whether the WgpuPoll, this thread, and something else is holding
                     // a handle.
                     if std::sync::Arc::strong_count(&thread_check) > 2 {
-                        if let Err(e) = device.poll(wgpu::PollType::Wait {
-                            submission_index: None, // Wait for most recent
-                            timeout: None,
-                        }) {
+                        let poll_type = match map_wait {
+                            MapWait::Block => wgpu::PollType::Wait {
+                                submission_index: None, // Wait for most recent
+                                timeout: None,
+                            },
+                            MapWait::Spin | MapWait::PollInterval(_) | MapWait::Manual => {
+                                wgpu::PollType::Poll
+                            }
+                        };
+
+                        if let Err(e) = device.poll(poll_type) {
                             log::warn!(
                                 "wgpu: requested wait timed out before the submission was completed during sync. ({e})"
                             )
                         }
+
+                        match map_wait {
+                            MapWait::Spin => core::hint::spin_loop(),
+                            MapWait::PollInterval(interval) => std::thread::sleep(interval),
+                            MapWait::Block | MapWait::Manual => {}
+                        }
                     } else {
                         // Do not cancel thread while someone still needs to poll.
                         if cancel_receiver.try_recv().is_ok() {
@@ -43,23 +69,53 @@ mod _impl {
             Self {
                 active_handle,
                 cancel_sender,
-                poll_thread,
+                poll_thread: Some(poll_thread),
+                manual_device: None,
+            }
+        }
+
+        /// Block on a future completing with the submitted work, e.g. a sync of the stream.
+        ///
+        /// Without a polling thread, the device is waited on first, since the future would
+        /// otherwise only complete once the application polls the device.
+        pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+            if let Some(device) = &self.manual_device {
+                wait_submitted(device);
             }
+            cubecl_common::future::block_on(future)
         }
         /// Get a handle, as long as it's alive the polling will be active.
         pub fn start_polling(&self) -> std::sync::Arc<()> {
             let handle = self.active_handle.clone();
-            self.poll_thread.thread().unpark();
+            if let Some(poll_thread) = &self.poll_thread {
+                poll_thread.thread().unpark();
+            }
             handle
         }
     }
 
+    /// Wait on the device until the most recent submission completes, running the callbacks of
+    /// the work it completes.
+    pub fn wait_submitted(device: &wgpu::Device) {
+        let poll_type = wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        };
+        if let Err(e) = device.poll(poll_type) {
+            log::warn!("wgpu: requested wait timed out before the submission was completed. ({e})")
+        }
+    }
+
     impl Drop for WgpuPoll {
         fn drop(&mut self) {
+            let Some(poll_thread) = &self.poll_thread else {
+                return;
+            };
+
             self.cancel_sender
                 .send(())
                 .expect("Failed to shutdown polling thread.");
-            self.poll_thread.thread().unpark();
+            poll_thread.thread().unpark();
         }
     }
 }
@@ -67,16 +123,23 @@ mod _impl {
 // On Wasm, the browser handles the polling loop, so we don't need anything.
 #[cfg(target_family = "wasm")]
 mod _impl {
+    use crate::MapWait;
+
     #[derive(Debug)]
     pub struct WgpuPoll {}
     impl WgpuPoll {
-        pub fn new(_device: wgpu::Device) -> Self {
+        pub fn new(_device: wgpu::Device, _map_wait: MapWait) -> Self {
             Self {}
         }
         pub fn start_polling(&self) -> alloc::sync::Arc<()> {
             alloc::sync::Arc::new(())
         }
+        pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+            cubecl_common::future::block_on(future)
+        }
     }
+
+    pub fn wait_submitted(_device: &wgpu::Device) {}
 }
 
 pub(crate) use _impl::*;
@@ -1,4 +1,4 @@
-use crate::{WgpuResource, stream::WgpuStream};
+use crate::{MapWait, WgpuResource, WgpuStorage, stream::WgpuStream};
 use alloc::sync::Arc;
 use cubecl_common::{bytes::Bytes, profile::TimingMethod};
 use cubecl_core::{
@@ -8,6 +8,7 @@ use cubecl_core::{
 use cubecl_ir::MemoryDeviceProperties;
 use cubecl_runtime::{
     logging::ServerLogger,
+    memory_management::{MemoryUsageSnapshot, SharedMemoryManagement},
     stream::{StreamFactory, scheduler::SchedulerStreamBackend},
 };
 
@@ -20,6 +21,18 @@ pub enum ScheduleTask {
         /// The target buffer resource.
         buffer: WgpuResource,
     },
+    /// Represents a task to copy a buffer into another on the device.
+    Copy {
+        /// The buffer to copy from.
+        src: WgpuResource,
+        /// The buffer to copy to.
+        dst: WgpuResource,
+    },
+    /// Represents a task to zero a buffer on the device.
+    Clear {
+        /// The buffer to clear.
+        buffer: WgpuResource,
+    },
     /// Represents a task to execute a compute pipeline.
     Execute {
         /// The compute pipeline to execute.
@@ -35,6 +48,8 @@ impl core::fmt::Debug for ScheduleTask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Write { data, .. } => f.write_fmt(format_args!("Write(bytes={})", data.len())),
+            Self::Copy { src, .. } => f.write_fmt(format_args!("Copy(bytes={})", src.size)),
+            Self::Clear { buffer } => f.write_fmt(format_args!("Clear(bytes={})", buffer.size)),
             Self::Execute {
                 count, resources, ..
             } => f.write_fmt(format_args!(
@@ -52,6 +67,8 @@ pub struct BindingsResource {
     pub resources: Vec<WgpuResource>,
     /// Metadata for uniform bindings.
     pub info: MetadataBindingInfo,
+    /// A bind group already created for the resources, when they are fixed.
+    pub bind_group: Option<wgpu::BindGroup>,
 }
 
 /// Represents a WGPU backend for scheduling tasks on streams.
@@ -70,7 +87,10 @@ pub struct WgpuStreamFactory {
     memory_config: MemoryConfiguration,
     timing_method: TimingMethod,
     tasks_max: usize,
+    map_wait: MapWait,
     logger: Arc<ServerLogger>,
+    memory_usage: Arc<MemoryUsageSnapshot>,
+    memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
     count: u64,
 }
 
@@ -87,13 +107,17 @@ impl StreamFactory for WgpuStreamFactory {
             self.memory_config.clone(),
             self.timing_method,
             self.tasks_max,
+            self.map_wait,
             self.logger.clone(),
+            self.memory_usage.clone(),
+            self.memory_shared.clone(),
         )
     }
 }
 
 impl ScheduledWgpuBackend {
     /// Creates a new `ScheduledWgpuBackend` with the given WGPU device, queue, and configurations.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         device: wgpu::Device,
         queue: wgpu::Queue,
@@ -101,7 +125,10 @@ impl ScheduledWgpuBackend {
         memory_config: MemoryConfiguration,
         timing_method: TimingMethod,
         tasks_max: usize,
+        map_wait: MapWait,
         logger: Arc<ServerLogger>,
+        memory_usage: Arc<MemoryUsageSnapshot>,
+        memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
     ) -> Self {
         Self {
             factory: WgpuStreamFactory {
@@ -111,7 +138,10 @@ impl ScheduledWgpuBackend {
                 memory_config,
                 timing_method,
                 tasks_max,
+                map_wait,
                 logger,
+                memory_usage,
+                memory_shared,
                 count: 0,
             },
         }
@@ -120,7 +150,10 @@ impl ScheduledWgpuBackend {
 
 impl BindingsResource {
     /// Converts metadata and scalar bindings into WGPU resources for a stream.
-    pub fn into_resources(mut self, stream: &mut WgpuStream) -> Vec<WgpuResource> {
+    pub fn into_resources(
+        mut self,
+        stream: &mut WgpuStream,
+    ) -> (Vec<WgpuResource>, Option<wgpu::BindGroup>) {
         // If metadata contains data, create a uniform buffer for it.
         if !self.info.data.is_empty() {
             let info = stream.create_uniform(bytemuck::cast_slice(&self.info.data));
@@ -128,7 +161,7 @@ impl BindingsResource {
         }
 
         // Return the complete list of resources.
-        self.resources
+        (self.resources, self.bind_group)
     }
 }
 
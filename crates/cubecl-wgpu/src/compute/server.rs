@@ -1,6 +1,6 @@
 use super::storage::{WgpuResource, WgpuStorage};
 use crate::schedule::{BindingsResource, ScheduleTask, ScheduledWgpuBackend};
-use crate::{AutoCompiler, AutoRepresentation};
+use crate::{AutoCompiler, AutoRepresentation, MapWait, poll};
 use alloc::sync::Arc;
 use cubecl_common::{
     backtrace::BackTrace,
@@ -8,7 +8,7 @@ use cubecl_common::{
     profile::{ProfileDuration, TimingMethod},
     stream_id::StreamId,
 };
-use cubecl_core::server::{Binding, StreamErrorMode};
+use cubecl_core::server::{BindGroup, BindGroupCache, Binding, PipelineCache, StreamErrorMode};
 use cubecl_core::zspace::Shape;
 use cubecl_core::{
     MemoryConfiguration, WgpuCompilationOptions,
@@ -22,38 +22,85 @@ use cubecl_core::{
 };
 #[cfg(feature = "spirv")]
 use cubecl_core::{cache::CacheOption, compilation_cache::CompilationCache, hash::StableHash};
-use cubecl_ir::MemoryDeviceProperties;
+use cubecl_ir::{ConstantValue, MemoryDeviceProperties};
 use cubecl_runtime::allocator::ContiguousMemoryLayoutPolicy;
-use cubecl_runtime::memory_management::{ManagedMemoryHandle, MemoryUsage};
+use cubecl_runtime::memory_management::{
+    AccessHint, ChunkInfo, ManagedMemoryHandle, MemoryUsage, SharedMemoryManagement,
+};
 use cubecl_runtime::{
-    compiler::CubeTask,
+    compiler::{CompilationError, CubeTask},
     config::GlobalConfig,
+    kernel::Visibility,
     logging::ServerLogger,
     memory_management::MemoryAllocationMode,
+    read_path::ReadPath,
     server::ComputeServer,
     storage::ManagedResource,
     stream::scheduler::{SchedulerMultiStream, SchedulerMultiStreamOptions, SchedulerStrategy},
-    validation::{validate_cube_dim, validate_units},
+    validation::{
+        validate_aliasing, validate_buffer_sizes, validate_cube_dim, validate_uniform_sizes,
+        validate_units,
+    },
 };
 use hashbrown::HashMap;
 use wgpu::ComputePipeline;
 
+/// Whether a buffer the kernel reads can overlap a buffer it writes. With exclusive pages, read
+/// buffers are bound read-only, which WebGPU doesn't allow to alias a writable binding.
+const READ_WRITE_ALIASING: bool = !cfg!(exclusive_memory_only);
+
 /// Wgpu compute server.
 #[derive(Debug)]
 pub struct WgpuServer {
     pub(crate) device: wgpu::Device,
     // A buffer that can be used to store stream id without extra allocations.
     streams_pool: Vec<StreamId>,
-    pipelines: HashMap<KernelId, Arc<ComputePipeline>>,
+    /// Pipelines by kernel id, including the values of its specialization constants.
+    pipelines: PipelineCache<Arc<ComputePipeline>>,
+    /// Modules of kernels with specialization constants, to create pipelines for new values
+    /// without compiling the kernel again.
+    spec_modules: HashMap<KernelId, SpecializableModule>,
+    /// Element sizes of the buffers of kernels compiled in checked mode, to validate bindings.
+    pub(crate) buffer_elem_sizes: HashMap<KernelId, Vec<usize>>,
+    /// Access of kernels compiled in checked mode to their buffers, to validate the buffers bound
+    /// to overlapping memory.
+    buffer_access: HashMap<KernelId, Vec<Visibility>>,
+    bind_groups: BindGroupCache<WgpuBindGroup>,
     scheduler: SchedulerMultiStream<ScheduledWgpuBackend>,
     #[cfg(feature = "spirv")]
     pub(crate) spirv_cache:
         Option<CompilationCache<(u64, StableHash), cubecl_spirv::SpirvCacheEntry>>,
     pub compilation_options: WgpuCompilationOptions,
     pub(crate) backend: wgpu::Backend,
+    map_wait: MapWait,
     pub(crate) utilities: Arc<ServerUtilities<Self>>,
 }
 
+/// A compiled module of a kernel with specialization constants.
+#[derive(Debug)]
+struct SpecializableModule {
+    entrypoint_name: String,
+    module: wgpu::ShaderModule,
+    layout: Option<wgpu::PipelineLayout>,
+    #[cfg(any(feature = "spirv", all(feature = "msl", target_os = "macos")))]
+    mode: ExecutionMode,
+    /// The kernel of passthrough modules, which are created again with the new values written
+    /// into them instead of reusing the module.
+    #[cfg(feature = "spirv")]
+    spirv: Option<cubecl_spirv::SpirvKernel>,
+    #[cfg(all(feature = "msl", target_os = "macos"))]
+    msl: Option<(cubecl_cpp::shared::MslComputeKernel, String)>,
+}
+
+/// The resources of a bind group, resolved when the group is created.
+#[derive(Debug)]
+struct WgpuBindGroup {
+    resources: Vec<WgpuResource>,
+    streams: Vec<StreamId>,
+    /// The bind groups created for launches that only bind the group, by pipeline id.
+    cached: Vec<(KernelId, wgpu::BindGroup)>,
+}
+
 impl ServerCommunication for WgpuServer {
     const SERVER_COMM_ENABLED: bool = false;
 }
@@ -68,10 +115,19 @@ impl WgpuServer {
         device: wgpu::Device,
         queue: wgpu::Queue,
         tasks_max: usize,
+        map_wait: MapWait,
+        memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
         backend: wgpu::Backend,
         timing_method: TimingMethod,
         utilities: ServerUtilities<Self>,
     ) -> Self {
+        // The servers sharing the main memory submit each task right away: a slice freed by one
+        // server can then only be reused by another once the work using it is on the queue they
+        // share, which orders it before the work of the other server.
+        let tasks_max = match memory_shared {
+            Some(_) => 1,
+            None => tasks_max,
+        };
         let backend_scheduler = ScheduledWgpuBackend::new(
             device.clone(),
             queue.clone(),
@@ -79,7 +135,10 @@ impl WgpuServer {
             memory_config,
             timing_method,
             tasks_max,
+            map_wait,
             utilities.logger.clone(),
+            utilities.memory_usage.clone(),
+            memory_shared,
         );
 
         let config = GlobalConfig::get();
@@ -89,7 +148,11 @@ impl WgpuServer {
             compilation_options,
             streams_pool: Vec::new(),
             device,
-            pipelines: HashMap::new(),
+            pipelines: PipelineCache::default(),
+            spec_modules: HashMap::new(),
+            buffer_elem_sizes: HashMap::new(),
+            buffer_access: HashMap::new(),
+            bind_groups: BindGroupCache::default(),
             scheduler: SchedulerMultiStream::new(
                 utilities.logger.clone(),
                 backend_scheduler,
@@ -113,6 +176,7 @@ impl WgpuServer {
                 }
             },
             backend,
+            map_wait,
             utilities: Arc::new(utilities),
         }
     }
@@ -120,9 +184,9 @@ impl WgpuServer {
     fn prepare_bindings(&mut self, bindings: KernelArguments) -> Result<BindingsResource, IoError> {
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
-        let mut resources = Vec::with_capacity(bindings.buffers.len());
+        let mut resources = Vec::with_capacity(bindings.buffers.len() + bindings.uniforms.len());
 
-        for b in bindings.buffers.into_iter() {
+        for b in bindings.buffers.into_iter().chain(bindings.uniforms) {
             let stream = self.scheduler.stream(&b.stream);
             let resource = stream.mem_manage.get_resource(b)?;
             resources.push(resource);
@@ -131,6 +195,7 @@ impl WgpuServer {
         Ok(BindingsResource {
             resources,
             info: bindings.info,
+            bind_group: None,
         })
     }
 
@@ -143,22 +208,69 @@ impl WgpuServer {
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
 
-        if let Some(pipeline) = self.pipelines.get(&kernel_id) {
+        validate_uniform_sizes(
+            &self.utilities.properties,
+            bindings.uniforms.iter().map(|b| b.size_in_used()),
+        )?;
+
+        if let Some(elem_sizes) = self.buffer_elem_sizes.get(&kernel_id) {
+            validate_buffer_sizes(
+                elem_sizes,
+                bindings.buffers.iter().map(|b| b.size_in_used()),
+            )?;
+        }
+
+        if let Some(access) = self.buffer_access.get(&kernel_id) {
+            validate_aliasing(access, &bindings.buffers, READ_WRITE_ALIASING)?;
+        }
+
+        let pipeline_id = kernel_id.clone().spec_constants(&bindings.spec_constants);
+
+        if let Some(pipeline) = self.pipelines.get(&pipeline_id) {
             return Ok(pipeline.clone());
         }
 
+        if let Some(module) = self.spec_modules.get(&kernel_id) {
+            let shader = self.specialize_module(module, &bindings.spec_constants)?;
+            let pipeline = self.specialize_pipeline(
+                &module.entrypoint_name,
+                module.layout.as_ref(),
+                &shader,
+                &bindings.spec_constants,
+            );
+            self.cache_pipeline(pipeline_id, pipeline.clone());
+            return Ok(pipeline);
+        }
+
         let cached = self.load_cached_pipeline(&kernel_id, bindings, mode)?;
 
         if let Some(Ok(pipeline)) = cached {
-            self.pipelines.insert(kernel_id, pipeline.clone());
+            if let Some(elem_sizes) = self.buffer_elem_sizes.get(&kernel_id) {
+                validate_buffer_sizes(
+                    elem_sizes,
+                    bindings.buffers.iter().map(|b| b.size_in_used()),
+                )?;
+            }
+            self.cache_pipeline(pipeline_id, pipeline.clone());
             return Ok(pipeline);
         }
 
         validate_cube_dim(&self.utilities.properties, &kernel_id)?;
         validate_units(&self.utilities.properties, &kernel_id)?;
 
+        // Taken before the kernel is consumed by the compiler.
+        let access: Option<Vec<_>> = match mode {
+            ExecutionMode::Checked => kernel
+                .layout()
+                .map(|layout| layout.buffers.iter().map(|arg| arg.access).collect()),
+            _ => None,
+        };
+
         let mut compiler = compiler(self.backend, &self.compilation_options);
         let mut compiled = compiler.compile(self, kernel, mode)?;
+        // SPIR-V kernels are created from their representation, so only text sources are
+        // affected.
+        self.utilities.source_transform.apply(&mut compiled);
 
         if self.scheduler.logger.compilation_activated() {
             compiled.debug_info = Some(DebugInformation::new(
@@ -170,6 +282,20 @@ impl WgpuServer {
 
         self.validate_shared(&compiled.repr)?;
 
+        if matches!(mode, ExecutionMode::Checked) {
+            validate_buffer_sizes(
+                &compiled.buffer_elem_sizes,
+                bindings.buffers.iter().map(|b| b.size_in_used()),
+            )?;
+            self.buffer_elem_sizes
+                .insert(kernel_id.clone(), compiled.buffer_elem_sizes.clone());
+        }
+
+        if let Some(access) = access {
+            validate_aliasing(&access, &bindings.buffers, READ_WRITE_ALIASING)?;
+            self.buffer_access.insert(kernel_id.clone(), access);
+        }
+
         // /!\ Do not delete the following commented code.
         // This is useful while working on the metal compiler.
         // Also the errors are printed nicely which is not the case when this is the runtime
@@ -194,9 +320,46 @@ impl WgpuServer {
         //     // std::process::exit(status.code().unwrap());
         // }
         let repr = compiled.repr.as_ref().map(|it| it.as_ref());
-        let module = self.create_module(&compiled.entrypoint_name, repr, &compiled.source, mode)?;
-        let pipeline = self.create_pipeline(&compiled.entrypoint_name, repr, module, bindings);
-        self.pipelines.insert(kernel_id.clone(), pipeline.clone());
+        let module = self.create_module(
+            &compiled.entrypoint_name,
+            repr,
+            &compiled.source,
+            mode,
+            &bindings.spec_constants,
+        )?;
+        let layout = self.create_pipeline_layout(repr, bindings);
+        let pipeline = self.specialize_pipeline(
+            &compiled.entrypoint_name,
+            layout.as_ref(),
+            &module,
+            &bindings.spec_constants,
+        );
+        self.cache_pipeline(pipeline_id, pipeline.clone());
+
+        if !bindings.spec_constants.is_empty() {
+            self.spec_modules.insert(
+                kernel_id.clone(),
+                SpecializableModule {
+                    entrypoint_name: compiled.entrypoint_name.clone(),
+                    module,
+                    layout,
+                    #[cfg(any(feature = "spirv", all(feature = "msl", target_os = "macos")))]
+                    mode,
+                    #[cfg(feature = "spirv")]
+                    spirv: compiled
+                        .repr
+                        .as_ref()
+                        .and_then(|repr| repr.as_spirv())
+                        .cloned(),
+                    #[cfg(all(feature = "msl", target_os = "macos"))]
+                    msl: compiled
+                        .repr
+                        .as_ref()
+                        .and_then(|repr| repr.as_msl())
+                        .map(|repr| (repr.clone(), compiled.source.clone())),
+                },
+            );
+        }
 
         #[cfg(feature = "spirv")]
         if let Some(Err(key)) = cached
@@ -205,7 +368,11 @@ impl WgpuServer {
             let cache = self.spirv_cache.as_mut().unwrap();
             let result = cache.insert(
                 key,
-                cubecl_spirv::SpirvCacheEntry::new(compiled.entrypoint_name, kernel),
+                cubecl_spirv::SpirvCacheEntry::new(
+                    compiled.entrypoint_name,
+                    kernel,
+                    compiled.buffer_elem_sizes,
+                ),
             );
             if let Err(err) = result {
                 log::warn!("Unable to save the SPIR-V {err:?}");
@@ -215,6 +382,65 @@ impl WgpuServer {
         Ok(pipeline)
     }
 
+    /// Cache the pipeline, dropping the state of the pipelines it evicts.
+    fn cache_pipeline(&mut self, pipeline_id: KernelId, pipeline: Arc<ComputePipeline>) {
+        let evicted = self.pipelines.insert(pipeline_id, pipeline);
+        self.evict_pipelines(evicted);
+    }
+
+    /// Drop the bind groups created for the evicted pipelines, and the modules and validation
+    /// data of the kernels left without any cached pipeline.
+    fn evict_pipelines(&mut self, evicted: Vec<KernelId>) {
+        for pipeline_id in evicted {
+            for group in self.bind_groups.values_mut() {
+                group.cached.retain(|(id, _)| *id != pipeline_id);
+            }
+
+            let kernel_id = pipeline_id.spec_constants(&[]);
+            let still_cached = self
+                .pipelines
+                .ids()
+                .any(|id| id.clone().spec_constants(&[]) == kernel_id);
+
+            if !still_cached {
+                self.spec_modules.remove(&kernel_id);
+                self.buffer_elem_sizes.remove(&kernel_id);
+                self.buffer_access.remove(&kernel_id);
+            }
+        }
+    }
+
+    /// The module of a kernel specialized for the values of its constants.
+    fn specialize_module(
+        &self,
+        module: &SpecializableModule,
+        spec_constants: &[ConstantValue],
+    ) -> Result<wgpu::ShaderModule, CompilationError> {
+        #[cfg(feature = "spirv")]
+        if let Some(kernel) = &module.spirv {
+            return self.create_module(
+                &module.entrypoint_name,
+                Some(crate::AutoRepresentationRef::SpirV(kernel)),
+                "",
+                module.mode,
+                spec_constants,
+            );
+        }
+        #[cfg(all(feature = "msl", target_os = "macos"))]
+        if let Some((kernel, source)) = &module.msl {
+            return self.create_module(
+                &module.entrypoint_name,
+                Some(crate::AutoRepresentationRef::Msl(kernel)),
+                source,
+                module.mode,
+                spec_constants,
+            );
+        }
+
+        let _ = spec_constants; // WGSL modules are specialized by the pipeline.
+        Ok(module.module.clone())
+    }
+
     fn validate_shared(&self, repr: &Option<crate::AutoRepresentation>) -> Result<(), LaunchError> {
         let shared_bytes = repr.as_ref().map(|repr| match repr {
             AutoRepresentation::Wgsl(repr) => repr.shared_memory_bytes(),
@@ -265,10 +491,61 @@ impl ComputeServer for WgpuServer {
         .into())
     }
 
-    fn initialize_memory(&mut self, memory: ManagedMemoryHandle, size: u64, stream_id: StreamId) {
+    fn initialize_memory(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
         let stream = self.scheduler.stream(&stream_id);
-        let reserved = stream.empty(size).unwrap();
+        let reserved = stream.empty(size)?;
         stream.mem_manage.bind(reserved, memory);
+        Ok(())
+    }
+
+    fn set_max_in_flight(&mut self, max_tasks: usize, stream_id: StreamId) {
+        let stream = self.scheduler.stream(&stream_id);
+        stream.set_max_in_flight(max_tasks);
+    }
+
+    fn set_pipeline_cache_size(&mut self, size: usize) {
+        let evicted = self.pipelines.set_capacity(size);
+        self.evict_pipelines(evicted);
+    }
+
+    fn pipeline_cache_stats(&mut self) -> (usize, usize) {
+        self.pipelines.stats()
+    }
+
+    fn is_compiled(&mut self, kernel_id: &KernelId) -> bool {
+        // Specializing the module of a kernel for new constant values doesn't compile it again.
+        self.pipelines.contains(kernel_id) || self.spec_modules.contains_key(kernel_id)
+    }
+
+    fn initialize_memory_with_hint(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        hint: AccessHint,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let stream = self.scheduler.stream(&stream_id);
+        let reserved = stream.mem_manage.reserve_with_hint(size, Some(hint))?;
+        stream.mem_manage.bind(reserved, memory);
+        Ok(())
+    }
+
+    fn initialize_memory_aligned(
+        &mut self,
+        memory: ManagedMemoryHandle,
+        size: u64,
+        alignment: u64,
+        stream_id: StreamId,
+    ) -> Result<(), IoError> {
+        let stream = self.scheduler.stream(&stream_id);
+        let reserved = stream.mem_manage.reserve_aligned(size, alignment)?;
+        stream.mem_manage.bind(reserved, memory);
+        Ok(())
     }
 
     fn read(
@@ -301,7 +578,15 @@ impl ComputeServer for WgpuServer {
         self.scheduler.execute_streams(streams);
 
         let stream = self.scheduler.stream(&stream_id);
-        stream.read_resources(resources)
+        let read = stream.read_resources(resources);
+        let utilities = self.utilities.clone();
+
+        Box::pin(async move {
+            let data = read.await?;
+            utilities.read_path.record(ReadPath::Staged);
+
+            Ok(data)
+        })
     }
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor, Bytes)>, stream_id: StreamId) {
@@ -331,6 +616,58 @@ impl ComputeServer for WgpuServer {
         }
     }
 
+    fn fill(&mut self, binding: Binding, pattern: Vec<u8>, stream_id: StreamId) {
+        // Only zeroing has a device-side command, other patterns are uploaded from the host.
+        if pattern.iter().any(|byte| *byte != 0) {
+            let size = binding.size_in_used() as usize;
+            let data = pattern.into_iter().cycle().take(size).collect::<Vec<_>>();
+            let descriptor = CopyDescriptor::new(binding, [size].into(), [1].into(), 1);
+
+            self.write(vec![(descriptor, Bytes::from_bytes_vec(data))], stream_id);
+            return;
+        }
+
+        let streams = [binding.stream];
+        let stream = self.scheduler.stream(&binding.stream);
+        let buffer = match stream.mem_manage.get_resource(binding) {
+            Ok(buffer) => buffer,
+            Err(err) => {
+                stream.error(ServerError::Io(err));
+                return;
+            }
+        };
+
+        self.scheduler
+            .register(stream_id, ScheduleTask::Clear { buffer }, &streams);
+    }
+
+    fn copy_buffer(&mut self, src: Binding, dst: Binding, stream_id: StreamId) {
+        let streams = [src.stream, dst.stream];
+        let src = self
+            .scheduler
+            .stream(&src.stream)
+            .mem_manage
+            .get_resource(src);
+        let dst = self
+            .scheduler
+            .stream(&dst.stream)
+            .mem_manage
+            .get_resource(dst);
+
+        let (src, dst) = match (src, dst) {
+            (Ok(src), Ok(dst)) => (src, dst),
+            (Err(err), _) | (_, Err(err)) => {
+                self.scheduler
+                    .stream(&stream_id)
+                    .error(ServerError::Io(err));
+                return;
+            }
+        };
+
+        self.scheduler
+            .register(stream_id, ScheduleTask::Copy { src, dst }, &streams);
+    }
+
     fn get_resource(
         &mut self,
         binding: Binding,
@@ -369,6 +706,7 @@ impl ComputeServer for WgpuServer {
         self.streams_pool.clear();
         args.buffers
             .iter()
+            .chain(args.uniforms.iter())
             .for_each(|b| self.streams_pool.push(b.stream));
 
         let resources = match self.prepare_bindings(args) {
@@ -389,6 +727,135 @@ impl ComputeServer for WgpuServer {
         self.scheduler.register(stream_id, task, &self.streams_pool);
     }
 
+    fn create_bind_group(&mut self, group: BindGroup, _stream_id: StreamId) {
+        let mut resources = Vec::with_capacity(group.len());
+        let mut streams = Vec::with_capacity(group.len());
+
+        for binding in group.bindings() {
+            streams.push(binding.stream);
+            let stream = self.scheduler.stream(&binding.stream);
+            match stream.mem_manage.get_resource(binding) {
+                Ok(resource) => resources.push(resource),
+                // The group is resolved again on every launch instead.
+                Err(_) => return,
+            }
+        }
+
+        self.bind_groups.insert(
+            &group,
+            WgpuBindGroup {
+                resources,
+                streams,
+                cached: Vec::new(),
+            },
+        );
+    }
+
+    unsafe fn launch_with_group(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        group: BindGroup,
+        args: KernelArguments,
+        mode: ExecutionMode,
+        stream_id: StreamId,
+    ) {
+        if self.bind_groups.get(group.id()).is_none() {
+            return unsafe {
+                self.launch(kernel, count, args.with_bind_group(&group), mode, stream_id)
+            };
+        }
+
+        let mut args = args.with_bind_group(&group);
+        let mut pipeline_id = kernel.id();
+        pipeline_id.mode(mode);
+        let pipeline_id = pipeline_id.spec_constants(&args.spec_constants);
+        let pipeline = match self.pipeline(kernel, &args, mode) {
+            Ok(val) => val,
+            Err(err) => {
+                let stream = self.scheduler.stream(&stream_id);
+                stream.errors.push(ServerError::Launch(err));
+                return;
+            }
+        };
+
+        // The buffers of the group are already resolved.
+        args.buffers.drain(..group.len());
+
+        let cached = self.bind_groups.get_mut(group.id()).unwrap();
+        self.streams_pool.clear();
+        self.streams_pool.extend(cached.streams.iter().copied());
+        args.buffers
+            .iter()
+            .chain(args.uniforms.iter())
+            .for_each(|b| self.streams_pool.push(b.stream));
+
+        // Without additional buffers or metadata, the whole wgpu bind group is reusable.
+        let bind_group =
+            if args.buffers.is_empty() && args.uniforms.is_empty() && args.info.data.is_empty() {
+                let position = cached
+                    .cached
+                    .iter()
+                    .position(|(cached_pipeline, _)| *cached_pipeline == pipeline_id);
+                let bind_group = match position {
+                    Some(position) => cached.cached[position].1.clone(),
+                    None => {
+                        let entries = cached
+                            .resources
+                            .iter()
+                            .enumerate()
+                            .map(|(i, r)| wgpu::BindGroupEntry {
+                                binding: i as u32,
+                                resource: r.as_wgpu_bind_resource(),
+                            })
+                            .collect::<Vec<_>>();
+                        let bind_group =
+                            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                                label: None,
+                                layout: &pipeline.get_bind_group_layout(0),
+                                entries: &entries,
+                            });
+                        cached.cached.push((pipeline_id, bind_group.clone()));
+                        bind_group
+                    }
+                };
+                Some(bind_group)
+            } else {
+                None
+            };
+        let mut resources = cached.resources.clone();
+
+        for b in args.buffers.into_iter().chain(args.uniforms) {
+            let stream = self.scheduler.stream(&b.stream);
+            match stream.mem_manage.get_resource(b) {
+                Ok(resource) => resources.push(resource),
+                Err(err) => {
+                    let stream = self.scheduler.stream(&stream_id);
+                    stream.errors.push(ServerError::Io(err));
+                    return;
+                }
+            }
+        }
+
+        let task = ScheduleTask::Execute {
+            pipeline,
+            count,
+            resources: BindingsResource {
+                resources,
+                info: args.info,
+                bind_group,
+            },
+        };
+
+        self.scheduler.register(stream_id, task, &self.streams_pool);
+    }
+
+    fn wait_streams(&mut self, dependencies: Vec<StreamId>, _stream_id: StreamId) {
+        // Streams share a single queue, so enqueuing the pending tasks of the dependencies
+        // orders them before the tasks registered next.
+        self.scheduler.execute_streams(dependencies);
+    }
+
     fn flush(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
         self.scheduler.execute_streams(vec![stream_id]);
 
@@ -408,6 +875,38 @@ impl ComputeServer for WgpuServer {
         stream.sync()
     }
 
+    fn fence(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        self.scheduler.execute_streams(vec![stream_id]);
+        self.scheduler.stream(&stream_id).fence()
+    }
+
+    fn poll_gpu_time(&mut self, stream_id: StreamId) -> Option<core::time::Duration> {
+        self.scheduler.stream(&stream_id).poll_gpu_time()
+    }
+
+    fn block_on<T>(&mut self, future: DynFut<T>) -> T {
+        // Nothing else polls the device for the server's own waits.
+        if self.map_wait == MapWait::Manual {
+            poll::wait_submitted(&self.device);
+        }
+        cubecl_common::future::block_on(future)
+    }
+
+    fn poll_device(&mut self) -> bool {
+        match self.device.poll(wgpu::PollType::Poll) {
+            Ok(status) => !status.is_queue_empty(),
+            Err(e) => {
+                log::warn!("wgpu: device poll failed ({e})");
+                true
+            }
+        }
+    }
+
+    fn has_pending_work(&mut self, stream_id: StreamId) -> bool {
+        self.scheduler.execute_streams(vec![stream_id]);
+        self.scheduler.stream(&stream_id).has_pending_work()
+    }
+
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
@@ -431,12 +930,28 @@ impl ComputeServer for WgpuServer {
         Ok(stream.mem_manage.memory_usage())
     }
 
+    fn chunk_map(&mut self, stream_id: StreamId) -> Result<Vec<ChunkInfo>, ServerError> {
+        self.scheduler.execute_streams(vec![stream_id]);
+        let stream = self.scheduler.stream(&stream_id);
+        Ok(stream.mem_manage.chunk_map())
+    }
+
     fn memory_cleanup(&mut self, stream_id: StreamId) {
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
         stream.mem_manage.memory_cleanup(true);
     }
 
+    fn release(&mut self, bindings: Vec<Binding>, stream_id: StreamId) {
+        self.scheduler.execute_streams(vec![stream_id]);
+
+        for binding in bindings {
+            let stream = self.scheduler.stream(&binding.stream);
+            // The handles of the memory are unusable either way.
+            let _ = stream.mem_manage.release(binding);
+        }
+    }
+
     fn allocation_mode(&mut self, mode: MemoryAllocationMode, stream_id: StreamId) {
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
@@ -444,7 +959,7 @@ impl ComputeServer for WgpuServer {
     }
 }
 
-fn compiler(backend: wgpu::Backend, options: &WgpuCompilationOptions) -> AutoCompiler {
+pub(crate) fn compiler(backend: wgpu::Backend, options: &WgpuCompilationOptions) -> AutoCompiler {
     let _ = options; // Unused without `spirv` feature
     match backend {
         #[cfg(feature = "spirv")]
@@ -24,7 +24,7 @@ impl core::fmt::Debug for WgpuStorage {
 }
 
 /// The memory resource that can be allocated for wgpu.
-#[derive(new, Debug)]
+#[derive(new, Debug, Clone)]
 pub struct WgpuResource {
     /// The wgpu buffer.
     pub buffer: wgpu::Buffer,
@@ -61,6 +61,31 @@ impl WgpuResource {
     }
 }
 
+/// The raw wgpu buffer region of a [`WgpuResource`].
+#[cfg(feature = "interop")]
+#[derive(Debug, Clone)]
+pub struct WgpuRawResource {
+    /// The wgpu buffer the resource is a part of.
+    pub buffer: wgpu::Buffer,
+    /// The offset of the resource in the buffer.
+    pub offset: u64,
+    /// The size of the resource in bytes.
+    pub size: u64,
+}
+
+#[cfg(feature = "interop")]
+impl cubecl_runtime::storage::InteropResource for WgpuResource {
+    type Raw = WgpuRawResource;
+
+    fn as_raw(&self) -> Self::Raw {
+        WgpuRawResource {
+            buffer: self.buffer.clone(),
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+}
+
 /// Keeps actual wgpu buffer references in a hashmap with ids as key.
 impl WgpuStorage {
     /// Create a new storage on the given [device](wgpu::Device).
@@ -1,19 +1,24 @@
-use super::{mem_manager::WgpuMemManager, poll::WgpuPoll, timings::QueryProfiler};
-use crate::{WgpuResource, controller::WgpuAllocController, schedule::ScheduleTask};
+use super::{
+    gpu_time::GpuTime, mem_manager::WgpuMemManager, poll::WgpuPoll, timings::QueryProfiler,
+};
+use crate::{
+    MapWait, WgpuResource, WgpuStorage, controller::WgpuAllocController, schedule::ScheduleTask,
+};
 use cubecl_common::{
     backtrace::BackTrace,
     bytes::Bytes,
-    profile::{ProfileDuration, TimingMethod},
+    profile::{Duration, ProfileDuration, TimingMethod},
 };
 use cubecl_core::{
     CubeCount, MemoryConfiguration,
-    future::{self, DynFut},
+    future::DynFut,
     server::{IoError, ProfileError, ProfilingToken, ServerError, StreamErrorMode},
     zspace::Shape,
 };
 use cubecl_ir::MemoryDeviceProperties;
 use cubecl_runtime::{
-    logging::ServerLogger, memory_management::ManagedMemoryHandle,
+    logging::ServerLogger,
+    memory_management::{ManagedMemoryHandle, MemoryUsageSnapshot, SharedMemoryManagement},
     timestamp_profiler::TimestampProfiler,
 };
 use std::{future::Future, num::NonZero, pin::Pin, sync::Arc};
@@ -25,6 +30,14 @@ enum Timings {
     System(TimestampProfiler),
 }
 
+/// Enough to keep the GPU busy.
+///
+/// - Too much can hang the GPU and create slowdown.
+/// - Too little and GPU utilization is really bad.
+///
+/// TODO: Could be smarter and dynamic based on stats.
+const DEFAULT_MAX_TASKS_IN_FLIGHT: usize = 512;
+
 #[derive(Debug)]
 pub struct WgpuStream {
     pub mem_manage: WgpuMemManager,
@@ -38,14 +51,21 @@ pub struct WgpuStream {
     encoder: wgpu::CommandEncoder,
     poll: WgpuPoll,
     submission_load: SubmissionLoad,
+    /// Number of submitted tasks after which the stream waits on the GPU before submitting more.
+    max_tasks_in_flight: usize,
     /// Number of consecutive `write_buffer` calls without a `queue.submit()`.
     /// Used to prevent wgpu staging buffer pool exhaustion during bulk writes
     /// (e.g. model loading with hundreds of tensors).
     pending_write_count: usize,
+    /// Set while a task is encoded, so that it stays set when encoding panics midway, leaving
+    /// the encoder with a partially recorded task.
+    poisoned: bool,
+    gpu_time: GpuTime,
 }
 
 impl WgpuStream {
     /// Creates a new WGPU stream.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         device: wgpu::Device,
         queue: wgpu::Queue,
@@ -53,7 +73,10 @@ impl WgpuStream {
         memory_config: MemoryConfiguration,
         timing_method: TimingMethod,
         tasks_max: usize,
+        map_wait: MapWait,
         logger: Arc<ServerLogger>,
+        memory_usage: Arc<MemoryUsageSnapshot>,
+        memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
     ) -> Self {
         let timings = if timing_method == TimingMethod::Device {
             Timings::Device(QueryProfiler::new(&queue, &device))
@@ -68,11 +91,17 @@ impl WgpuStream {
             Timings::System(TimestampProfiler::default())
         };
 
-        let poll = WgpuPoll::new(device.clone());
+        let poll = WgpuPoll::new(device.clone(), map_wait);
 
         #[allow(unused_mut)]
-        let mut mem_manage =
-            WgpuMemManager::new(device.clone(), memory_properties, memory_config, logger);
+        let mut mem_manage = WgpuMemManager::new(
+            device.clone(),
+            memory_properties,
+            memory_config,
+            logger,
+            memory_usage,
+            memory_shared,
+        );
 
         Self {
             mem_manage,
@@ -90,7 +119,10 @@ impl WgpuStream {
             tasks_max,
             poll,
             submission_load: SubmissionLoad::default(),
+            max_tasks_in_flight: DEFAULT_MAX_TASKS_IN_FLIGHT,
             pending_write_count: 0,
+            poisoned: false,
+            gpu_time: GpuTime::default(),
         }
     }
 
@@ -100,6 +132,8 @@ impl WgpuStream {
     ///
     /// * `task` - The task to execute.
     pub fn enqueue_task(&mut self, task: ScheduleTask) {
+        self.recover_if_poisoned();
+
         match task {
             ScheduleTask::Write { data, buffer } => {
                 // It is important to flush before writing, as the write operation is inserted
@@ -113,17 +147,91 @@ impl WgpuStream {
                     .ok();
                 self.write_to_buffer(&buffer, &data);
             }
+            ScheduleTask::Copy { src, dst } => {
+                self.copy_buffer(&src, &dst);
+            }
+            ScheduleTask::Clear { buffer } => {
+                self.clear_buffer(&buffer);
+            }
             ScheduleTask::Execute {
                 pipeline,
                 count,
                 resources,
             } => {
-                let resources = resources.into_resources(self);
-                self.register_pipeline(pipeline, resources.iter(), &count);
+                self.poisoned = true;
+                let (resources, bind_group) = resources.into_resources(self);
+                self.register_pipeline(pipeline, resources.iter(), bind_group, &count);
+                self.poisoned = false;
+                self.flush_if_needed();
             }
         }
     }
 
+    /// Discard the tasks encoded since the last submission if encoding one of them panicked,
+    /// starting over with a clean encoder.
+    ///
+    /// The discarded tasks are reported as an error of the stream.
+    fn recover_if_poisoned(&mut self) {
+        if !self.poisoned {
+            return;
+        }
+
+        self.poisoned = false;
+        self.compute_pass = None;
+        self.encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("CubeCL Tasks Encoder"),
+            });
+        self.mem_manage.release_uniforms();
+
+        let discarded = core::mem::take(&mut self.tasks_count);
+        self.errors.push(ServerError::Generic {
+            reason: alloc::format!(
+                "A task panicked while being encoded, discarding the {discarded} tasks encoded since the last submission"
+            ),
+            backtrace: BackTrace::capture(),
+        });
+    }
+
+    /// Copy the contents of the `src` buffer into the `dst` buffer.
+    fn copy_buffer(&mut self, src: &WgpuResource, dst: &WgpuResource) {
+        if src.size == 0 {
+            return;
+        }
+
+        self.compute_pass = None;
+        self.tasks_count += 1;
+
+        // Copies have to be 4 byte aligned, which stays within the buffers since memory is
+        // 32 bytes aligned (see WgpuStorage).
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let aligned_len = src.size.div_ceil(align) * align;
+        self.encoder.copy_buffer_to_buffer(
+            &src.buffer,
+            src.offset,
+            &dst.buffer,
+            dst.offset,
+            aligned_len,
+        );
+    }
+
+    /// Zero the contents of the `buffer`.
+    fn clear_buffer(&mut self, buffer: &WgpuResource) {
+        if buffer.size == 0 {
+            return;
+        }
+
+        self.compute_pass = None;
+        self.tasks_count += 1;
+
+        // Same alignment as the copies, the padding of the buffer is cleared as well.
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let aligned_len = buffer.size.div_ceil(align) * align;
+        self.encoder
+            .clear_buffer(&buffer.buffer, buffer.offset, Some(aligned_len));
+    }
+
     /// Read multiple buffers lazily to [Bytes], potentially using pinned memory.
     ///
     /// # Arguments
@@ -138,6 +246,7 @@ impl WgpuStream {
         &mut self,
         descriptors: Vec<(WgpuResource, Shape, usize)>,
     ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        self.recover_if_poisoned();
         self.compute_pass = None;
         let mut staging_info = Vec::with_capacity(descriptors.len());
         let mut callbacks = Vec::with_capacity(descriptors.len());
@@ -238,7 +347,8 @@ impl WgpuStream {
 
     pub fn start_profile(&mut self) -> Result<ProfilingToken, ServerError> {
         if matches!(self.timings, Timings::System(_)) {
-            cubecl_common::future::block_on(self.sync())?;
+            let sync = self.sync();
+            self.poll.block_on(sync)?;
         } else {
             self.flush(StreamErrorMode {
                 ignore: false,
@@ -274,7 +384,8 @@ impl WgpuStream {
         match &mut self.timings {
             Timings::System(..) => {
                 // Nb: WASM _has_ to use device timing and will panic here if query timestamps are not supported.
-                let result = future::block_on(self.sync());
+                let sync = self.sync();
+                let result = self.poll.block_on(sync);
                 let profiler = self.system_profiler();
 
                 if let Err(err) = result {
@@ -363,6 +474,62 @@ impl WgpuStream {
         })
     }
 
+    /// Submit the pending tasks and return a future completing with them, leaving the errors of
+    /// the stream for the next synchronization to report.
+    pub fn fence(
+        &mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerError>> + Send + 'static>> {
+        self.flush(StreamErrorMode {
+            ignore: true,
+            flush: false,
+        })
+        .ok();
+
+        let (sender, receiver) = async_channel::bounded::<()>(1);
+        let poll = self.poll.start_polling();
+
+        // Registered right away rather than when the future is polled, so that the work submitted
+        // in the meantime isn't waited on.
+        self.queue.on_submitted_work_done(move || {
+            let _ = sender.try_send(());
+            core::mem::drop(poll);
+        });
+
+        Box::pin(async move {
+            let _ = receiver.recv().await;
+            Ok(())
+        })
+    }
+
+    /// Whether tasks are waiting to be submitted, or submitted work hasn't completed yet.
+    pub fn has_pending_work(&self) -> bool {
+        if self.tasks_count > 0 || self.pending_write_count > 0 {
+            return true;
+        }
+
+        // The queue is shared by every stream, so this also covers their work.
+        !self
+            .device
+            .poll(wgpu::PollType::Poll)
+            .is_ok_and(|status| status.is_queue_empty())
+    }
+
+    /// The time the GPU spent on the submissions that completed since the last call, without
+    /// waiting on the ones still running. Returns `None` when none completed.
+    pub fn poll_gpu_time(&mut self) -> Option<Duration> {
+        if let Err(e) = self.device.poll(wgpu::PollType::Poll) {
+            log::warn!("wgpu: poll for completed submissions failed ({e})");
+        }
+
+        self.gpu_time.take()
+    }
+
+    /// Bound the number of submitted tasks that can be in flight before the stream blocks
+    /// on the GPU.
+    pub fn set_max_in_flight(&mut self, max_tasks: usize) {
+        self.max_tasks_in_flight = max_tasks.max(1);
+    }
+
     /// Allocates a new empty buffer using the main memory pool.
     pub fn empty(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
         self.mem_manage.reserve(size)
@@ -454,7 +621,7 @@ impl WgpuStream {
         // Flush when there are too many tasks, or when too many handles are locked.
         // Locked handles should only accumulate in rare circumstances (where uniforms
         // are being created but no work is submitted).
-        if self.tasks_count >= self.tasks_max {
+        if self.tasks_count >= self.tasks_max.min(self.max_tasks_in_flight) {
             let _ = self
                 .flush(StreamErrorMode {
                     ignore: true,
@@ -465,6 +632,8 @@ impl WgpuStream {
     }
 
     pub fn flush(&mut self, mode: StreamErrorMode) -> Result<(), ServerError> {
+        self.recover_if_poisoned();
+
         if self.tasks_count == 0 {
             return self.flush_errors(mode);
         }
@@ -485,9 +654,14 @@ impl WgpuStream {
 
         // This will _first_ fire off all pending write_buffer work.
         let index = self.queue.submit([tasks_encoder.finish()]);
+        self.gpu_time.track(&self.queue);
 
-        self.submission_load
-            .regulate(&self.device, self.tasks_count, index);
+        self.submission_load.regulate(
+            &self.device,
+            self.tasks_count,
+            self.max_tasks_in_flight,
+            index,
+        );
 
         // Cleanup allocations and deallocations.
         self.mem_manage.memory_cleanup(false);
@@ -525,20 +699,13 @@ impl WgpuStream {
         &mut self,
         pipeline: Arc<ComputePipeline>,
         resources: impl Iterator<Item = &'a WgpuResource>,
+        bind_group: Option<wgpu::BindGroup>,
         dispatch: &CubeCount,
     ) {
         if dispatch.is_empty() {
             return;
         }
 
-        let entries = resources
-            .enumerate()
-            .map(|(i, r)| wgpu::BindGroupEntry {
-                binding: i as u32,
-                resource: r.as_wgpu_bind_resource(),
-            })
-            .collect::<Vec<_>>();
-
         // Start a new compute pass if needed. The forget_lifetime allows
         // to store this with a 'static lifetime, but the compute pass must
         // be dropped before the encoder. This isn't unsafe - it's still checked at runtime.
@@ -564,11 +731,19 @@ impl WgpuStream {
 
         self.tasks_count += 1;
 
-        let group_layout = pipeline.get_bind_group_layout(0);
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: None,
-            layout: &group_layout,
-            entries: &entries,
+        let bind_group = bind_group.unwrap_or_else(|| {
+            let entries = resources
+                .enumerate()
+                .map(|(i, r)| wgpu::BindGroupEntry {
+                    binding: i as u32,
+                    resource: r.as_wgpu_bind_resource(),
+                })
+                .collect::<Vec<_>>();
+            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &entries,
+            })
         });
 
         pass.set_pipeline(&pipeline);
@@ -583,7 +758,6 @@ impl WgpuStream {
                 pass.dispatch_workgroups_indirect(&res.buffer, res.offset);
             }
         }
-        self.flush_if_needed();
     }
 
     pub(crate) fn flush_errors_queue(&mut self) -> Vec<ServerError> {
@@ -602,57 +776,41 @@ impl WgpuStream {
 
 #[cfg(not(target_family = "wasm"))]
 mod __submission_load {
+    use alloc::collections::VecDeque;
+
+    /// Tracks the submissions that may still be running on the GPU.
     #[derive(Default, Debug)]
-    pub enum SubmissionLoad {
-        Init {
-            last_index: wgpu::SubmissionIndex,
-            tasks_count_submitted: usize,
-        },
-        #[default]
-        Empty,
+    pub struct SubmissionLoad {
+        /// The submissions in order, with their number of tasks.
+        submissions: VecDeque<(wgpu::SubmissionIndex, usize)>,
+        tasks_count_submitted: usize,
     }
 
     impl SubmissionLoad {
+        /// Register a new submission, then wait on the oldest submissions until at most
+        /// `max_tasks` tasks are in flight.
         pub fn regulate(
             &mut self,
             device: &wgpu::Device,
             tasks_count: usize,
-            mut index: wgpu::SubmissionIndex,
+            max_tasks: usize,
+            index: wgpu::SubmissionIndex,
         ) {
-            match self {
-                SubmissionLoad::Init {
-                    last_index,
-                    tasks_count_submitted,
-                } => {
-                    *tasks_count_submitted += tasks_count;
-
-                    // Enough to keep the GPU busy.
-                    //
-                    // - Too much can hang the GPU and create slowdown.
-                    // - Too little and GPU utilization is really bad.
-                    //
-                    // TODO: Could be smarter and dynamic based on stats.
-                    const MAX_TOTAL_TASKS: usize = 512;
-
-                    if *tasks_count_submitted >= MAX_TOTAL_TASKS {
-                        core::mem::swap(last_index, &mut index);
-                        if let Err(e) = device.poll(wgpu::PollType::Wait {
-                            submission_index: Some(index),
-                            timeout: None,
-                        }) {
-                            log::warn!(
-                                "wgpu: requested wait timed out before the submission was completed during sync. ({e})"
-                            )
-                        }
-                        *tasks_count_submitted = 0;
-                    }
-                }
-                SubmissionLoad::Empty => {
-                    *self = Self::Init {
-                        last_index: index,
-                        tasks_count_submitted: 0,
-                    }
+            self.submissions.push_back((index, tasks_count));
+            self.tasks_count_submitted += tasks_count;
+
+            while self.tasks_count_submitted > max_tasks
+                && let Some((index, tasks_count)) = self.submissions.pop_front()
+            {
+                if let Err(e) = device.poll(wgpu::PollType::Wait {
+                    submission_index: Some(index),
+                    timeout: None,
+                }) {
+                    log::warn!(
+                        "wgpu: requested wait timed out before the submission was completed during sync. ({e})"
+                    )
                 }
+                self.tasks_count_submitted -= tasks_count;
             }
         }
     }
@@ -668,6 +826,7 @@ mod __submission_load_wasm {
             &mut self,
             _device: &wgpu::Device,
             _tasks_count: usize,
+            _max_tasks: usize,
             _index: wgpu::SubmissionIndex,
         ) {
             // Nothing to do.
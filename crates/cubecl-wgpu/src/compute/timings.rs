@@ -21,7 +21,8 @@ pub struct QueryProfiler {
     counter_token: u64,
     counter_query_set: u64,
     cleanups: Vec<QuerySetId>,
-    queue_period: f64,
+    /// Queried when the timestamps are converted, since the period can change on some adapters.
+    queue: wgpu::Queue,
     epoch_tick: u64,
     epoch_instant: Instant,
 }
@@ -145,7 +146,7 @@ impl QueryProfiler {
             current: None,
             timestamps: HashMap::new(),
             init_tokens: Vec::new(),
-            queue_period: queue.get_timestamp_period() as f64,
+            queue: queue.clone(),
             epoch_instant,
             epoch_tick: sync_timestamps,
         }
@@ -233,7 +234,7 @@ impl QueryProfiler {
         poll_signal: Arc<()>,
     ) -> Result<ProfileDuration, ProfileError> {
         if let Some(map_buffer) = map_buffer {
-            let period = self.queue_period;
+            let queue = self.queue.clone();
             let epoch_tick = self.epoch_tick;
             let epoch_instant = self.epoch_instant;
 
@@ -262,7 +263,8 @@ impl QueryProfiler {
                 drop(binding);
 
                 map_buffer.unmap();
-                // Convert to a duration.
+                // Convert to a duration, with the period in nanoseconds per tick.
+                let period = queue.get_timestamp_period() as f64;
                 let start_duration = Duration::from_nanos((data_start as f64 * period) as u64);
                 let end_duration = Duration::from_nanos((data_end as f64 * period) as u64);
 
@@ -1,5 +1,6 @@
 use crate::{
-    AutoCompiler, AutoGraphicsApi, GraphicsApi, WgpuDevice, backend, compute::WgpuServer,
+    AutoCompiler, AutoGraphicsApi, GraphicsApi, WgpuDevice, WgpuStorage, backend,
+    compute::{WgpuServer, compiler, mem_manager::WgpuMemManager},
     contiguous_strides,
 };
 use cubecl_common::device::{Device, DeviceService};
@@ -13,6 +14,7 @@ use cubecl_runtime::allocator::ContiguousMemoryLayoutPolicy;
 #[cfg(not(feature = "vulkan-validate"))]
 use cubecl_runtime::logging::ProfileLevel;
 pub use cubecl_runtime::memory_management::MemoryConfiguration;
+use cubecl_runtime::memory_management::{MemoryManagementOptions, SharedMemoryManagement};
 use cubecl_runtime::{client::ComputeClient, logging::ServerLogger};
 use wgpu::{InstanceFlags, RequestAdapterOptions};
 
@@ -180,6 +182,38 @@ pub struct RuntimeOptions {
     pub tasks_max: usize,
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// How the device is polled while waiting on buffer maps, e.g. for reads.
+    pub map_wait: MapWait,
+    /// The main memory to share with the other servers of the device, see
+    /// [`create_shared_memory`] for what the servers then have in common. The server creates its
+    /// own from the `memory_config` when `None`.
+    pub memory_shared: Option<SharedMemoryManagement<WgpuStorage>>,
+    /// Whether the server is only ever used from one thread at a time, see
+    /// [`ComputeClient::init_exclusive`]. Thread-safe by default.
+    pub exclusive: bool,
+}
+
+/// How the device is polled while a buffer map, such as a read, is pending.
+///
+/// Ignored on WebGPU, where the browser drives the maps.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapWait {
+    /// Poll the device in a busy loop, for the lowest latency at the cost of a CPU core.
+    Spin,
+    /// Poll the device, sleeping for the given duration between polls.
+    PollInterval(core::time::Duration),
+    /// Block on the device until the most recent submission completes.
+    #[default]
+    Block,
+    /// Never poll the device automatically, leaving it to the application to call
+    /// [`ComputeClient::poll_device`], e.g. from its event loop.
+    ///
+    /// The reads, syncs and profiles of the client only complete once the device is polled
+    /// after the work they wait on: blocking on one of them without polling from another thread
+    /// never returns. The server still waits on the device by itself where it blocks internally:
+    /// when throttling the submissions [in flight](ComputeClient::set_max_in_flight), when
+    /// profiling without timestamp queries and in [`fetch_add_u32`](ComputeClient::fetch_add_u32).
+    Manual,
 }
 
 impl Default for RuntimeOptions {
@@ -199,6 +233,9 @@ impl Default for RuntimeOptions {
         Self {
             tasks_max,
             memory_config: MemoryConfiguration::default(),
+            map_wait: MapWait::default(),
+            memory_shared: None,
+            exclusive: false,
         }
     }
 }
@@ -240,8 +277,7 @@ pub fn init_device(setup: WgpuSetup, options: RuntimeOptions) -> WgpuDevice {
     }
 
     let device_id = WgpuDevice::Existing(device_id);
-    let server = create_server(setup, options);
-    let _ = ComputeClient::<WgpuRuntime>::init(&device_id, server);
+    init_client(&device_id, setup, options);
     device_id
 }
 
@@ -267,11 +303,20 @@ pub async fn init_setup_async<G: GraphicsApi>(
 ) -> WgpuSetup {
     let setup = create_setup_for_device(device, G::backend()).await;
     let return_setup = setup.clone();
-    let server = create_server(setup, options);
-    let _ = ComputeClient::<WgpuRuntime>::init(device, server);
+    init_client(device, setup, options);
     return_setup
 }
 
+fn init_client(device: &WgpuDevice, setup: WgpuSetup, options: RuntimeOptions) {
+    let exclusive = options.exclusive;
+    let server = create_server(setup, options);
+
+    let _ = match exclusive {
+        true => ComputeClient::<WgpuRuntime>::init_exclusive(device, server),
+        false => ComputeClient::<WgpuRuntime>::init(device, server),
+    };
+}
+
 pub(crate) fn create_server(setup: WgpuSetup, options: RuntimeOptions) -> WgpuServer {
     let limits = setup.device.limits();
     let adapter_limits = setup.adapter.limits();
@@ -287,10 +332,7 @@ pub(crate) fn create_server(setup: WgpuSetup, options: RuntimeOptions) -> WgpuSe
         adapter_info.subgroup_max_size = 128;
     }
 
-    let mem_props = MemoryDeviceProperties {
-        max_page_size: limits.max_storage_buffer_binding_size,
-        alignment: limits.min_uniform_buffer_offset_alignment as u64,
-    };
+    let mem_props = memory_properties(&setup.device);
     let max_count = adapter_limits.max_compute_workgroups_per_dimension;
     let hardware_props = HardwareProperties {
         load_width: 128,
@@ -312,6 +354,8 @@ pub(crate) fn create_server(setup: WgpuSetup, options: RuntimeOptions) -> WgpuSe
             .max_storage_buffers_per_shader_stage
             .saturating_sub(1),
         max_shared_memory_size: limits.max_compute_workgroup_storage_size as usize,
+        // Set below once the compiler is known.
+        max_uniform_buffer_size: 0,
         max_cube_count: (max_count, max_count, max_count),
         max_units_per_cube: adapter_limits.max_compute_invocations_per_workgroup,
         max_cube_dim: (
@@ -367,6 +411,14 @@ pub(crate) fn create_server(setup: WgpuSetup, options: RuntimeOptions) -> WgpuSe
         &options.memory_config,
     );
 
+    // Only the wgsl compiler lowers uniform bindings, the passthrough compilers don't support them.
+    if matches!(
+        compiler(setup.backend, &compilation_options),
+        AutoCompiler::Wgsl(_)
+    ) {
+        device_props.hardware.max_uniform_buffer_size = limits.max_uniform_buffer_binding_size;
+    }
+
     let logger = alloc::sync::Arc::new(ServerLogger::default());
 
     let allocator = ContiguousMemoryLayoutPolicy::new(device_props.memory.alignment as usize);
@@ -377,12 +429,46 @@ pub(crate) fn create_server(setup: WgpuSetup, options: RuntimeOptions) -> WgpuSe
         setup.device.clone(),
         setup.queue,
         options.tasks_max,
+        options.map_wait,
+        options.memory_shared,
         setup.backend,
         time_measurement,
         ServerUtilities::new(device_props, logger, setup.backend, allocator),
     )
 }
 
+/// Create main memory for the servers created on the `setup`, so they share their memory pools
+/// through [`RuntimeOptions::memory_shared`] instead of each reserving their own.
+///
+/// The servers must share the queue of the `setup`, which orders their work. Each of them submits
+/// its tasks right away instead of batching them, so that a slice freed by one server is only
+/// reused by another after the work using it. The memory usage and the memory logs of every
+/// server cover the shared pools, and the memory operations of a client apply to all the servers:
+/// [`memory_cleanup`](ComputeClient::memory_cleanup) frees the unused memory of every server, and
+/// [`allocation_mode`](ComputeClient::allocation_mode) changes the allocation mode of every server.
+pub fn create_shared_memory(
+    setup: &WgpuSetup,
+    memory_config: MemoryConfiguration,
+) -> SharedMemoryManagement<WgpuStorage> {
+    WgpuMemManager::main_memory(
+        setup.device.clone(),
+        &memory_properties(&setup.device),
+        memory_config,
+        alloc::sync::Arc::new(ServerLogger::default()),
+        MemoryManagementOptions::new("Shared GPU Memory"),
+    )
+    .into()
+}
+
+fn memory_properties(device: &wgpu::Device) -> MemoryDeviceProperties {
+    let limits = device.limits();
+
+    MemoryDeviceProperties {
+        max_page_size: limits.max_storage_buffer_binding_size,
+        alignment: limits.min_uniform_buffer_offset_alignment as u64,
+    }
+}
+
 /// Select the wgpu device and queue based on the provided [device](WgpuDevice) and
 /// [backend](wgpu::Backend).
 pub(crate) async fn create_setup_for_device(